@@ -54,8 +54,9 @@
           LIBCLANG_PATH = "${pkgs.llvmPackages.libclang.lib}/lib";
           
           # Bindgen configuration for MinGW cross-compilation
-          # Tell bindgen to use mingw headers, not Linux headers
-          BINDGEN_EXTRA_CLANG_ARGS = pkgs.lib.concatStringsSep " " [
+          # Tell bindgen to use mingw headers, not Linux headers. Scoped to the Windows
+          # target so native builds of the core crate keep using the host headers.
+          BINDGEN_EXTRA_CLANG_ARGS_x86_64_pc_windows_gnu = pkgs.lib.concatStringsSep " " [
             "--target=x86_64-w64-mingw32"
             # Use -isystem to add includes with lower priority than -I
             # This allows the mingw headers to find clang intrinsics
@@ -68,22 +69,19 @@
             "-D_WIN32"
             "-D_WIN64"
           ];
-          
-          # Set target for bindgen
-          TARGET = "x86_64-pc-windows-gnu";
         };
 
         # Build script
         buildScript = pkgs.writeShellScriptBin "build-protossbot" ''
           set -e
           cd protossbot
-          cargo build --target x86_64-pc-windows-gnu --release
+          cargo build-bot --release
         '';
 
         buildDebugScript = pkgs.writeShellScriptBin "build-protossbot-debug" ''
           set -e
           cd protossbot
-          cargo build --target x86_64-pc-windows-gnu
+          cargo build-bot
         '';
 
         cleanScript = pkgs.writeShellScriptBin "clean-protossbot" ''
@@ -94,7 +92,13 @@
 
         checkScript = pkgs.writeShellScriptBin "check-protossbot" ''
           cd protossbot
-          cargo check --target x86_64-pc-windows-gnu
+          cargo check-bot
+        '';
+
+        testScript = pkgs.writeShellScriptBin "test-protossbot" ''
+          set -e
+          cd protossbot
+          cargo test-core
         '';
 
         startScript = pkgs.writeShellScriptBin "start" ''
@@ -167,6 +171,7 @@
             buildDebugScript
             cleanScript
             checkScript
+            testScript
             startScript
             
             # Additional development tools
@@ -190,6 +195,7 @@
             echo "  build-protossbot        - Build release version for Windows"
             echo "  build-protossbot-debug  - Build debug version for Windows"
             echo "  check-protossbot        - Quick check without building"
+            echo "  test-protossbot         - Run the core crate tests natively"
             echo "  clean-protossbot        - Clean build artifacts"
             echo "  start                   - Run the bot with StarCraft"
           '';
@@ -211,7 +217,7 @@
                   (name: value: "export ${name}=\"${value}\"") 
                   shellEnv)}
               
-              cargo build --release --target x86_64-pc-windows-gnu --locked
+              cargo build -p protossbot --release --target x86_64-pc-windows-gnu --locked
             '';
 
             installPhase = ''
@@ -244,6 +250,11 @@
             program = "${checkScript}/bin/check-protossbot";
           };
 
+          test = {
+            type = "app";
+            program = "${testScript}/bin/test-protossbot";
+          };
+
           start = {
             type = "app";
             program = "${startScript}/bin/start";
@@ -0,0 +1,267 @@
+use protossbot_core::{
+  log_debug, log_info,
+  logging::{self, LogTarget},
+  state::game_state::GameState,
+  utils::{
+    army_manager, build_manager,
+    command_recorder::COMMAND_LOG_EXTENSION,
+    defense_manager, detection_manager, expansion_manager, game_stats,
+    results_store::{ResultsStore, RESULTS_PATH},
+    scouting_manager::{self, ScoutingConfig, SCOUTING_CONFIG_PATH},
+    spellcasting, start_location_inference, strategy_recognition, strategy_selection,
+    target_priority::{TargetPriorityTable, TARGET_PRIORITY_PATH},
+    terrain_analysis, worker_management,
+  },
+  web_server::EnemyStrategyData,
+  world::{bwapi_world::BwapiWorld, snapshot::WorldSnapshot},
+};
+use rsbwapi::*;
+use std::sync::{Arc, Mutex};
+
+fn draw_unit_ids(game: &Game) {
+  for unit in game.get_all_units() {
+    if unit.exists() {
+      let pos = unit.get_position();
+      let unit_id = unit.get_id();
+      game.draw_text_map(pos, &format!("{}", unit_id));
+    }
+  }
+}
+
+impl AiModule for ProtosBot {
+  fn on_start(&mut self, game: &Game) {
+    // SAFETY: rsbwapi uses interior mutability (RefCell) for the command queue.
+    // enable_flag only adds a command to the queue.
+    // This cast is safe in the single-threaded BWAPI callback context.
+    unsafe {
+      let game_ptr = game as *const Game as *mut Game;
+      (*game_ptr).enable_flag(Flag::UserInput as i32);
+    }
+
+    let Ok(mut locked_state) = self.game_state.lock() else {
+      return;
+    };
+    let data = locked_state.data.clone();
+
+    logging::set_frame(0);
+    let started = logging::unix_time();
+    if data.mode.writes_debug_files() {
+      logging::open_game_log(&game.map_file_name(), started);
+      let command_log =
+        logging::game_file_path(&game.map_file_name(), started, COMMAND_LOG_EXTENSION);
+      locked_state.commands.log_to(&command_log);
+    }
+    log_info!(
+      LogTarget::Game,
+      "Game started on map: {}",
+      game.map_file_name()
+    );
+
+    locked_state.terrain = terrain_analysis::analyze_game(game);
+    locked_state.target_priorities =
+      TargetPriorityTable::load(&data.config_path(TARGET_PRIORITY_PATH));
+    locked_state.scouting.scout_supply =
+      ScoutingConfig::load(&data.config_path(SCOUTING_CONFIG_PATH)).scout_supply;
+    let (opponent, race) = game_stats::opponent(game);
+    let results = ResultsStore::new(&data.learning_path(RESULTS_PATH)).load();
+    strategy_selection::on_start(&opponent, &race, &results, &mut locked_state);
+    game_stats::on_start(game, &mut locked_state, started);
+
+    if let Some(player) = game.self_() {
+      start_location_inference::initialize(game, &player, &mut locked_state);
+    }
+  }
+
+  fn on_frame(&mut self, game: &Game) {
+    let Ok(mut locked_state) = self.game_state.lock() else {
+      return;
+    };
+
+    let Some(player) = game.self_() else {
+      return;
+    };
+
+    // Apply desired game speed from shared state. Without the web page to change it,
+    // the speed is left to whoever runs the game.
+    if locked_state.data.mode.web_server_enabled() {
+      let desired_speed = self.shared_speed.get();
+      unsafe {
+        let game_ptr = game as *const Game as *mut Game;
+        (*game_ptr).set_local_speed(desired_speed);
+      }
+    }
+
+    logging::set_frame(game.get_frame_count());
+    locked_state.commands.set_frame(game.get_frame_count());
+    locked_state.enemy_memory.refresh(game, &player);
+    start_location_inference::on_frame(game, &mut locked_state);
+    scouting_manager::on_frame(game, &player, &mut locked_state);
+    strategy_recognition::on_frame(game.get_frame_count(), &mut locked_state);
+    expansion_manager::on_frame(game, &player, &mut locked_state);
+    let world = BwapiWorld::new(game, &player);
+    if self.snapshot.is_requested() {
+      // Captured before planning so a replay sees the same inputs
+      self
+        .snapshot
+        .store(WorldSnapshot::capture(&world, &locked_state));
+    }
+    build_manager::on_frame(&world, &mut locked_state);
+    worker_management::assign_idle_workers_to_minerals(&world, &mut locked_state);
+    worker_management::assign_workers_to_gas(&world, &mut locked_state);
+    army_manager::on_frame(game, &player, &mut locked_state);
+    spellcasting::on_frame(game, &player, &mut locked_state);
+    detection_manager::on_frame(game, &player, &mut locked_state);
+    defense_manager::on_frame(game, &player, &mut locked_state);
+    game_stats::on_frame(game, &player, &mut locked_state);
+
+    // Update web server with current build status
+    let stage_name = locked_state
+      .build_stages
+      .get(locked_state.current_stage_index)
+      .map(|s| s.name.clone())
+      .unwrap_or_else(|| "Unknown".to_string());
+    self.build_status.update(
+      stage_name,
+      locked_state.current_stage_index,
+      locked_state.stage_item_status.clone(),
+    );
+    self.enemy_strategy.update(EnemyStrategyData {
+      opening: locked_state.enemy_strategy.opening.name().to_string(),
+      confidence: locked_state.enemy_strategy.confidence,
+      evidence: locked_state.enemy_strategy.evidence.clone(),
+      is_rush: locked_state.enemy_strategy.opening.is_rush(),
+    });
+
+    build_manager::print_debug_build_status(&world, &locked_state);
+    terrain_analysis::draw_debug(game, &locked_state.terrain);
+    start_location_inference::draw_debug(game, &locked_state);
+    army_manager::draw_debug(game, &locked_state);
+    draw_unit_ids(game);
+  }
+
+  fn on_unit_create(&mut self, game: &Game, unit: Unit) {
+    if game.get_frame_count() < 1 {
+      return;
+    }
+    log_debug!(LogTarget::Game, "unit created: {:?}", unit.get_type());
+
+    // Check if the created unit is a building
+    if !unit.get_type().is_building() {
+      return;
+    }
+
+    let Ok(mut locked_state) = self.game_state.lock() else {
+      return;
+    };
+
+    build_manager::on_building_create(unit.get_type(), &mut locked_state);
+  }
+
+  fn on_unit_discover(&mut self, game: &Game, unit: Unit) {
+    self.remember_enemy_unit(game, &unit);
+  }
+
+  fn on_unit_show(&mut self, game: &Game, unit: Unit) {
+    self.remember_enemy_unit(game, &unit);
+  }
+
+  fn on_unit_hide(&mut self, _game: &Game, unit: Unit) {
+    let Ok(mut locked_state) = self.game_state.lock() else {
+      return;
+    };
+
+    locked_state.enemy_memory.on_hide(unit.get_id());
+  }
+
+  fn on_unit_morph(&mut self, game: &Game, unit: Unit) {
+    self.remember_enemy_unit(game, &unit);
+  }
+
+  fn on_unit_destroy(&mut self, game: &Game, unit: Unit) {
+    let Ok(mut locked_state) = self.game_state.lock() else {
+      return;
+    };
+
+    if game
+      .self_()
+      .is_some_and(|player| unit.get_player().get_id() == player.get_id())
+    {
+      locked_state.stats.unit_lost(unit.get_type());
+    }
+    locked_state.enemy_memory.mark_dead(unit.get_id());
+    scouting_manager::on_unit_destroy(&unit, &mut locked_state);
+  }
+
+  fn on_unit_complete(&mut self, game: &Game, unit: Unit) {
+    // Starting units complete on the first frame, they weren't produced
+    if game.get_frame_count() < 1 {
+      return;
+    }
+    let Some(player) = game.self_() else {
+      return;
+    };
+    if unit.get_player().get_id() != player.get_id() {
+      return;
+    }
+
+    let Ok(mut locked_state) = self.game_state.lock() else {
+      return;
+    };
+    locked_state.stats.unit_produced(unit.get_type());
+  }
+
+  fn on_end(&mut self, game: &Game, is_winner: bool) {
+    if is_winner {
+      log_info!(LogTarget::Game, "Victory!");
+    } else {
+      log_info!(LogTarget::Game, "Defeat!");
+    }
+
+    let Ok(mut locked_state) = self.game_state.lock() else {
+      return;
+    };
+    game_stats::on_end(game.get_frame_count(), is_winner, &mut locked_state);
+  }
+}
+
+pub struct ProtosBot {
+  game_state: Arc<Mutex<GameState>>,
+  shared_speed: protossbot_core::web_server::SharedGameSpeed,
+  build_status: protossbot_core::web_server::SharedBuildStatus,
+  enemy_strategy: protossbot_core::web_server::SharedEnemyStrategy,
+  snapshot: protossbot_core::web_server::SharedSnapshot,
+}
+
+impl ProtosBot {
+  fn remember_enemy_unit(&self, game: &Game, unit: &Unit) {
+    let Some(player) = game.self_() else {
+      return;
+    };
+    if !unit.get_player().is_enemy(&player) {
+      return;
+    }
+
+    let Ok(mut locked_state) = self.game_state.lock() else {
+      return;
+    };
+    locked_state
+      .enemy_memory
+      .observe(unit, game.get_frame_count());
+  }
+
+  pub fn new(
+    game_state: Arc<Mutex<GameState>>,
+    shared_speed: protossbot_core::web_server::SharedGameSpeed,
+    build_status: protossbot_core::web_server::SharedBuildStatus,
+    enemy_strategy: protossbot_core::web_server::SharedEnemyStrategy,
+    snapshot: protossbot_core::web_server::SharedSnapshot,
+  ) -> Self {
+    Self {
+      game_state,
+      shared_speed,
+      build_status,
+      enemy_strategy,
+      snapshot,
+    }
+  }
+}
@@ -0,0 +1,54 @@
+mod bot;
+
+use bot::ProtosBot;
+use protossbot_core::{
+  data_paths::{DataPaths, RunMode},
+  log_info,
+  logging::LogTarget,
+  state::game_state::GameState,
+  web_server::{self, SharedBuildStatus, SharedEnemyStrategy, SharedGameSpeed, SharedSnapshot},
+};
+use std::sync::{Arc, Mutex};
+
+fn main() {
+  log_info!(LogTarget::Game, "Starting RustBot...");
+
+  let run_mode = RunMode::detect();
+  log_info!(LogTarget::Game, "Running in {:?} mode", run_mode);
+
+  let game_state = Arc::new(Mutex::new(GameState {
+    data: DataPaths::new(run_mode),
+    ..GameState::default()
+  }));
+  let shared_speed = SharedGameSpeed::new(42); // Default speed (slowest)
+  let build_status = SharedBuildStatus::new();
+  let enemy_strategy = SharedEnemyStrategy::new();
+  let snapshot = SharedSnapshot::new();
+
+  // Start web server in a separate thread, tournaments don't allow opening ports
+  if run_mode.web_server_enabled() {
+    let shared_speed_clone = shared_speed.clone();
+    let build_status_clone = build_status.clone();
+    let enemy_strategy_clone = enemy_strategy.clone();
+    let snapshot_clone = snapshot.clone();
+    std::thread::spawn(move || {
+      let runtime = tokio::runtime::Runtime::new().unwrap();
+      runtime.block_on(web_server::start_web_server(
+        shared_speed_clone,
+        build_status_clone,
+        enemy_strategy_clone,
+        snapshot_clone,
+      ));
+    });
+  }
+
+  rsbwapi::start(move |_game| {
+    ProtosBot::new(
+      game_state.clone(),
+      shared_speed.clone(),
+      build_status.clone(),
+      enemy_strategy.clone(),
+      snapshot.clone(),
+    )
+  });
+}
@@ -0,0 +1,201 @@
+//! Where the bot reads its config and learning data from and where it writes them.
+//!
+//! Tournaments run the bot from the StarCraft folder with `bwapi-data/read`, holding
+//! what earlier games left behind, and `bwapi-data/write`, copied over `read` after each
+//! game. They also forbid opening network ports and cap how much a bot may write, so in
+//! tournament mode the web server stays off and only learning data is written. Set
+//! `PROTOSSBOT_TOURNAMENT` to `1` or `0` to force the mode instead of detecting it from
+//! those folders.
+
+use std::path::Path;
+
+use crate::{log_info, log_warn, logging::LogTarget};
+
+pub const READ_DIR: &str = "bwapi-data/read";
+pub const WRITE_DIR: &str = "bwapi-data/write";
+pub const TOURNAMENT_ENV: &str = "PROTOSSBOT_TOURNAMENT";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RunMode {
+  Development,
+  Tournament,
+}
+
+impl RunMode {
+  pub fn detect() -> Self {
+    let forced = std::env::var(TOURNAMENT_ENV).ok();
+    let has_dirs = Path::new(READ_DIR).is_dir() && Path::new(WRITE_DIR).is_dir();
+    Self::from_env(forced.as_deref(), has_dirs)
+  }
+
+  fn from_env(forced: Option<&str>, has_tournament_dirs: bool) -> Self {
+    match forced.map(str::trim) {
+      Some("0") | Some("false") => RunMode::Development,
+      Some(_) => RunMode::Tournament,
+      None if has_tournament_dirs => RunMode::Tournament,
+      None => RunMode::Development,
+    }
+  }
+
+  pub fn web_server_enabled(&self) -> bool {
+    *self == RunMode::Development
+  }
+
+  /// Debug output like game logs and command logs, kept out of the capped write folder
+  pub fn writes_debug_files(&self) -> bool {
+    *self == RunMode::Development
+  }
+}
+
+#[derive(Clone, Debug)]
+pub struct DataPaths {
+  pub mode: RunMode,
+  read_dir: String,
+  write_dir: String,
+}
+
+impl Default for DataPaths {
+  fn default() -> Self {
+    Self::new(RunMode::Development)
+  }
+}
+
+impl DataPaths {
+  pub fn new(mode: RunMode) -> Self {
+    Self::with_dirs(mode, READ_DIR, WRITE_DIR)
+  }
+
+  pub fn with_dirs(mode: RunMode, read_dir: &str, write_dir: &str) -> Self {
+    Self {
+      mode,
+      read_dir: read_dir.to_string(),
+      write_dir: write_dir.to_string(),
+    }
+  }
+
+  /// Config file to load: the read folder's copy in a tournament when there is one,
+  /// otherwise the file next to the bot. Loaders fall back to built-in defaults when
+  /// neither exists.
+  pub fn config_path(&self, file_name: &str) -> String {
+    if self.mode == RunMode::Tournament {
+      let in_read_dir = format!("{}/{}", self.read_dir, file_name);
+      if Path::new(&in_read_dir).is_file() {
+        return in_read_dir;
+      }
+    }
+    file_name.to_string()
+  }
+
+  /// Learning file the bot both loads and appends to. In a tournament this is the write
+  /// folder's copy, seeded from the read folder so the history carries over to the next
+  /// game; without a usable copy the history starts empty.
+  pub fn learning_path(&self, file_name: &str) -> String {
+    if self.mode == RunMode::Development {
+      return file_name.to_string();
+    }
+
+    let in_write_dir = format!("{}/{}", self.write_dir, file_name);
+    let in_read_dir = format!("{}/{}", self.read_dir, file_name);
+    if let Err(e) = std::fs::create_dir_all(&self.write_dir) {
+      log_warn!(
+        LogTarget::Game,
+        "Could not create {}: {}",
+        self.write_dir,
+        e
+      );
+    }
+    if Path::new(&in_write_dir).is_file() || !Path::new(&in_read_dir).is_file() {
+      return in_write_dir;
+    }
+
+    match std::fs::copy(&in_read_dir, &in_write_dir) {
+      Ok(_) => log_info!(
+        LogTarget::Game,
+        "Carried {} over from {}",
+        file_name,
+        self.read_dir
+      ),
+      Err(e) => log_warn!(
+        LogTarget::Game,
+        "Could not copy {} into {}: {}, starting a new history",
+        in_read_dir,
+        self.write_dir,
+        e
+      ),
+    }
+    in_write_dir
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::fs;
+
+  fn temp_dirs(name: &str) -> (String, String) {
+    let root = std::env::temp_dir().join(format!("protossbot_data_paths_{}", name));
+    let _ = fs::remove_dir_all(&root);
+    let read = root.join("read");
+    let write = root.join("write");
+    fs::create_dir_all(&read).unwrap();
+    fs::create_dir_all(&write).unwrap();
+    (
+      read.to_str().unwrap().to_string(),
+      write.to_str().unwrap().to_string(),
+    )
+  }
+
+  #[test]
+  fn detects_tournament_mode() {
+    assert_eq!(RunMode::from_env(None, true), RunMode::Tournament);
+    assert_eq!(RunMode::from_env(None, false), RunMode::Development);
+    assert_eq!(RunMode::from_env(Some("1"), false), RunMode::Tournament);
+    assert_eq!(RunMode::from_env(Some("0"), true), RunMode::Development);
+    assert!(!RunMode::Tournament.web_server_enabled());
+  }
+
+  #[test]
+  fn development_uses_files_next_to_the_bot() {
+    let paths = DataPaths::default();
+
+    assert_eq!(paths.config_path("config.yaml"), "config.yaml");
+    assert_eq!(paths.learning_path("results.jsonl"), "results.jsonl");
+  }
+
+  #[test]
+  fn tournament_config_prefers_the_read_folder() {
+    let (read, write) = temp_dirs("config");
+    let paths = DataPaths::with_dirs(RunMode::Tournament, &read, &write);
+
+    assert_eq!(paths.config_path("config.yaml"), "config.yaml");
+
+    fs::write(format!("{}/config.yaml", read), "default_score: 2.0").unwrap();
+    assert_eq!(
+      paths.config_path("config.yaml"),
+      format!("{}/config.yaml", read)
+    );
+  }
+
+  #[test]
+  fn tournament_learning_data_carries_over() {
+    let (read, write) = temp_dirs("learning");
+    let paths = DataPaths::with_dirs(RunMode::Tournament, &read, &write);
+    fs::write(format!("{}/results.jsonl", read), "{}\n").unwrap();
+
+    let path = paths.learning_path("results.jsonl");
+
+    assert_eq!(path, format!("{}/results.jsonl", write));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "{}\n");
+  }
+
+  #[test]
+  fn tournament_learning_data_starts_empty() {
+    let (read, write) = temp_dirs("empty");
+    let paths = DataPaths::with_dirs(RunMode::Tournament, &read, &write);
+
+    let path = paths.learning_path("results.jsonl");
+
+    assert_eq!(path, format!("{}/results.jsonl", write));
+    assert!(!Path::new(&path).exists());
+  }
+}
@@ -0,0 +1,13 @@
+//! Everything the bot decides and serves, independent of how it is attached to the game.
+//! The `protossbot` binary wires this up to BWAPI; tests and simulators run it natively.
+
+// Without BWAPI the managers' pure helpers have no caller outside their tests
+#![cfg_attr(not(feature = "bwapi"), allow(dead_code))]
+
+pub mod data_paths;
+pub mod logging;
+pub mod state;
+pub mod types;
+pub mod utils;
+pub mod web_server;
+pub mod world;
@@ -0,0 +1,375 @@
+//! Leveled logging with one filter per bot subsystem. Every record carries the frame it
+//! was written on; records go to the console as text and, once a log file is open, to
+//! that file as JSON lines. Filters can be changed while a game runs through the web API.
+//!
+//! Use the `log_error!` .. `log_trace!` macros rather than calling [`log`] directly so
+//! filtered records are never formatted.
+
+use serde::{Deserialize, Serialize};
+use std::{
+  fmt,
+  fs::File,
+  io::{LineWriter, Write},
+  sync::{
+    atomic::{AtomicI32, Ordering},
+    Mutex,
+  },
+};
+
+/// Replays land under `maps/replays`, so the game log and stats are written next to them
+pub const LOG_DIR: &str = "maps/replays";
+/// Level every target starts at
+pub const DEFAULT_LEVEL: LogLevel = LogLevel::Info;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+  /// Only valid as a filter: silences the target
+  Off,
+  Error,
+  Warn,
+  Info,
+  /// Per-frame decisions, noisy at high game speeds
+  Debug,
+  Trace,
+}
+
+impl LogLevel {
+  pub fn name(&self) -> &'static str {
+    match self {
+      LogLevel::Off => "off",
+      LogLevel::Error => "error",
+      LogLevel::Warn => "warn",
+      LogLevel::Info => "info",
+      LogLevel::Debug => "debug",
+      LogLevel::Trace => "trace",
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogTarget {
+  Game,
+  Build,
+  Workers,
+  Placement,
+  Scouting,
+  Strategy,
+  Army,
+  Micro,
+  Spells,
+  Defense,
+  Web,
+}
+
+impl LogTarget {
+  pub const ALL: [LogTarget; 11] = [
+    LogTarget::Game,
+    LogTarget::Build,
+    LogTarget::Workers,
+    LogTarget::Placement,
+    LogTarget::Scouting,
+    LogTarget::Strategy,
+    LogTarget::Army,
+    LogTarget::Micro,
+    LogTarget::Spells,
+    LogTarget::Defense,
+    LogTarget::Web,
+  ];
+
+  pub fn name(&self) -> &'static str {
+    match self {
+      LogTarget::Game => "game",
+      LogTarget::Build => "build",
+      LogTarget::Workers => "workers",
+      LogTarget::Placement => "placement",
+      LogTarget::Scouting => "scouting",
+      LogTarget::Strategy => "strategy",
+      LogTarget::Army => "army",
+      LogTarget::Micro => "micro",
+      LogTarget::Spells => "spells",
+      LogTarget::Defense => "defense",
+      LogTarget::Web => "web",
+    }
+  }
+
+  fn index(&self) -> usize {
+    *self as usize
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct LogRecord {
+  pub frame: i32,
+  pub level: LogLevel,
+  pub target: LogTarget,
+  pub message: String,
+}
+
+impl fmt::Display for LogRecord {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "[{:>6}] {:<5} {}: {}",
+      self.frame,
+      self.level.name().to_uppercase(),
+      self.target.name(),
+      self.message
+    )
+  }
+}
+
+pub struct Logger {
+  levels: [LogLevel; LogTarget::ALL.len()],
+  console: bool,
+  file: Option<LineWriter<File>>,
+}
+
+impl Default for Logger {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Logger {
+  pub const fn new() -> Self {
+    Self {
+      levels: [DEFAULT_LEVEL; LogTarget::ALL.len()],
+      console: true,
+      file: None,
+    }
+  }
+
+  pub fn enabled(&self, target: LogTarget, level: LogLevel) -> bool {
+    level != LogLevel::Off && level <= self.levels[target.index()]
+  }
+
+  pub fn level(&self, target: LogTarget) -> LogLevel {
+    self.levels[target.index()]
+  }
+
+  pub fn set_level(&mut self, target: LogTarget, level: LogLevel) {
+    self.levels[target.index()] = level;
+  }
+
+  pub fn set_console(&mut self, console: bool) {
+    self.console = console;
+  }
+
+  /// Starts writing records to `path`, replacing what was there
+  pub fn open_file(&mut self, path: &str) -> Result<(), String> {
+    let file = File::create(path).map_err(|e| e.to_string())?;
+    self.file = Some(LineWriter::new(file));
+    Ok(())
+  }
+
+  pub fn write(&mut self, record: &LogRecord) {
+    if !self.enabled(record.target, record.level) {
+      return;
+    }
+    if self.console {
+      println!("{}", record);
+    }
+    if let Some(file) = &mut self.file {
+      let written = serde_json::to_string(record)
+        .map_err(|e| e.to_string())
+        .and_then(|line| writeln!(file, "{}", line).map_err(|e| e.to_string()));
+      if let Err(e) = written {
+        println!("Log file write FAILED, closing it: {}", e);
+        self.file = None;
+      }
+    }
+  }
+}
+
+static LOGGER: Mutex<Logger> = Mutex::new(Logger::new());
+static FRAME: AtomicI32 = AtomicI32::new(0);
+
+fn with_logger<T>(f: impl FnOnce(&mut Logger) -> T) -> T {
+  // A panic while logging must not silence the rest of the game
+  let mut logger = LOGGER.lock().unwrap_or_else(|e| e.into_inner());
+  f(&mut logger)
+}
+
+/// Frame stamped on the records written from now on
+pub fn set_frame(frame: i32) {
+  FRAME.store(frame, Ordering::Relaxed);
+}
+
+pub fn enabled(target: LogTarget, level: LogLevel) -> bool {
+  with_logger(|logger| logger.enabled(target, level))
+}
+
+pub fn set_level(target: LogTarget, level: LogLevel) {
+  with_logger(|logger| logger.set_level(target, level));
+}
+
+/// Current filter of every target, in [`LogTarget::ALL`] order
+pub fn levels() -> Vec<(LogTarget, LogLevel)> {
+  with_logger(|logger| {
+    LogTarget::ALL
+      .iter()
+      .map(|target| (*target, logger.level(*target)))
+      .collect()
+  })
+}
+
+pub fn set_console(console: bool) {
+  with_logger(|logger| logger.set_console(console));
+}
+
+/// Seconds since the Unix epoch, used to tell apart the files of different games
+pub fn unix_time() -> u64 {
+  std::time::SystemTime::now()
+    .duration_since(std::time::UNIX_EPOCH)
+    .map(|d| d.as_secs())
+    .unwrap_or(0)
+}
+
+/// Path in [`LOG_DIR`] for a file about the game started at `started`, falling back to the
+/// working directory when the folder can't be created
+pub fn game_file_path(map_name: &str, started: u64, extension: &str) -> String {
+  let map = map_name.trim_end_matches(".scx").trim_end_matches(".scm");
+  let file_name = format!("protossbot_{}_{}.{}", map, started, extension);
+
+  if std::fs::create_dir_all(LOG_DIR).is_ok() {
+    format!("{}/{}", LOG_DIR, file_name)
+  } else {
+    file_name
+  }
+}
+
+/// Opens the log file for the game started at `started`
+pub fn open_game_log(map_name: &str, started: u64) {
+  let path = game_file_path(map_name, started, "log.jsonl");
+
+  match with_logger(|logger| logger.open_file(&path)) {
+    Ok(()) => crate::log_info!(LogTarget::Game, "Logging to {}", path),
+    Err(e) => crate::log_warn!(LogTarget::Game, "Could not open log file {}: {}", path, e),
+  }
+}
+
+pub fn log(target: LogTarget, level: LogLevel, args: fmt::Arguments) {
+  let record = LogRecord {
+    frame: FRAME.load(Ordering::Relaxed),
+    level,
+    target,
+    message: args.to_string(),
+  };
+  with_logger(|logger| logger.write(&record));
+}
+
+#[macro_export]
+macro_rules! log_at {
+  ($target:expr, $level:expr, $($arg:tt)+) => {{
+    let (target, level) = ($target, $level);
+    if $crate::logging::enabled(target, level) {
+      $crate::logging::log(target, level, format_args!($($arg)+));
+    }
+  }};
+}
+
+#[macro_export]
+macro_rules! log_error {
+  ($target:expr, $($arg:tt)+) => {
+    $crate::log_at!($target, $crate::logging::LogLevel::Error, $($arg)+)
+  };
+}
+
+#[macro_export]
+macro_rules! log_warn {
+  ($target:expr, $($arg:tt)+) => {
+    $crate::log_at!($target, $crate::logging::LogLevel::Warn, $($arg)+)
+  };
+}
+
+#[macro_export]
+macro_rules! log_info {
+  ($target:expr, $($arg:tt)+) => {
+    $crate::log_at!($target, $crate::logging::LogLevel::Info, $($arg)+)
+  };
+}
+
+#[macro_export]
+macro_rules! log_debug {
+  ($target:expr, $($arg:tt)+) => {
+    $crate::log_at!($target, $crate::logging::LogLevel::Debug, $($arg)+)
+  };
+}
+
+#[macro_export]
+macro_rules! log_trace {
+  ($target:expr, $($arg:tt)+) => {
+    $crate::log_at!($target, $crate::logging::LogLevel::Trace, $($arg)+)
+  };
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn record(target: LogTarget, level: LogLevel) -> LogRecord {
+    LogRecord {
+      frame: 120,
+      level,
+      target,
+      message: "Worker 5 assigned".to_string(),
+    }
+  }
+
+  #[test]
+  fn filters_per_target() {
+    let mut logger = Logger::new();
+    logger.set_level(LogTarget::Workers, LogLevel::Warn);
+    logger.set_level(LogTarget::Build, LogLevel::Debug);
+
+    assert!(!logger.enabled(LogTarget::Workers, LogLevel::Info));
+    assert!(logger.enabled(LogTarget::Workers, LogLevel::Error));
+    assert!(logger.enabled(LogTarget::Build, LogLevel::Debug));
+    assert!(!logger.enabled(LogTarget::Build, LogLevel::Trace));
+    assert!(logger.enabled(LogTarget::Army, DEFAULT_LEVEL));
+  }
+
+  #[test]
+  fn off_silences_everything() {
+    let mut logger = Logger::new();
+    logger.set_level(LogTarget::Micro, LogLevel::Off);
+
+    assert!(!logger.enabled(LogTarget::Micro, LogLevel::Error));
+  }
+
+  #[test]
+  fn formats_frame_level_and_target() {
+    let text = record(LogTarget::Workers, LogLevel::Info).to_string();
+
+    assert_eq!(text, "[   120] INFO  workers: Worker 5 assigned");
+  }
+
+  #[test]
+  fn writes_only_enabled_records_to_the_file() {
+    let path = std::env::temp_dir().join(format!(
+      "protossbot_logging_{}_writes_only_enabled_records.jsonl",
+      std::process::id()
+    ));
+    let path = path.to_str().unwrap();
+    let mut logger = Logger::new();
+    logger.set_console(false);
+    logger.open_file(path).unwrap();
+
+    logger.write(&record(LogTarget::Workers, LogLevel::Info));
+    logger.write(&record(LogTarget::Workers, LogLevel::Debug));
+    drop(logger);
+
+    let text = std::fs::read_to_string(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    let lines: Vec<serde_json::Value> = text
+      .lines()
+      .map(|line| serde_json::from_str(line).unwrap())
+      .collect();
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0]["frame"], 120);
+    assert_eq!(lines[0]["level"], "info");
+    assert_eq!(lines[0]["target"], "workers");
+  }
+}
@@ -0,0 +1,161 @@
+use crate::types::UnitType;
+use std::collections::HashMap;
+
+use crate::utils::strategy_recognition::EnemyOpening;
+
+#[derive(Clone, Debug)]
+pub struct BuildStage {
+  pub name: String,
+  pub desired_counts: HashMap<UnitType, i32>,
+}
+
+impl BuildStage {
+  pub fn new(name: &str) -> Self {
+    Self {
+      name: name.to_string(),
+      desired_counts: HashMap::new(),
+    }
+  }
+
+  pub fn with_unit(mut self, unit_type: UnitType, count: i32) -> Self {
+    self.desired_counts.insert(unit_type, count);
+    self
+  }
+}
+
+/// Opening played with [`get_build_stages`], and the one used when no history says otherwise
+pub const DEFAULT_STRATEGY: &str = "cannon_expand";
+/// Openings the strategy selection picks from at the start of a game
+pub const STRATEGIES: [&str; 3] = [DEFAULT_STRATEGY, "two_gate_zealot", "one_gate_dragoon"];
+
+pub fn get_build_stages() -> Vec<BuildStage> {
+  vec![
+    BuildStage::new("Start")
+      .with_unit(UnitType::Protoss_Probe, 10)
+      .with_unit(UnitType::Protoss_Pylon, 1),
+    BuildStage::new("Basic Production")
+      .with_unit(UnitType::Protoss_Probe, 12)
+      .with_unit(UnitType::Protoss_Pylon, 2)
+      .with_unit(UnitType::Protoss_Gateway, 1)
+      .with_unit(UnitType::Protoss_Forge, 1),
+    // Stage 2: Defense cannons
+    BuildStage::new("Defense Cannons")
+      .with_unit(UnitType::Protoss_Probe, 16)
+      .with_unit(UnitType::Protoss_Pylon, 3)
+      .with_unit(UnitType::Protoss_Nexus, 1)
+      .with_unit(UnitType::Protoss_Gateway, 1)
+      .with_unit(UnitType::Protoss_Forge, 1)
+      .with_unit(UnitType::Protoss_Photon_Cannon, 4),
+    BuildStage::new("Natural Expansion")
+      .with_unit(UnitType::Protoss_Probe, 20)
+      .with_unit(UnitType::Protoss_Pylon, 4)
+      .with_unit(UnitType::Protoss_Nexus, 2)
+      .with_unit(UnitType::Protoss_Gateway, 2)
+      .with_unit(UnitType::Protoss_Forge, 1)
+      .with_unit(UnitType::Protoss_Photon_Cannon, 4),
+  ]
+}
+
+/// Stages of the named opening from [`STRATEGIES`], the default opening for unknown names
+pub fn get_strategy_stages(strategy: &str) -> Vec<BuildStage> {
+  match strategy {
+    "two_gate_zealot" => get_two_gate_zealot_stages(),
+    "one_gate_dragoon" => get_one_gate_dragoon_stages(),
+    _ => get_build_stages(),
+  }
+}
+
+fn get_two_gate_zealot_stages() -> Vec<BuildStage> {
+  vec![
+    BuildStage::new("Start")
+      .with_unit(UnitType::Protoss_Probe, 9)
+      .with_unit(UnitType::Protoss_Pylon, 1),
+    BuildStage::new("Two Gateways")
+      .with_unit(UnitType::Protoss_Probe, 12)
+      .with_unit(UnitType::Protoss_Pylon, 2)
+      .with_unit(UnitType::Protoss_Gateway, 2),
+    BuildStage::new("Zealot Pressure")
+      .with_unit(UnitType::Protoss_Probe, 14)
+      .with_unit(UnitType::Protoss_Pylon, 4)
+      .with_unit(UnitType::Protoss_Gateway, 2)
+      .with_unit(UnitType::Protoss_Zealot, 6),
+    BuildStage::new("Natural Expansion")
+      .with_unit(UnitType::Protoss_Probe, 20)
+      .with_unit(UnitType::Protoss_Pylon, 5)
+      .with_unit(UnitType::Protoss_Nexus, 2)
+      .with_unit(UnitType::Protoss_Gateway, 3)
+      .with_unit(UnitType::Protoss_Zealot, 8),
+  ]
+}
+
+fn get_one_gate_dragoon_stages() -> Vec<BuildStage> {
+  vec![
+    BuildStage::new("Start")
+      .with_unit(UnitType::Protoss_Probe, 10)
+      .with_unit(UnitType::Protoss_Pylon, 1),
+    BuildStage::new("Gateway and Gas")
+      .with_unit(UnitType::Protoss_Probe, 12)
+      .with_unit(UnitType::Protoss_Pylon, 2)
+      .with_unit(UnitType::Protoss_Gateway, 1)
+      .with_unit(UnitType::Protoss_Assimilator, 1),
+    BuildStage::new("Cybernetics Core")
+      .with_unit(UnitType::Protoss_Probe, 15)
+      .with_unit(UnitType::Protoss_Pylon, 2)
+      .with_unit(UnitType::Protoss_Gateway, 1)
+      .with_unit(UnitType::Protoss_Assimilator, 1)
+      .with_unit(UnitType::Protoss_Cybernetics_Core, 1),
+    BuildStage::new("Dragoons")
+      .with_unit(UnitType::Protoss_Probe, 18)
+      .with_unit(UnitType::Protoss_Pylon, 4)
+      .with_unit(UnitType::Protoss_Gateway, 2)
+      .with_unit(UnitType::Protoss_Assimilator, 1)
+      .with_unit(UnitType::Protoss_Cybernetics_Core, 1)
+      .with_unit(UnitType::Protoss_Dragoon, 4),
+    BuildStage::new("Natural Expansion")
+      .with_unit(UnitType::Protoss_Probe, 22)
+      .with_unit(UnitType::Protoss_Pylon, 5)
+      .with_unit(UnitType::Protoss_Nexus, 2)
+      .with_unit(UnitType::Protoss_Gateway, 2)
+      .with_unit(UnitType::Protoss_Assimilator, 1)
+      .with_unit(UnitType::Protoss_Cybernetics_Core, 1)
+      .with_unit(UnitType::Protoss_Dragoon, 6),
+  ]
+}
+
+/// Extra stage inserted ahead of the current one once an enemy opening is recognized
+pub fn get_reaction_stage(opening: EnemyOpening) -> Option<BuildStage> {
+  match opening {
+    EnemyOpening::FourPool => Some(
+      BuildStage::new("Hold 4-Pool")
+        .with_unit(UnitType::Protoss_Pylon, 1)
+        .with_unit(UnitType::Protoss_Forge, 1)
+        .with_unit(UnitType::Protoss_Photon_Cannon, 2)
+        .with_unit(UnitType::Protoss_Gateway, 1)
+        .with_unit(UnitType::Protoss_Zealot, 2),
+    ),
+    EnemyOpening::TwoGateZealot | EnemyOpening::ProxyGateway | EnemyOpening::ProxyBarracks => Some(
+      BuildStage::new("Hold Gateway/Barracks Rush")
+        .with_unit(UnitType::Protoss_Pylon, 2)
+        .with_unit(UnitType::Protoss_Gateway, 2)
+        .with_unit(UnitType::Protoss_Zealot, 4)
+        .with_unit(UnitType::Protoss_Forge, 1)
+        .with_unit(UnitType::Protoss_Photon_Cannon, 2),
+    ),
+    EnemyOpening::FastExpand | EnemyOpening::EarlyFactory | EnemyOpening::Unknown => None,
+  }
+}
+
+/// Extra stage inserted ahead of the current one once cloaked or burrowed units are
+/// expected, with one more cannon per base on top of the ones we already have
+pub fn get_detection_stage(cannons: i32) -> BuildStage {
+  BuildStage::new("Detection")
+    .with_unit(UnitType::Protoss_Pylon, 2)
+    .with_unit(UnitType::Protoss_Gateway, 1)
+    .with_unit(UnitType::Protoss_Assimilator, 1)
+    .with_unit(UnitType::Protoss_Cybernetics_Core, 1)
+    .with_unit(UnitType::Protoss_Forge, 1)
+    .with_unit(UnitType::Protoss_Photon_Cannon, cannons)
+    .with_unit(UnitType::Protoss_Robotics_Facility, 1)
+    .with_unit(UnitType::Protoss_Observatory, 1)
+    .with_unit(UnitType::Protoss_Observer, 2)
+}
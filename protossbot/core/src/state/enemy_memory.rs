@@ -0,0 +1,260 @@
+use crate::types::{Position, TilePosition, UnitType};
+#[cfg(feature = "bwapi")]
+use rsbwapi::{Game, Player, Unit};
+use std::collections::HashMap;
+
+#[derive(Clone, Debug)]
+pub struct EnemyUnitInfo {
+  pub unit_id: usize,
+  pub unit_type: UnitType,
+  pub owner_id: usize,
+  pub last_position: Position,
+  pub first_seen_frame: i32,
+  pub last_seen_frame: i32,
+  pub hit_points: i32,
+  pub shields: i32,
+  pub is_completed: bool,
+  pub is_visible: bool,
+  /// False once the last known position is in vision again without the unit there
+  pub position_known: bool,
+  pub believed_dead: bool,
+}
+
+impl EnemyUnitInfo {
+  pub fn last_tile(&self) -> TilePosition {
+    TilePosition {
+      x: self.last_position.x / 32,
+      y: self.last_position.y / 32,
+    }
+  }
+}
+
+/// Everything we have ever seen of the enemy, keyed by unit id, so decisions can
+/// use buildings and armies that are currently outside of vision.
+#[derive(Clone, Debug, Default)]
+pub struct EnemyMemory {
+  pub units: HashMap<usize, EnemyUnitInfo>,
+}
+
+impl EnemyMemory {
+  #[cfg(feature = "bwapi")]
+  pub fn observe(&mut self, unit: &Unit, frame: i32) {
+    let unit_type = unit.get_type();
+    let info = self
+      .units
+      .entry(unit.get_id())
+      .or_insert_with(|| EnemyUnitInfo {
+        unit_id: unit.get_id(),
+        unit_type,
+        owner_id: unit.get_player().get_id(),
+        last_position: unit.get_position(),
+        first_seen_frame: frame,
+        last_seen_frame: frame,
+        hit_points: unit.get_hit_points(),
+        shields: unit.get_shields(),
+        is_completed: unit.is_completed(),
+        is_visible: true,
+        position_known: true,
+        believed_dead: false,
+      });
+
+    info.unit_type = unit_type;
+    info.owner_id = unit.get_player().get_id();
+    info.last_position = unit.get_position();
+    info.last_seen_frame = frame;
+    info.hit_points = unit.get_hit_points();
+    info.shields = unit.get_shields();
+    info.is_completed = unit.is_completed();
+    info.is_visible = true;
+    info.position_known = true;
+    info.believed_dead = false;
+  }
+
+  pub fn on_hide(&mut self, unit_id: usize) {
+    if let Some(info) = self.units.get_mut(&unit_id) {
+      info.is_visible = false;
+    }
+  }
+
+  pub fn mark_dead(&mut self, unit_id: usize) {
+    if let Some(info) = self.units.get_mut(&unit_id) {
+      info.is_visible = false;
+      info.believed_dead = true;
+    }
+  }
+
+  /// Updates visible enemies and invalidates memories that vision now contradicts.
+  #[cfg(feature = "bwapi")]
+  pub fn refresh(&mut self, game: &Game, player: &Player) {
+    let frame = game.get_frame_count();
+
+    let visible: Vec<Unit> = game
+      .get_all_units()
+      .into_iter()
+      .filter(|u| u.get_player().is_enemy(player))
+      .collect();
+    for unit in &visible {
+      self.observe(unit, frame);
+    }
+
+    let visible_ids: Vec<usize> = visible.iter().map(|u| u.get_id()).collect();
+    self.forget_contradicted(&visible_ids, |tile| game.is_visible(tile));
+  }
+
+  /// Hides every remembered unit that is not in `visible_ids` and drops what vision
+  /// contradicts, with `is_tile_visible` telling which tiles we currently see.
+  pub fn forget_contradicted(
+    &mut self,
+    visible_ids: &[usize],
+    is_tile_visible: impl Fn(TilePosition) -> bool,
+  ) {
+    for info in self.units.values_mut() {
+      if info.believed_dead || visible_ids.contains(&info.unit_id) {
+        continue;
+      }
+      info.is_visible = false;
+      if !info.position_known || !is_tile_visible(info.last_tile()) {
+        continue;
+      }
+
+      // We can see where it was and it is gone: buildings are destroyed (or lifted
+      // off), mobile units have simply moved on
+      if info.unit_type.is_building() {
+        info.believed_dead = true;
+      } else {
+        info.position_known = false;
+      }
+    }
+  }
+
+  pub fn alive(&self) -> impl Iterator<Item = &EnemyUnitInfo> {
+    self.units.values().filter(|info| !info.believed_dead)
+  }
+
+  pub fn buildings(&self) -> impl Iterator<Item = &EnemyUnitInfo> {
+    self.alive().filter(|info| info.unit_type.is_building())
+  }
+
+  pub fn count_of_type(&self, unit_type: UnitType) -> usize {
+    self
+      .alive()
+      .filter(|info| info.unit_type == unit_type)
+      .count()
+  }
+
+  /// Frame at which a unit of this type was first seen, dead or alive
+  pub fn first_seen_frame(&self, unit_type: UnitType) -> Option<i32> {
+    self
+      .units
+      .values()
+      .filter(|info| info.unit_type == unit_type)
+      .map(|info| info.first_seen_frame)
+      .min()
+  }
+
+  pub fn nearest_building(&self, position: Position) -> Option<&EnemyUnitInfo> {
+    self.buildings().min_by_key(|info| {
+      (info.last_position.x - position.x).pow(2) + (info.last_position.y - position.y).pow(2)
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn remember(memory: &mut EnemyMemory, unit_id: usize, unit_type: UnitType, x: i32, y: i32) {
+    memory.units.insert(
+      unit_id,
+      EnemyUnitInfo {
+        unit_id,
+        unit_type,
+        owner_id: 1,
+        last_position: Position { x, y },
+        first_seen_frame: unit_id as i32 * 100,
+        last_seen_frame: unit_id as i32 * 100,
+        hit_points: 100,
+        shields: 0,
+        is_completed: true,
+        is_visible: true,
+        position_known: true,
+        believed_dead: false,
+      },
+    );
+  }
+
+  /// A zealot, a gateway and a pylon, with the pylon already dead
+  fn sample_memory() -> EnemyMemory {
+    let mut memory = EnemyMemory::default();
+    remember(&mut memory, 1, UnitType::Protoss_Zealot, 100, 100);
+    remember(&mut memory, 2, UnitType::Protoss_Gateway, 1000, 1000);
+    remember(&mut memory, 3, UnitType::Protoss_Pylon, 200, 200);
+    memory.mark_dead(3);
+    memory
+  }
+
+  #[test]
+  fn hidden_units_keep_their_last_position() {
+    let mut memory = sample_memory();
+
+    memory.on_hide(1);
+
+    let zealot = &memory.units[&1];
+    assert!(!zealot.is_visible);
+    assert!(zealot.position_known);
+    assert_eq!(zealot.last_position, Position { x: 100, y: 100 });
+  }
+
+  #[test]
+  fn dead_units_are_left_out_but_remembered() {
+    let memory = sample_memory();
+
+    let pylon = &memory.units[&3];
+    assert!(pylon.believed_dead && !pylon.is_visible);
+    let mut alive: Vec<usize> = memory.alive().map(|info| info.unit_id).collect();
+    alive.sort();
+    assert_eq!(alive, vec![1, 2]);
+    assert_eq!(
+      memory
+        .buildings()
+        .map(|info| info.unit_id)
+        .collect::<Vec<_>>(),
+      vec![2]
+    );
+    assert_eq!(memory.count_of_type(UnitType::Protoss_Pylon), 0);
+    assert_eq!(memory.count_of_type(UnitType::Protoss_Zealot), 1);
+    assert_eq!(memory.first_seen_frame(UnitType::Protoss_Pylon), Some(300));
+    assert_eq!(memory.first_seen_frame(UnitType::Protoss_Dragoon), None);
+  }
+
+  #[test]
+  fn nearest_building_ignores_units_and_dead_buildings() {
+    let mut memory = sample_memory();
+    remember(&mut memory, 4, UnitType::Protoss_Forge, 2000, 2000);
+
+    let nearest = memory.nearest_building(Position { x: 0, y: 0 }).unwrap();
+
+    assert_eq!(nearest.unit_id, 2);
+  }
+
+  #[test]
+  fn seeing_an_empty_spot_kills_buildings_and_loses_units() {
+    let mut memory = sample_memory();
+    remember(&mut memory, 4, UnitType::Protoss_Forge, 2000, 2000);
+    remember(&mut memory, 5, UnitType::Protoss_Dragoon, 2000, 2000);
+
+    // Only the gateway's and the zealot's spots are in vision
+    let seen = [TilePosition { x: 3, y: 3 }, TilePosition { x: 31, y: 31 }];
+    memory.forget_contradicted(&[5], |tile| seen.contains(&tile));
+
+    let gateway = &memory.units[&2];
+    assert!(gateway.believed_dead);
+    let zealot = &memory.units[&1];
+    assert!(!zealot.believed_dead && !zealot.position_known && !zealot.is_visible);
+    // Out of vision, remembered as it was
+    let forge = &memory.units[&4];
+    assert!(!forge.believed_dead && forge.position_known && !forge.is_visible);
+    // Still in sight elsewhere
+    assert!(memory.units[&5].is_visible);
+  }
+}
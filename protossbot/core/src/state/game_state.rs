@@ -0,0 +1,253 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+  data_paths::DataPaths,
+  state::{build_stages::BuildStage, enemy_memory::EnemyMemory},
+  types::{Order, Position, TilePosition, UnitType, UpgradeType},
+  utils::{
+    command_recorder::CommandRecorder,
+    game_stats::GameStats,
+    pathfinding::FlowField,
+    scouting_manager::ScoutingConfig,
+    strategy_recognition::{EnemyOpening, StrategyHypothesis},
+    target_priority::TargetPriorityTable,
+    terrain_analysis::TerrainAnalysis,
+  },
+};
+
+pub struct GameState {
+  pub intended_commands: HashMap<usize, IntendedCommand>,
+  pub unit_build_history: Vec<BuildHistoryEntry>,
+  /// Opening picked for this game, one of `build_stages::STRATEGIES`
+  pub strategy: &'static str,
+  pub build_stages: Vec<BuildStage>,
+  pub current_stage_index: usize,
+  pub desired_game_speed: i32,
+  pub stage_item_status: HashMap<String, String>,
+  pub workers: WorkerState,
+  pub terrain: TerrainAnalysis,
+  pub expansion: ExpansionPlan,
+  pub enemy_starts: StartLocationInference,
+  pub scouting: ScoutingState,
+  pub enemy_memory: EnemyMemory,
+  pub enemy_strategy: StrategyHypothesis,
+  pub reacted_to_opening: Option<EnemyOpening>,
+  pub army: ArmyState,
+  pub defense: DefenseState,
+  pub target_priorities: TargetPriorityTable,
+  pub spells: SpellState,
+  pub detection: DetectionState,
+  pub commands: CommandRecorder,
+  pub stats: GameStats,
+  pub data: DataPaths,
+}
+
+impl Default for GameState {
+  fn default() -> Self {
+    Self {
+      intended_commands: HashMap::new(),
+      unit_build_history: Vec::new(),
+      strategy: crate::state::build_stages::DEFAULT_STRATEGY,
+      build_stages: crate::state::build_stages::get_build_stages(),
+      current_stage_index: 0,
+      desired_game_speed: 20,
+      stage_item_status: HashMap::new(),
+      workers: WorkerState::default(),
+      terrain: TerrainAnalysis::default(),
+      expansion: ExpansionPlan::default(),
+      enemy_starts: StartLocationInference::default(),
+      scouting: ScoutingState::default(),
+      enemy_memory: EnemyMemory::default(),
+      enemy_strategy: StrategyHypothesis::default(),
+      reacted_to_opening: None,
+      army: ArmyState::default(),
+      defense: DefenseState::default(),
+      target_priorities: TargetPriorityTable::default(),
+      spells: SpellState::default(),
+      detection: DetectionState::default(),
+      commands: CommandRecorder::default(),
+      stats: GameStats::default(),
+      data: DataPaths::default(),
+    }
+  }
+}
+
+#[derive(Clone, Debug)]
+pub struct IntendedCommand {
+  pub order: Order,
+  pub target_position: Option<Position>,
+  pub target_unit_id: Option<usize>,
+}
+
+#[derive(Clone, Debug)]
+pub enum BuildStatus {
+  Assigned,
+  Started,
+}
+
+#[derive(Clone, Debug)]
+pub struct BuildHistoryEntry {
+  pub unit_type: Option<UnitType>,
+  pub upgrade_type: Option<UpgradeType>,
+  pub assigned_unit_id: Option<usize>,
+  // pub status: BuildStatus,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct WorkerState {
+  /// Workers sent to mine gas and the assimilator each one was sent to
+  pub gas_assignments: HashMap<usize, usize>,
+  /// Flow fields towards each base location's depot, built the first time a worker
+  /// looks for minerals there
+  pub mineral_line_fields: HashMap<usize, FlowField>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ExpansionPlan {
+  pub main_base_id: Option<usize>,
+  /// Ground distance in pixels from the main base, indexed by base location id
+  pub ground_distance_from_main: Vec<Option<i32>>,
+  /// Untaken base location ids, best candidate first
+  pub ranked_base_ids: Vec<usize>,
+  pub saturation_requested: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct StartLocationCandidate {
+  pub tile: TilePosition,
+  pub weight: f32,
+  pub probability: f32,
+  pub scouted_empty: bool,
+  pub enemy_confirmed: bool,
+  /// Ground distances to this start location, built once at game start
+  pub distances: FlowField,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct StartLocationInference {
+  pub candidates: Vec<StartLocationCandidate>,
+  pub most_likely_enemy_main: Option<TilePosition>,
+  pub observed_building_ids: HashSet<usize>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScoutPhase {
+  #[default]
+  Waiting,
+  Searching,
+  Circling,
+  Returning,
+  Done,
+}
+
+#[derive(Clone, Debug)]
+pub struct ScoutingState {
+  /// Supply as shown in game at which a probe is sent out to scout, from `ScoutingConfig`
+  pub scout_supply: i32,
+  pub scout_id: Option<usize>,
+  pub phase: ScoutPhase,
+  pub enemy_main: Option<TilePosition>,
+  pub circle_waypoint: usize,
+  /// Frame the scout set off towards the current circle waypoint
+  pub waypoint_started_frame: Option<i32>,
+}
+
+impl Default for ScoutingState {
+  fn default() -> Self {
+    Self {
+      scout_supply: ScoutingConfig::default().scout_supply,
+      scout_id: None,
+      phase: ScoutPhase::Waiting,
+      enemy_main: None,
+      circle_waypoint: 0,
+      waypoint_started_frame: None,
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SquadMode {
+  Rally,
+  Attack,
+  Defend,
+}
+
+#[derive(Clone, Debug)]
+pub struct Squad {
+  pub id: usize,
+  pub unit_ids: Vec<usize>,
+  pub mode: SquadMode,
+  pub target: Option<Position>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ArmyState {
+  pub squads: Vec<Squad>,
+  pub next_squad_id: usize,
+  /// Army supply as shown in game that the rallied squad needs before it attacks
+  pub attack_supply: i32,
+  pub rally_position: Option<Position>,
+  pub rally_region: Option<usize>,
+  /// Enemy location the rally choke was chosen to face
+  pub rally_facing: Option<TilePosition>,
+  pub last_retreat_frame: i32,
+  /// Units whose orders came from micro this frame
+  pub micro_unit_ids: HashSet<usize>,
+  /// Units pulled back home until their shields recharge
+  pub recharging_ids: HashSet<usize>,
+}
+
+impl Default for ArmyState {
+  fn default() -> Self {
+    Self {
+      squads: Vec::new(),
+      next_squad_id: 0,
+      attack_supply: 24,
+      rally_position: None,
+      rally_region: None,
+      rally_facing: None,
+      last_retreat_frame: i32::MIN / 2,
+      micro_unit_ids: HashSet::new(),
+      recharging_ids: HashSet::new(),
+    }
+  }
+}
+
+/// Enemy units inside one of our regions, or hitting our buildings from outside of one
+#[derive(Clone, Debug)]
+pub struct Threat {
+  pub region_id: Option<usize>,
+  pub center: Position,
+  pub enemy_ids: Vec<usize>,
+  /// Estimated fighting strength in supply
+  pub strength: f32,
+  pub squad_id: Option<usize>,
+  pub pulled_worker_ids: Vec<usize>,
+  pub last_seen_frame: i32,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct DefenseState {
+  pub threats: Vec<Threat>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct SpellState {
+  /// Storm centers and the frame they were cast on
+  pub recent_storms: Vec<(Position, i32)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ObserverPost {
+  Squad(usize),
+  Base(Position),
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct DetectionState {
+  /// Why detection was queued, once cloaked units are expected
+  pub reason: Option<String>,
+  /// Recent frames a unit of ours was hit with no known enemy in range
+  pub unexplained_hit_frames: Vec<i32>,
+  pub observer_posts: HashMap<usize, ObserverPost>,
+}
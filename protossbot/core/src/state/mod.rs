@@ -1,2 +1,3 @@
 pub mod build_stages;
+pub mod enemy_memory;
 pub mod game_state;
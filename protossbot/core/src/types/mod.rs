@@ -0,0 +1,23 @@
+//! The BWAPI value types decision logic works with. With the `bwapi` feature these are
+//! rsbwapi's own; without it they are plain copies with the same names and methods, so
+//! the core builds and tests on machines that have neither libclang nor StarCraft.
+
+#[cfg(feature = "bwapi")]
+pub use rsbwapi::{Order, Position, TilePosition, UnitType, UpgradeType, WalkPosition};
+
+#[cfg(not(feature = "bwapi"))]
+mod order;
+#[cfg(not(feature = "bwapi"))]
+mod position;
+#[cfg(not(feature = "bwapi"))]
+mod unit_type;
+#[cfg(not(feature = "bwapi"))]
+mod upgrade_type;
+
+#[cfg(not(feature = "bwapi"))]
+pub use self::{
+  order::Order,
+  position::{Position, TilePosition, WalkPosition},
+  unit_type::UnitType,
+  upgrade_type::UpgradeType,
+};
@@ -0,0 +1,154 @@
+//! Same shape and methods as rsbwapi's positions, for builds without BWAPI.
+
+use std::ops::{Add, AddAssign, Div, Mul, Sub, SubAssign};
+
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ScaledPosition<const N: i32> {
+  pub x: i32,
+  pub y: i32,
+}
+
+pub type Position = ScaledPosition<1>;
+pub type WalkPosition = ScaledPosition<8>;
+pub type TilePosition = ScaledPosition<32>;
+
+const fn pos_to_pos<const I: i32, const O: i32>(pos: ScaledPosition<I>) -> ScaledPosition<O> {
+  ScaledPosition {
+    x: pos.x * I / O,
+    y: pos.y * I / O,
+  }
+}
+
+impl Position {
+  pub const fn to_tile_position(self) -> TilePosition {
+    pos_to_pos(self)
+  }
+
+  pub const fn to_walk_position(self) -> WalkPosition {
+    pos_to_pos(self)
+  }
+
+  pub fn get_approx_distance<P: Into<Position>>(&self, other: P) -> i32 {
+    let p = other.into();
+    let mut max = (self.x - p.x).abs();
+    let mut min = (self.y - p.y).abs();
+    if max < min {
+      std::mem::swap(&mut max, &mut min);
+    }
+    if min <= (max >> 2) {
+      return max;
+    }
+    let min_calc = (3 * min) >> 3;
+    (min_calc >> 5) + min_calc + max - (max >> 4) - (max >> 6)
+  }
+}
+
+impl TilePosition {
+  pub const fn to_position(self) -> Position {
+    pos_to_pos(self)
+  }
+
+  pub const fn to_walk_position(self) -> WalkPosition {
+    pos_to_pos(self)
+  }
+
+  pub const fn center(self) -> Position {
+    Position::new(self.x * 32 + 16, self.y * 32 + 16)
+  }
+}
+
+impl WalkPosition {
+  pub const fn to_tile_position(self) -> TilePosition {
+    pos_to_pos(self)
+  }
+
+  pub const fn to_position(self) -> Position {
+    pos_to_pos(self)
+  }
+}
+
+impl<const N: i32> ScaledPosition<N> {
+  pub const fn new(x: i32, y: i32) -> Self {
+    Self { x, y }
+  }
+
+  pub const fn distance_squared(&self, other: Self) -> u32 {
+    let dx = self.x - other.x;
+    let dy = self.y - other.y;
+    (dx * dx + dy * dy) as u32
+  }
+
+  pub fn distance(&self, other: Self) -> f64 {
+    (self.distance_squared(other) as f64).sqrt()
+  }
+
+  pub fn chebyshev_distance(&self, other: Self) -> u32 {
+    (self.x - other.x).abs().max((self.y - other.y).abs()) as u32
+  }
+}
+
+impl<const N: i32> From<(i32, i32)> for ScaledPosition<N> {
+  fn from((x, y): (i32, i32)) -> Self {
+    Self { x, y }
+  }
+}
+
+impl<const N: i32> Add for ScaledPosition<N> {
+  type Output = Self;
+
+  fn add(self, other: Self) -> Self {
+    Self::new(self.x + other.x, self.y + other.y)
+  }
+}
+
+impl<const N: i32> Sub for ScaledPosition<N> {
+  type Output = Self;
+
+  fn sub(self, other: Self) -> Self {
+    Self::new(self.x - other.x, self.y - other.y)
+  }
+}
+
+impl<const N: i32> AddAssign for ScaledPosition<N> {
+  fn add_assign(&mut self, other: Self) {
+    *self = *self + other;
+  }
+}
+
+impl<const N: i32> SubAssign for ScaledPosition<N> {
+  fn sub_assign(&mut self, other: Self) {
+    *self = *self - other;
+  }
+}
+
+impl<const N: i32> Mul<i32> for ScaledPosition<N> {
+  type Output = Self;
+
+  fn mul(self, other: i32) -> Self {
+    Self::new(self.x * other, self.y * other)
+  }
+}
+
+impl<const N: i32> Div<i32> for ScaledPosition<N> {
+  type Output = Self;
+
+  fn div(self, other: i32) -> Self {
+    Self::new(self.x / other, self.y / other)
+  }
+}
+
+impl<const N: i32> Add<(i32, i32)> for ScaledPosition<N> {
+  type Output = Self;
+
+  fn add(self, other: (i32, i32)) -> Self {
+    Self::new(self.x + other.0, self.y + other.1)
+  }
+}
+
+impl<const N: i32> Sub<(i32, i32)> for ScaledPosition<N> {
+  type Output = Self;
+
+  fn sub(self, other: (i32, i32)) -> Self {
+    Self::new(self.x - other.0, self.y - other.1)
+  }
+}
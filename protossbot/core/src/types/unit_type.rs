@@ -0,0 +1,734 @@
+//! Generated from the BWAPI headers shipped with bwapi_wrapper 3.6.3; keep in step with rsbwapi.
+//! Only the fields the core reads are kept; the methods match rsbwapi's.
+
+use super::TilePosition;
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum UnitType {
+  Terran_Marine = 0,
+  Terran_Ghost,
+  Terran_Vulture,
+  Terran_Goliath,
+  Terran_Goliath_Turret,
+  Terran_Siege_Tank_Tank_Mode,
+  Terran_Siege_Tank_Tank_Mode_Turret,
+  Terran_SCV,
+  Terran_Wraith,
+  Terran_Science_Vessel,
+  Hero_Gui_Montag,
+  Terran_Dropship,
+  Terran_Battlecruiser,
+  Terran_Vulture_Spider_Mine,
+  Terran_Nuclear_Missile,
+  Terran_Civilian,
+  Hero_Sarah_Kerrigan,
+  Hero_Alan_Schezar,
+  Hero_Alan_Schezar_Turret,
+  Hero_Jim_Raynor_Vulture,
+  Hero_Jim_Raynor_Marine,
+  Hero_Tom_Kazansky,
+  Hero_Magellan,
+  Hero_Edmund_Duke_Tank_Mode,
+  Hero_Edmund_Duke_Tank_Mode_Turret,
+  Hero_Edmund_Duke_Siege_Mode,
+  Hero_Edmund_Duke_Siege_Mode_Turret,
+  Hero_Arcturus_Mengsk,
+  Hero_Hyperion,
+  Hero_Norad_II,
+  Terran_Siege_Tank_Siege_Mode,
+  Terran_Siege_Tank_Siege_Mode_Turret,
+  Terran_Firebat,
+  Spell_Scanner_Sweep,
+  Terran_Medic,
+  Zerg_Larva,
+  Zerg_Egg,
+  Zerg_Zergling,
+  Zerg_Hydralisk,
+  Zerg_Ultralisk,
+  Zerg_Broodling,
+  Zerg_Drone,
+  Zerg_Overlord,
+  Zerg_Mutalisk,
+  Zerg_Guardian,
+  Zerg_Queen,
+  Zerg_Defiler,
+  Zerg_Scourge,
+  Hero_Torrasque,
+  Hero_Matriarch,
+  Zerg_Infested_Terran,
+  Hero_Infested_Kerrigan,
+  Hero_Unclean_One,
+  Hero_Hunter_Killer,
+  Hero_Devouring_One,
+  Hero_Kukulza_Mutalisk,
+  Hero_Kukulza_Guardian,
+  Hero_Yggdrasill,
+  Terran_Valkyrie,
+  Zerg_Cocoon,
+  Protoss_Corsair,
+  Protoss_Dark_Templar,
+  Zerg_Devourer,
+  Protoss_Dark_Archon,
+  Protoss_Probe,
+  Protoss_Zealot,
+  Protoss_Dragoon,
+  Protoss_High_Templar,
+  Protoss_Archon,
+  Protoss_Shuttle,
+  Protoss_Scout,
+  Protoss_Arbiter,
+  Protoss_Carrier,
+  Protoss_Interceptor,
+  Hero_Dark_Templar,
+  Hero_Zeratul,
+  Hero_Tassadar_Zeratul_Archon,
+  Hero_Fenix_Zealot,
+  Hero_Fenix_Dragoon,
+  Hero_Tassadar,
+  Hero_Mojo,
+  Hero_Warbringer,
+  Hero_Gantrithor,
+  Protoss_Reaver,
+  Protoss_Observer,
+  Protoss_Scarab,
+  Hero_Danimoth,
+  Hero_Aldaris,
+  Hero_Artanis,
+  Critter_Rhynadon,
+  Critter_Bengalaas,
+  Special_Cargo_Ship,
+  Special_Mercenary_Gunship,
+  Critter_Scantid,
+  Critter_Kakaru,
+  Critter_Ragnasaur,
+  Critter_Ursadon,
+  Zerg_Lurker_Egg,
+  Hero_Raszagal,
+  Hero_Samir_Duran,
+  Hero_Alexei_Stukov,
+  Special_Map_Revealer,
+  Hero_Gerard_DuGalle,
+  Zerg_Lurker,
+  Hero_Infested_Duran,
+  Spell_Disruption_Web,
+  Terran_Command_Center,
+  Terran_Comsat_Station,
+  Terran_Nuclear_Silo,
+  Terran_Supply_Depot,
+  Terran_Refinery,
+  Terran_Barracks,
+  Terran_Academy,
+  Terran_Factory,
+  Terran_Starport,
+  Terran_Control_Tower,
+  Terran_Science_Facility,
+  Terran_Covert_Ops,
+  Terran_Physics_Lab,
+  Unused_Terran1,
+  Terran_Machine_Shop,
+  Unused_Terran2,
+  Terran_Engineering_Bay,
+  Terran_Armory,
+  Terran_Missile_Turret,
+  Terran_Bunker,
+  Special_Crashed_Norad_II,
+  Special_Ion_Cannon,
+  Powerup_Uraj_Crystal,
+  Powerup_Khalis_Crystal,
+  Zerg_Infested_Command_Center,
+  Zerg_Hatchery,
+  Zerg_Lair,
+  Zerg_Hive,
+  Zerg_Nydus_Canal,
+  Zerg_Hydralisk_Den,
+  Zerg_Defiler_Mound,
+  Zerg_Greater_Spire,
+  Zerg_Queens_Nest,
+  Zerg_Evolution_Chamber,
+  Zerg_Ultralisk_Cavern,
+  Zerg_Spire,
+  Zerg_Spawning_Pool,
+  Zerg_Creep_Colony,
+  Zerg_Spore_Colony,
+  Unused_Zerg1,
+  Zerg_Sunken_Colony,
+  Special_Overmind_With_Shell,
+  Special_Overmind,
+  Zerg_Extractor,
+  Special_Mature_Chrysalis,
+  Special_Cerebrate,
+  Special_Cerebrate_Daggoth,
+  Unused_Zerg2,
+  Protoss_Nexus,
+  Protoss_Robotics_Facility,
+  Protoss_Pylon,
+  Protoss_Assimilator,
+  Unused_Protoss1,
+  Protoss_Observatory,
+  Protoss_Gateway,
+  Unused_Protoss2,
+  Protoss_Photon_Cannon,
+  Protoss_Citadel_of_Adun,
+  Protoss_Cybernetics_Core,
+  Protoss_Templar_Archives,
+  Protoss_Forge,
+  Protoss_Stargate,
+  Special_Stasis_Cell_Prison,
+  Protoss_Fleet_Beacon,
+  Protoss_Arbiter_Tribunal,
+  Protoss_Robotics_Support_Bay,
+  Protoss_Shield_Battery,
+  Special_Khaydarin_Crystal_Form,
+  Special_Protoss_Temple,
+  Special_XelNaga_Temple,
+  Resource_Mineral_Field,
+  Resource_Mineral_Field_Type_2,
+  Resource_Mineral_Field_Type_3,
+  Unused_Cave,
+  Unused_Cave_In,
+  Unused_Cantina,
+  Unused_Mining_Platform,
+  Unused_Independant_Command_Center,
+  Special_Independant_Starport,
+  Unused_Independant_Jump_Gate,
+  Unused_Ruins,
+  Unused_Khaydarin_Crystal_Formation,
+  Resource_Vespene_Geyser,
+  Special_Warp_Gate,
+  Special_Psi_Disrupter,
+  Unused_Zerg_Marker,
+  Unused_Terran_Marker,
+  Unused_Protoss_Marker,
+  Special_Zerg_Beacon,
+  Special_Terran_Beacon,
+  Special_Protoss_Beacon,
+  Special_Zerg_Flag_Beacon,
+  Special_Terran_Flag_Beacon,
+  Special_Protoss_Flag_Beacon,
+  Special_Power_Generator,
+  Special_Overmind_Cocoon,
+  Spell_Dark_Swarm,
+  Special_Floor_Missile_Trap,
+  Special_Floor_Hatch,
+  Special_Upper_Level_Door,
+  Special_Right_Upper_Level_Door,
+  Special_Pit_Door,
+  Special_Right_Pit_Door,
+  Special_Floor_Gun_Trap,
+  Special_Wall_Missile_Trap,
+  Special_Wall_Flame_Trap,
+  Special_Right_Wall_Missile_Trap,
+  Special_Right_Wall_Flame_Trap,
+  Special_Start_Location,
+  Powerup_Flag,
+  Powerup_Young_Chrysalis,
+  Powerup_Psi_Emitter,
+  Powerup_Data_Disk,
+  Powerup_Khaydarin_Crystal,
+  Powerup_Mineral_Cluster_Type_1,
+  Powerup_Mineral_Cluster_Type_2,
+  Powerup_Protoss_Gas_Orb_Type_1,
+  Powerup_Protoss_Gas_Orb_Type_2,
+  Powerup_Zerg_Gas_Sac_Type_1,
+  Powerup_Zerg_Gas_Sac_Type_2,
+  Powerup_Terran_Gas_Tank_Type_1,
+  Powerup_Terran_Gas_Tank_Type_2,
+  #[default]
+  None,
+  AllUnits,
+  Men,
+  Buildings,
+  Factories,
+  Unknown,
+  MAX,
+}
+
+struct UnitTypeData {
+  mineral_price: i32,
+  gas_price: i32,
+  tile_width: i32,
+  tile_height: i32,
+  width: i32,
+  height: i32,
+  max_hit_points: i32,
+  max_shields: i32,
+  armor: i32,
+  top_speed: f64,
+  sight_range: i32,
+  max_energy: i32,
+  supply_required: i32,
+  supply_provided: i32,
+  build_time: i32,
+  what_builds: (UnitType, i32),
+  required_units: &'static [(UnitType, i32)],
+  can_attack: bool,
+  can_move: bool,
+  is_flyer: bool,
+  is_building: bool,
+  is_addon: bool,
+  is_detector: bool,
+  is_spellcaster: bool,
+  has_permanent_cloak: bool,
+  is_cloakable: bool,
+  is_burrowable: bool,
+  is_mineral_field: bool,
+  is_refinery: bool,
+  is_resource_depot: bool,
+  is_worker: bool,
+  requires_psi: bool,
+  name: &'static str,
+}
+
+#[allow(clippy::too_many_arguments)]
+const fn d(
+  mineral_price: i32,
+  gas_price: i32,
+  tile_width: i32,
+  tile_height: i32,
+  width: i32,
+  height: i32,
+  max_hit_points: i32,
+  max_shields: i32,
+  armor: i32,
+  top_speed: f64,
+  sight_range: i32,
+  max_energy: i32,
+  supply_required: i32,
+  supply_provided: i32,
+  build_time: i32,
+  what_builds: (UnitType, i32),
+  required_units: &'static [(UnitType, i32)],
+  can_attack: bool,
+  can_move: bool,
+  is_flyer: bool,
+  is_building: bool,
+  is_addon: bool,
+  is_detector: bool,
+  is_spellcaster: bool,
+  has_permanent_cloak: bool,
+  is_cloakable: bool,
+  is_burrowable: bool,
+  is_mineral_field: bool,
+  is_refinery: bool,
+  is_resource_depot: bool,
+  is_worker: bool,
+  requires_psi: bool,
+  name: &'static str,
+) -> UnitTypeData {
+  UnitTypeData {
+    mineral_price,
+    gas_price,
+    tile_width,
+    tile_height,
+    width,
+    height,
+    max_hit_points,
+    max_shields,
+    armor,
+    top_speed,
+    sight_range,
+    max_energy,
+    supply_required,
+    supply_provided,
+    build_time,
+    what_builds,
+    required_units,
+    can_attack,
+    can_move,
+    is_flyer,
+    is_building,
+    is_addon,
+    is_detector,
+    is_spellcaster,
+    has_permanent_cloak,
+    is_cloakable,
+    is_burrowable,
+    is_mineral_field,
+    is_refinery,
+    is_resource_depot,
+    is_worker,
+    requires_psi,
+    name,
+  }
+}
+
+#[rustfmt::skip]
+static UNIT_TYPE_DATA: [UnitTypeData; 234] = [
+  d(50, 0, 1, 1, 17, 20, 40, 0, 0, 4.0, 224, 0, 2, 0, 360, (UnitType::Terran_Barracks, 1), &[(UnitType::Terran_Barracks, 1)], true, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Terran_Marine"),
+  d(25, 75, 1, 1, 15, 22, 45, 0, 0, 4.0, 288, 200, 2, 0, 750, (UnitType::Terran_Barracks, 1), &[(UnitType::Terran_Academy, 1), (UnitType::Terran_Barracks, 1), (UnitType::Terran_Covert_Ops, 1)], true, true, false, false, false, false, true, false, true, false, false, false, false, false, false, "Terran_Ghost"),
+  d(75, 0, 1, 1, 32, 32, 80, 0, 0, 6.4, 256, 0, 4, 0, 450, (UnitType::Terran_Factory, 1), &[(UnitType::Terran_Factory, 1)], true, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Terran_Vulture"),
+  d(100, 50, 1, 1, 32, 32, 125, 0, 1, 4.57, 256, 0, 4, 0, 600, (UnitType::Terran_Factory, 1), &[(UnitType::Terran_Armory, 1), (UnitType::Terran_Factory, 1)], true, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Terran_Goliath"),
+  d(1, 1, 1, 1, 3, 3, 0, 0, 0, 0.0, 256, 0, 0, 0, 1, (UnitType::None, 0), &[], true, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Terran_Goliath_Turret"),
+  d(150, 100, 1, 1, 32, 32, 150, 0, 1, 4.0, 320, 0, 4, 0, 750, (UnitType::Terran_Factory, 1), &[(UnitType::Terran_Factory, 1), (UnitType::Terran_Machine_Shop, 1)], true, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Terran_Siege_Tank_Tank_Mode"),
+  d(1, 1, 1, 1, 3, 3, 0, 0, 0, 0.0, 320, 0, 0, 0, 1, (UnitType::None, 0), &[], true, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Terran_Siege_Tank_Tank_Mode_Turret"),
+  d(50, 0, 1, 1, 23, 23, 60, 0, 0, 4.92, 224, 0, 2, 0, 300, (UnitType::Terran_Command_Center, 1), &[(UnitType::Terran_Command_Center, 1)], true, true, false, false, false, false, false, false, false, false, false, false, false, true, false, "Terran_SCV"),
+  d(150, 100, 1, 1, 38, 30, 120, 0, 0, 6.67, 224, 200, 4, 0, 900, (UnitType::Terran_Starport, 1), &[(UnitType::Terran_Starport, 1)], true, true, true, false, false, false, true, false, true, false, false, false, false, false, false, "Terran_Wraith"),
+  d(100, 225, 2, 2, 65, 50, 200, 0, 1, 5.0, 320, 200, 4, 0, 1200, (UnitType::Terran_Starport, 1), &[(UnitType::Terran_Starport, 1), (UnitType::Terran_Control_Tower, 1), (UnitType::Terran_Science_Facility, 1)], false, true, true, false, false, true, true, false, false, false, false, false, false, false, false, "Terran_Science_Vessel"),
+  d(100, 50, 1, 1, 23, 22, 160, 0, 3, 4.0, 224, 0, 0, 0, 720, (UnitType::None, 0), &[], true, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Hero_Gui_Montag"),
+  d(100, 100, 2, 2, 49, 37, 150, 0, 1, 5.47, 256, 0, 4, 0, 750, (UnitType::Terran_Starport, 1), &[(UnitType::Terran_Starport, 1), (UnitType::Terran_Control_Tower, 1)], false, true, true, false, false, false, false, false, false, false, false, false, false, false, false, "Terran_Dropship"),
+  d(400, 300, 2, 2, 75, 59, 500, 0, 3, 2.5, 352, 200, 12, 0, 2000, (UnitType::Terran_Starport, 1), &[(UnitType::Terran_Starport, 1), (UnitType::Terran_Control_Tower, 1), (UnitType::Terran_Physics_Lab, 1)], true, true, true, false, false, false, true, false, false, false, false, false, false, false, false, "Terran_Battlecruiser"),
+  d(1, 0, 1, 1, 15, 15, 20, 0, 0, 16.0, 96, 0, 0, 0, 1, (UnitType::None, 0), &[], true, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Terran_Vulture_Spider_Mine"),
+  d(200, 200, 1, 1, 15, 29, 100, 0, 0, 33.33, 96, 0, 16, 0, 1500, (UnitType::Terran_Nuclear_Silo, 1), &[(UnitType::Terran_Nuclear_Silo, 1)], true, true, true, false, false, false, false, false, false, false, false, false, false, false, false, "Terran_Nuclear_Missile"),
+  d(0, 0, 1, 1, 17, 20, 40, 0, 0, 4.0, 224, 0, 0, 0, 1, (UnitType::None, 0), &[], false, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Terran_Civilian"),
+  d(50, 150, 1, 1, 15, 22, 250, 0, 3, 4.0, 352, 250, 0, 0, 1500, (UnitType::None, 0), &[], true, true, false, false, false, false, true, false, true, false, false, false, false, false, false, "Hero_Sarah_Kerrigan"),
+  d(200, 100, 1, 1, 32, 32, 300, 0, 3, 4.57, 256, 0, 0, 0, 1200, (UnitType::None, 0), &[], true, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Hero_Alan_Schezar"),
+  d(1, 1, 1, 1, 3, 3, 0, 0, 0, 0.0, 256, 0, 0, 0, 1, (UnitType::None, 0), &[], true, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Hero_Alan_Schezar_Turret"),
+  d(150, 0, 1, 1, 32, 32, 300, 0, 3, 6.4, 256, 0, 0, 0, 900, (UnitType::None, 0), &[], true, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Hero_Jim_Raynor_Vulture"),
+  d(50, 0, 1, 1, 17, 20, 200, 0, 3, 4.0, 224, 0, 0, 0, 1, (UnitType::None, 0), &[], true, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Hero_Jim_Raynor_Marine"),
+  d(400, 200, 1, 1, 38, 30, 500, 0, 4, 6.67, 224, 250, 0, 0, 1800, (UnitType::None, 0), &[], true, true, true, false, false, false, true, false, true, false, false, false, false, false, false, "Hero_Tom_Kazansky"),
+  d(50, 600, 2, 2, 65, 50, 800, 0, 4, 5.0, 320, 250, 0, 0, 2400, (UnitType::None, 0), &[], false, true, true, false, false, true, true, false, false, false, false, false, false, false, false, "Hero_Magellan"),
+  d(300, 200, 1, 1, 32, 32, 400, 0, 3, 4.0, 320, 0, 0, 0, 1500, (UnitType::None, 0), &[], true, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Hero_Edmund_Duke_Tank_Mode"),
+  d(1, 1, 1, 1, 3, 3, 0, 0, 0, 0.0, 320, 0, 0, 0, 1, (UnitType::None, 0), &[], true, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Hero_Edmund_Duke_Tank_Mode_Turret"),
+  d(300, 200, 1, 1, 32, 32, 400, 0, 3, 0.0, 320, 0, 0, 0, 1500, (UnitType::None, 0), &[], true, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Hero_Edmund_Duke_Siege_Mode"),
+  d(1, 1, 1, 1, 3, 3, 0, 0, 0, 0.0, 320, 0, 0, 0, 1, (UnitType::None, 0), &[], true, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Hero_Edmund_Duke_Siege_Mode_Turret"),
+  d(800, 600, 2, 2, 75, 59, 1000, 0, 4, 2.5, 256, 250, 0, 0, 4800, (UnitType::None, 0), &[], true, true, true, false, false, false, true, false, false, false, false, false, false, false, false, "Hero_Arcturus_Mengsk"),
+  d(800, 600, 2, 2, 75, 59, 850, 0, 4, 2.5, 352, 250, 0, 0, 2400, (UnitType::None, 0), &[], true, true, true, false, false, false, true, false, false, false, false, false, false, false, false, "Hero_Hyperion"),
+  d(800, 600, 2, 2, 75, 59, 700, 0, 4, 2.5, 352, 250, 0, 0, 4800, (UnitType::None, 0), &[], true, true, true, false, false, false, true, false, false, false, false, false, false, false, false, "Hero_Norad_II"),
+  d(150, 100, 1, 1, 32, 32, 150, 0, 1, 0.0, 320, 0, 4, 0, 750, (UnitType::Terran_Factory, 1), &[(UnitType::Terran_Factory, 1), (UnitType::Terran_Machine_Shop, 1)], true, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Terran_Siege_Tank_Siege_Mode"),
+  d(1, 1, 1, 1, 3, 3, 0, 0, 0, 0.0, 320, 0, 0, 0, 1, (UnitType::None, 0), &[], true, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Terran_Siege_Tank_Siege_Mode_Turret"),
+  d(50, 25, 1, 1, 23, 22, 50, 0, 1, 4.0, 224, 0, 2, 0, 360, (UnitType::Terran_Barracks, 1), &[(UnitType::Terran_Academy, 1), (UnitType::Terran_Barracks, 1)], true, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Terran_Firebat"),
+  d(0, 0, 1, 1, 27, 31, 0, 0, 0, 0.0, 320, 0, 0, 0, 1, (UnitType::None, 0), &[], false, true, true, false, false, true, false, false, false, false, false, false, false, false, false, "Spell_Scanner_Sweep"),
+  d(50, 25, 1, 1, 17, 20, 60, 0, 1, 4.0, 288, 200, 2, 0, 450, (UnitType::Terran_Barracks, 1), &[(UnitType::Terran_Academy, 1), (UnitType::Terran_Barracks, 1)], false, true, false, false, false, false, true, false, false, false, false, false, false, false, false, "Terran_Medic"),
+  d(1, 1, 1, 1, 16, 16, 25, 0, 10, 0.0, 128, 0, 0, 0, 1, (UnitType::Zerg_Hatchery, 1), &[(UnitType::Zerg_Hatchery, 1)], false, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Zerg_Larva"),
+  d(1, 1, 1, 1, 32, 32, 200, 0, 10, 0.0, 128, 0, 0, 0, 1, (UnitType::Zerg_Larva, 1), &[(UnitType::Zerg_Larva, 1)], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Zerg_Egg"),
+  d(50, 0, 1, 1, 16, 16, 35, 0, 0, 5.49, 160, 0, 1, 0, 420, (UnitType::Zerg_Larva, 1), &[(UnitType::Zerg_Spawning_Pool, 1), (UnitType::Zerg_Larva, 1)], true, true, false, false, false, false, false, false, false, true, false, false, false, false, false, "Zerg_Zergling"),
+  d(75, 25, 1, 1, 21, 23, 80, 0, 0, 3.66, 192, 0, 2, 0, 420, (UnitType::Zerg_Larva, 1), &[(UnitType::Zerg_Hydralisk_Den, 1), (UnitType::Zerg_Larva, 1)], true, true, false, false, false, false, false, false, false, true, false, false, false, false, false, "Zerg_Hydralisk"),
+  d(200, 200, 2, 2, 38, 32, 400, 0, 1, 5.12, 224, 0, 8, 0, 900, (UnitType::Zerg_Larva, 1), &[(UnitType::Zerg_Ultralisk_Cavern, 1), (UnitType::Zerg_Larva, 1)], true, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Zerg_Ultralisk"),
+  d(1, 1, 1, 1, 19, 19, 30, 0, 0, 6.0, 160, 0, 0, 0, 1, (UnitType::None, 0), &[], true, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Zerg_Broodling"),
+  d(50, 0, 1, 1, 23, 23, 40, 0, 0, 4.92, 224, 0, 2, 0, 300, (UnitType::Zerg_Larva, 1), &[(UnitType::Zerg_Larva, 1)], true, true, false, false, false, false, false, false, false, true, false, false, false, true, false, "Zerg_Drone"),
+  d(100, 0, 2, 2, 50, 50, 200, 0, 0, 0.83, 288, 0, 0, 16, 600, (UnitType::Zerg_Larva, 1), &[(UnitType::Zerg_Larva, 1)], false, true, true, false, false, true, false, false, false, false, false, false, false, false, false, "Zerg_Overlord"),
+  d(100, 100, 2, 2, 44, 44, 120, 0, 0, 6.67, 224, 0, 4, 0, 600, (UnitType::Zerg_Larva, 1), &[(UnitType::Zerg_Spire, 1), (UnitType::Zerg_Larva, 1)], true, true, true, false, false, false, false, false, false, false, false, false, false, false, false, "Zerg_Mutalisk"),
+  d(50, 100, 2, 2, 44, 44, 150, 0, 2, 2.5, 352, 0, 4, 0, 600, (UnitType::Zerg_Mutalisk, 1), &[(UnitType::Zerg_Mutalisk, 1), (UnitType::Zerg_Greater_Spire, 1)], true, true, true, false, false, false, false, false, false, false, false, false, false, false, false, "Zerg_Guardian"),
+  d(100, 100, 2, 2, 48, 48, 120, 0, 0, 6.67, 320, 200, 4, 0, 750, (UnitType::Zerg_Larva, 1), &[(UnitType::Zerg_Queens_Nest, 1), (UnitType::Zerg_Larva, 1)], false, true, true, false, false, false, true, false, false, false, false, false, false, false, false, "Zerg_Queen"),
+  d(50, 150, 1, 1, 27, 25, 80, 0, 1, 4.0, 320, 200, 4, 0, 750, (UnitType::Zerg_Larva, 1), &[(UnitType::Zerg_Defiler_Mound, 1), (UnitType::Zerg_Larva, 1)], false, true, false, false, false, false, true, false, false, true, false, false, false, false, false, "Zerg_Defiler"),
+  d(25, 75, 1, 1, 24, 24, 25, 0, 0, 6.67, 160, 0, 1, 0, 450, (UnitType::Zerg_Larva, 1), &[(UnitType::Zerg_Spire, 1), (UnitType::Zerg_Larva, 1)], true, true, true, false, false, false, false, false, false, false, false, false, false, false, false, "Zerg_Scourge"),
+  d(400, 400, 2, 2, 38, 32, 800, 0, 4, 5.12, 224, 0, 0, 0, 1800, (UnitType::None, 0), &[], true, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Hero_Torrasque"),
+  d(200, 300, 2, 2, 48, 48, 300, 0, 3, 6.67, 320, 250, 0, 0, 1500, (UnitType::None, 0), &[], false, true, true, false, false, false, true, false, false, false, false, false, false, false, false, "Hero_Matriarch"),
+  d(100, 50, 1, 1, 17, 20, 60, 0, 0, 5.82, 160, 0, 2, 0, 600, (UnitType::Zerg_Infested_Command_Center, 1), &[(UnitType::Zerg_Infested_Command_Center, 1)], true, true, false, false, false, false, false, false, false, true, false, false, false, false, false, "Zerg_Infested_Terran"),
+  d(200, 300, 1, 1, 15, 22, 400, 0, 2, 4.0, 288, 250, 0, 0, 1500, (UnitType::None, 0), &[], true, true, false, false, false, false, true, false, true, false, false, false, false, false, false, "Hero_Infested_Kerrigan"),
+  d(50, 200, 1, 1, 27, 25, 250, 0, 3, 4.0, 320, 250, 0, 0, 1500, (UnitType::None, 0), &[], false, true, false, false, false, false, true, false, false, true, false, false, false, false, false, "Hero_Unclean_One"),
+  d(150, 50, 1, 1, 21, 23, 160, 0, 2, 3.66, 256, 0, 0, 0, 780, (UnitType::None, 0), &[], true, true, false, false, false, false, false, false, false, true, false, false, false, false, false, "Hero_Hunter_Killer"),
+  d(100, 0, 1, 1, 16, 16, 120, 0, 3, 5.49, 160, 0, 0, 0, 840, (UnitType::None, 0), &[], true, true, false, false, false, false, false, false, false, true, false, false, false, false, false, "Hero_Devouring_One"),
+  d(200, 200, 2, 2, 44, 44, 300, 0, 3, 6.67, 224, 0, 0, 0, 1200, (UnitType::None, 0), &[], true, true, true, false, false, false, false, false, false, false, false, false, false, false, false, "Hero_Kukulza_Mutalisk"),
+  d(100, 200, 2, 2, 44, 44, 400, 0, 4, 2.5, 352, 0, 0, 0, 1200, (UnitType::None, 0), &[], true, true, true, false, false, false, false, false, false, false, false, false, false, false, false, "Hero_Kukulza_Guardian"),
+  d(200, 0, 2, 2, 50, 50, 1000, 0, 4, 0.83, 352, 0, 0, 60, 1200, (UnitType::None, 0), &[], false, true, true, false, false, true, false, false, false, false, false, false, false, false, false, "Hero_Yggdrasill"),
+  d(250, 125, 2, 2, 49, 37, 200, 0, 2, 6.6, 256, 0, 6, 0, 750, (UnitType::Terran_Starport, 1), &[(UnitType::Terran_Armory, 1), (UnitType::Terran_Starport, 1), (UnitType::Terran_Control_Tower, 1)], true, true, true, false, false, false, false, false, false, false, false, false, false, false, false, "Terran_Valkyrie"),
+  d(1, 1, 1, 1, 32, 32, 200, 0, 0, 0.0, 128, 0, 0, 0, 1, (UnitType::Zerg_Mutalisk, 1), &[(UnitType::Zerg_Mutalisk, 1), (UnitType::Zerg_Greater_Spire, 1)], false, false, true, false, false, false, false, false, false, false, false, false, false, false, false, "Zerg_Cocoon"),
+  d(150, 100, 1, 1, 36, 32, 100, 80, 1, 6.67, 288, 200, 4, 0, 600, (UnitType::Protoss_Stargate, 1), &[(UnitType::Protoss_Stargate, 1)], true, true, true, false, false, false, true, false, false, false, false, false, false, false, false, "Protoss_Corsair"),
+  d(125, 100, 1, 1, 24, 26, 80, 40, 1, 4.92, 224, 0, 4, 0, 750, (UnitType::Protoss_Gateway, 1), &[(UnitType::Protoss_Gateway, 1), (UnitType::Protoss_Templar_Archives, 1)], true, true, false, false, false, false, false, true, false, false, false, false, false, false, false, "Protoss_Dark_Templar"),
+  d(150, 50, 2, 2, 44, 44, 250, 0, 2, 5.0, 320, 0, 4, 0, 600, (UnitType::Zerg_Mutalisk, 1), &[(UnitType::Zerg_Mutalisk, 1), (UnitType::Zerg_Greater_Spire, 1)], true, true, true, false, false, false, false, false, false, false, false, false, false, false, false, "Zerg_Devourer"),
+  d(0, 0, 1, 1, 32, 32, 25, 200, 1, 4.92, 320, 200, 8, 0, 300, (UnitType::Protoss_Dark_Templar, 2), &[(UnitType::Protoss_Dark_Templar, 2)], false, true, false, false, false, false, true, false, false, false, false, false, false, false, false, "Protoss_Dark_Archon"),
+  d(50, 0, 1, 1, 23, 23, 20, 20, 0, 4.92, 256, 0, 2, 0, 300, (UnitType::Protoss_Nexus, 1), &[(UnitType::Protoss_Nexus, 1)], true, true, false, false, false, false, false, false, false, false, false, false, false, true, false, "Protoss_Probe"),
+  d(100, 0, 1, 1, 23, 19, 100, 60, 1, 4.0, 224, 0, 4, 0, 600, (UnitType::Protoss_Gateway, 1), &[(UnitType::Protoss_Gateway, 1)], true, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Protoss_Zealot"),
+  d(125, 50, 1, 1, 32, 32, 100, 80, 1, 5.0, 256, 0, 4, 0, 750, (UnitType::Protoss_Gateway, 1), &[(UnitType::Protoss_Gateway, 1), (UnitType::Protoss_Cybernetics_Core, 1)], true, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Protoss_Dragoon"),
+  d(50, 150, 1, 1, 24, 24, 40, 40, 0, 3.2, 224, 200, 4, 0, 750, (UnitType::Protoss_Gateway, 1), &[(UnitType::Protoss_Gateway, 1), (UnitType::Protoss_Templar_Archives, 1)], false, true, false, false, false, false, true, false, false, false, false, false, false, false, false, "Protoss_High_Templar"),
+  d(0, 0, 1, 1, 32, 32, 10, 350, 0, 4.92, 256, 0, 8, 0, 300, (UnitType::Protoss_High_Templar, 2), &[(UnitType::Protoss_High_Templar, 2)], true, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Protoss_Archon"),
+  d(200, 0, 2, 1, 40, 32, 80, 60, 1, 4.43, 256, 0, 4, 0, 900, (UnitType::Protoss_Robotics_Facility, 1), &[(UnitType::Protoss_Robotics_Facility, 1)], false, true, true, false, false, false, false, false, false, false, false, false, false, false, false, "Protoss_Shuttle"),
+  d(275, 125, 2, 1, 36, 32, 150, 100, 0, 5.0, 256, 0, 6, 0, 1200, (UnitType::Protoss_Stargate, 1), &[(UnitType::Protoss_Stargate, 1)], true, true, true, false, false, false, false, false, false, false, false, false, false, false, false, "Protoss_Scout"),
+  d(100, 350, 2, 2, 44, 44, 200, 150, 1, 5.0, 288, 200, 8, 0, 2400, (UnitType::Protoss_Stargate, 1), &[(UnitType::Protoss_Arbiter_Tribunal, 1), (UnitType::Protoss_Stargate, 1)], true, true, true, false, false, false, true, false, false, false, false, false, false, false, false, "Protoss_Arbiter"),
+  d(350, 250, 2, 2, 64, 64, 300, 150, 4, 3.33, 352, 0, 12, 0, 2100, (UnitType::Protoss_Stargate, 1), &[(UnitType::Protoss_Stargate, 1), (UnitType::Protoss_Fleet_Beacon, 1)], true, true, true, false, false, false, false, false, false, false, false, false, false, false, false, "Protoss_Carrier"),
+  d(25, 0, 1, 1, 16, 16, 40, 40, 0, 13.33, 192, 0, 0, 0, 300, (UnitType::Protoss_Carrier, 1), &[(UnitType::Protoss_Carrier, 1)], true, true, true, false, false, false, false, false, false, false, false, false, false, false, false, "Protoss_Interceptor"),
+  d(150, 150, 1, 1, 24, 26, 40, 80, 0, 4.92, 224, 0, 2, 0, 750, (UnitType::None, 0), &[], true, true, false, false, false, false, false, true, false, false, false, false, false, false, false, "Hero_Dark_Templar"),
+  d(100, 300, 1, 1, 24, 26, 60, 400, 0, 4.92, 224, 0, 0, 0, 1500, (UnitType::None, 0), &[], true, true, false, false, false, false, false, true, false, false, false, false, false, false, false, "Hero_Zeratul"),
+  d(0, 0, 1, 1, 32, 32, 100, 800, 3, 4.92, 256, 0, 0, 0, 600, (UnitType::None, 0), &[], true, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Hero_Tassadar_Zeratul_Archon"),
+  d(200, 0, 1, 1, 23, 19, 240, 240, 2, 4.0, 224, 0, 0, 0, 1200, (UnitType::None, 0), &[], true, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Hero_Fenix_Zealot"),
+  d(300, 100, 1, 1, 32, 32, 240, 240, 3, 5.0, 256, 0, 0, 0, 1500, (UnitType::None, 0), &[], true, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Hero_Fenix_Dragoon"),
+  d(100, 300, 1, 1, 24, 24, 80, 300, 2, 3.2, 224, 250, 0, 0, 1500, (UnitType::None, 0), &[], true, true, false, false, false, false, true, false, false, false, false, false, false, false, false, "Hero_Tassadar"),
+  d(600, 300, 2, 1, 36, 32, 400, 400, 3, 5.0, 320, 0, 0, 0, 2400, (UnitType::None, 0), &[], true, true, true, false, false, false, false, false, false, false, false, false, false, false, false, "Hero_Mojo"),
+  d(400, 200, 1, 1, 32, 32, 200, 400, 3, 1.78, 320, 0, 0, 0, 1800, (UnitType::None, 0), &[], true, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Hero_Warbringer"),
+  d(700, 600, 2, 2, 64, 64, 800, 500, 4, 3.33, 288, 0, 0, 0, 4200, (UnitType::None, 0), &[], true, true, true, false, false, false, false, false, false, false, false, false, false, false, false, "Hero_Gantrithor"),
+  d(200, 100, 1, 1, 32, 32, 100, 80, 0, 1.78, 320, 0, 8, 0, 1050, (UnitType::Protoss_Robotics_Facility, 1), &[(UnitType::Protoss_Robotics_Support_Bay, 1), (UnitType::Protoss_Robotics_Facility, 1)], true, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Protoss_Reaver"),
+  d(25, 75, 1, 1, 32, 32, 40, 20, 0, 3.33, 288, 0, 2, 0, 600, (UnitType::Protoss_Robotics_Facility, 1), &[(UnitType::Protoss_Observatory, 1), (UnitType::Protoss_Robotics_Facility, 1)], false, true, true, false, false, true, false, true, false, false, false, false, false, false, false, "Protoss_Observer"),
+  d(15, 0, 1, 1, 5, 5, 20, 10, 0, 16.0, 160, 0, 0, 0, 105, (UnitType::Protoss_Reaver, 1), &[(UnitType::Protoss_Reaver, 1)], true, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Protoss_Scarab"),
+  d(50, 1000, 2, 2, 44, 44, 600, 500, 3, 5.0, 288, 250, 0, 0, 4800, (UnitType::None, 0), &[], true, true, true, false, false, false, true, false, false, false, false, false, false, false, false, "Hero_Danimoth"),
+  d(100, 300, 1, 1, 24, 24, 80, 300, 2, 3.2, 224, 250, 0, 0, 1500, (UnitType::None, 0), &[], true, true, false, false, false, false, true, false, false, false, false, false, false, false, false, "Hero_Aldaris"),
+  d(600, 300, 2, 1, 36, 32, 250, 250, 3, 5.0, 320, 0, 0, 0, 2400, (UnitType::None, 0), &[], true, true, true, false, false, false, false, false, false, false, false, false, false, false, false, "Hero_Artanis"),
+  d(1, 1, 1, 1, 32, 32, 60, 0, 0, 4.0, 224, 0, 0, 0, 1, (UnitType::None, 0), &[], false, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Critter_Rhynadon"),
+  d(1, 1, 1, 1, 32, 32, 60, 0, 0, 4.0, 224, 0, 0, 0, 1, (UnitType::None, 0), &[], false, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Critter_Bengalaas"),
+  d(100, 100, 1, 1, 32, 32, 125, 0, 1, 0.0, 256, 0, 0, 0, 600, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Special_Cargo_Ship"),
+  d(100, 100, 1, 1, 32, 32, 125, 0, 1, 0.0, 224, 0, 0, 0, 600, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Special_Mercenary_Gunship"),
+  d(1, 1, 1, 1, 32, 32, 60, 0, 0, 4.0, 224, 0, 0, 0, 1, (UnitType::None, 0), &[], false, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Critter_Scantid"),
+  d(1, 1, 1, 1, 32, 32, 60, 0, 0, 5.0, 224, 0, 0, 0, 1, (UnitType::None, 0), &[], false, true, true, false, false, false, false, false, false, false, false, false, false, false, false, "Critter_Kakaru"),
+  d(1, 1, 1, 1, 32, 32, 60, 0, 0, 4.0, 224, 0, 0, 0, 1, (UnitType::None, 0), &[], false, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Critter_Ragnasaur"),
+  d(1, 1, 1, 1, 32, 32, 60, 0, 0, 4.0, 224, 0, 0, 0, 1, (UnitType::None, 0), &[], false, true, false, false, false, false, false, false, false, false, false, false, false, false, false, "Critter_Ursadon"),
+  d(1, 1, 1, 1, 32, 32, 200, 0, 10, 0.0, 128, 0, 0, 0, 1, (UnitType::Zerg_Hydralisk, 1), &[(UnitType::Zerg_Hydralisk, 1)], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Zerg_Lurker_Egg"),
+  d(150, 100, 1, 1, 36, 32, 100, 60, 0, 6.67, 288, 250, 0, 0, 750, (UnitType::None, 0), &[], true, true, true, false, false, false, true, false, false, false, false, false, false, false, false, "Hero_Raszagal"),
+  d(200, 75, 1, 1, 15, 22, 200, 0, 2, 4.0, 320, 250, 0, 0, 1500, (UnitType::None, 0), &[], true, true, false, false, false, false, true, false, true, false, false, false, false, false, false, "Hero_Samir_Duran"),
+  d(200, 75, 1, 1, 15, 22, 250, 0, 3, 4.0, 352, 250, 0, 0, 1500, (UnitType::None, 0), &[], true, true, false, false, false, false, true, false, true, false, false, false, false, false, false, "Hero_Alexei_Stukov"),
+  d(0, 0, 1, 1, 27, 31, 1, 0, 0, 0.0, 320, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, true, false, false, false, false, false, false, false, false, false, false, false, false, "Special_Map_Revealer"),
+  d(800, 600, 2, 2, 75, 59, 700, 0, 4, 2.5, 352, 250, 0, 0, 4800, (UnitType::None, 0), &[], true, true, true, false, false, false, true, false, false, false, false, false, false, false, false, "Hero_Gerard_DuGalle"),
+  d(50, 100, 1, 1, 32, 32, 125, 0, 1, 5.82, 256, 0, 4, 0, 600, (UnitType::Zerg_Hydralisk, 1), &[(UnitType::Zerg_Hydralisk, 1)], true, true, false, false, false, false, false, false, false, true, false, false, false, false, false, "Zerg_Lurker"),
+  d(200, 75, 1, 1, 15, 22, 300, 0, 3, 4.0, 352, 250, 0, 0, 1500, (UnitType::None, 0), &[], true, true, false, false, false, false, true, false, true, false, false, false, false, false, false, "Hero_Infested_Duran"),
+  d(250, 250, 4, 3, 120, 80, 800, 0, 0, 0.0, 256, 0, 0, 0, 2400, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Spell_Disruption_Web"),
+  d(400, 0, 4, 3, 117, 83, 1500, 0, 1, 1.0, 320, 0, 0, 20, 1800, (UnitType::Terran_SCV, 1), &[(UnitType::Terran_SCV, 1)], false, false, false, true, false, false, false, false, false, false, false, false, true, false, false, "Terran_Command_Center"),
+  d(50, 50, 2, 2, 69, 42, 500, 0, 1, 0.0, 320, 200, 0, 0, 600, (UnitType::Terran_Command_Center, 1), &[(UnitType::Terran_Command_Center, 1), (UnitType::Terran_Academy, 1)], false, false, false, true, true, false, true, false, false, false, false, false, false, false, false, "Terran_Comsat_Station"),
+  d(100, 100, 2, 2, 69, 42, 600, 0, 1, 0.0, 256, 0, 0, 0, 1200, (UnitType::Terran_Command_Center, 1), &[(UnitType::Terran_Command_Center, 1), (UnitType::Terran_Science_Facility, 1), (UnitType::Terran_Covert_Ops, 1)], false, false, false, true, true, false, false, false, false, false, false, false, false, false, false, "Terran_Nuclear_Silo"),
+  d(100, 0, 3, 2, 77, 49, 500, 0, 1, 0.0, 256, 0, 0, 16, 600, (UnitType::Terran_SCV, 1), &[(UnitType::Terran_SCV, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Terran_Supply_Depot"),
+  d(100, 0, 4, 2, 113, 64, 750, 0, 1, 0.0, 256, 0, 0, 0, 600, (UnitType::Terran_SCV, 1), &[(UnitType::Terran_SCV, 1)], false, false, false, true, false, false, false, false, false, false, false, true, false, false, false, "Terran_Refinery"),
+  d(150, 0, 4, 3, 105, 73, 1000, 0, 1, 1.0, 256, 0, 0, 0, 1200, (UnitType::Terran_SCV, 1), &[(UnitType::Terran_Command_Center, 1), (UnitType::Terran_SCV, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Terran_Barracks"),
+  d(150, 0, 3, 2, 85, 57, 600, 0, 1, 0.0, 256, 0, 0, 0, 1200, (UnitType::Terran_SCV, 1), &[(UnitType::Terran_SCV, 1), (UnitType::Terran_Barracks, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Terran_Academy"),
+  d(200, 100, 4, 3, 113, 81, 1250, 0, 1, 1.0, 256, 0, 0, 0, 1200, (UnitType::Terran_SCV, 1), &[(UnitType::Terran_SCV, 1), (UnitType::Terran_Barracks, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Terran_Factory"),
+  d(150, 100, 4, 3, 97, 79, 1300, 0, 1, 1.0, 320, 0, 0, 0, 1050, (UnitType::Terran_SCV, 1), &[(UnitType::Terran_Factory, 1), (UnitType::Terran_SCV, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Terran_Starport"),
+  d(50, 50, 2, 2, 76, 47, 500, 0, 1, 0.0, 256, 0, 0, 0, 600, (UnitType::Terran_Starport, 1), &[(UnitType::Terran_Starport, 1)], false, false, false, true, true, false, false, false, false, false, false, false, false, false, false, "Terran_Control_Tower"),
+  d(100, 150, 4, 3, 97, 77, 850, 0, 1, 1.0, 320, 0, 0, 0, 900, (UnitType::Terran_SCV, 1), &[(UnitType::Terran_Starport, 1), (UnitType::Terran_SCV, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Terran_Science_Facility"),
+  d(50, 50, 2, 2, 76, 47, 750, 0, 1, 0.0, 256, 0, 0, 0, 600, (UnitType::Terran_Science_Facility, 1), &[(UnitType::Terran_Science_Facility, 1)], false, false, false, true, true, false, false, false, false, false, false, false, false, false, false, "Terran_Covert_Ops"),
+  d(50, 50, 2, 2, 76, 47, 600, 0, 1, 0.0, 256, 0, 0, 0, 600, (UnitType::Terran_Science_Facility, 1), &[(UnitType::Terran_Science_Facility, 1)], false, false, false, true, true, false, false, false, false, false, false, false, false, false, false, "Terran_Physics_Lab"),
+  d(1, 1, 3, 2, 96, 64, 0, 0, 1, 0.0, 256, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Unused_Terran1"),
+  d(50, 50, 2, 2, 71, 49, 750, 0, 1, 0.0, 256, 0, 0, 0, 600, (UnitType::Terran_Factory, 1), &[(UnitType::Terran_Factory, 1)], false, false, false, true, true, false, false, false, false, false, false, false, false, false, false, "Terran_Machine_Shop"),
+  d(1, 1, 3, 3, 96, 96, 0, 0, 1, 0.0, 256, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, true, false, false, false, false, false, false, false, false, false, false, "Unused_Terran2"),
+  d(125, 0, 4, 3, 97, 61, 850, 0, 1, 1.0, 256, 0, 0, 0, 900, (UnitType::Terran_SCV, 1), &[(UnitType::Terran_Command_Center, 1), (UnitType::Terran_SCV, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Terran_Engineering_Bay"),
+  d(100, 50, 3, 2, 96, 55, 750, 0, 1, 0.0, 256, 0, 0, 0, 1200, (UnitType::Terran_SCV, 1), &[(UnitType::Terran_Factory, 1), (UnitType::Terran_SCV, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Terran_Armory"),
+  d(75, 0, 2, 2, 33, 49, 200, 0, 0, 0.0, 352, 0, 0, 0, 450, (UnitType::Terran_SCV, 1), &[(UnitType::Terran_Engineering_Bay, 1), (UnitType::Terran_SCV, 1)], true, false, false, true, false, true, false, false, false, false, false, false, false, false, false, "Terran_Missile_Turret"),
+  d(100, 0, 3, 2, 65, 41, 350, 0, 1, 0.0, 320, 0, 0, 0, 450, (UnitType::Terran_SCV, 1), &[(UnitType::Terran_SCV, 1), (UnitType::Terran_Barracks, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Terran_Bunker"),
+  d(800, 600, 3, 2, 96, 64, 700, 0, 1, 0.0, 320, 0, 0, 0, 4800, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Special_Crashed_Norad_II"),
+  d(200, 0, 3, 2, 96, 64, 2000, 0, 1, 0.0, 256, 0, 0, 0, 900, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Special_Ion_Cannon"),
+  d(1, 1, 1, 1, 32, 32, 10000, 0, 0, 0.0, 160, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Powerup_Uraj_Crystal"),
+  d(1, 1, 1, 1, 32, 32, 10000, 0, 0, 0.0, 160, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Powerup_Khalis_Crystal"),
+  d(1, 1, 4, 3, 117, 83, 1500, 0, 1, 1.0, 320, 0, 0, 0, 1800, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Zerg_Infested_Command_Center"),
+  d(300, 0, 4, 3, 99, 65, 1250, 0, 1, 0.0, 288, 0, 0, 2, 1800, (UnitType::Zerg_Drone, 1), &[(UnitType::Zerg_Drone, 1)], false, false, false, true, false, false, false, false, false, false, false, false, true, false, false, "Zerg_Hatchery"),
+  d(150, 100, 4, 3, 99, 65, 1800, 0, 1, 0.0, 320, 0, 0, 2, 1500, (UnitType::Zerg_Hatchery, 1), &[(UnitType::Zerg_Spawning_Pool, 1), (UnitType::Zerg_Hatchery, 1)], false, false, false, true, false, false, false, false, false, false, false, false, true, false, false, "Zerg_Lair"),
+  d(200, 150, 4, 3, 99, 65, 2500, 0, 1, 0.0, 352, 0, 0, 2, 1800, (UnitType::Zerg_Lair, 1), &[(UnitType::Zerg_Lair, 1), (UnitType::Zerg_Queens_Nest, 1)], false, false, false, true, false, false, false, false, false, false, false, false, true, false, false, "Zerg_Hive"),
+  d(150, 0, 2, 2, 64, 64, 250, 0, 1, 0.0, 256, 0, 0, 0, 600, (UnitType::Zerg_Drone, 1), &[(UnitType::Zerg_Hive, 1), (UnitType::Zerg_Drone, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Zerg_Nydus_Canal"),
+  d(100, 50, 3, 2, 81, 57, 850, 0, 1, 0.0, 256, 0, 0, 0, 600, (UnitType::Zerg_Drone, 1), &[(UnitType::Zerg_Spawning_Pool, 1), (UnitType::Zerg_Drone, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Zerg_Hydralisk_Den"),
+  d(100, 100, 4, 2, 97, 37, 850, 0, 1, 0.0, 256, 0, 0, 0, 900, (UnitType::Zerg_Drone, 1), &[(UnitType::Zerg_Hive, 1), (UnitType::Zerg_Drone, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Zerg_Defiler_Mound"),
+  d(100, 150, 2, 2, 57, 57, 1000, 0, 1, 0.0, 256, 0, 0, 0, 1800, (UnitType::Zerg_Spire, 1), &[(UnitType::Zerg_Hive, 1), (UnitType::Zerg_Spire, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Zerg_Greater_Spire"),
+  d(150, 100, 3, 2, 71, 57, 850, 0, 1, 0.0, 256, 0, 0, 0, 900, (UnitType::Zerg_Drone, 1), &[(UnitType::Zerg_Lair, 1), (UnitType::Zerg_Drone, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Zerg_Queens_Nest"),
+  d(75, 0, 3, 2, 77, 53, 750, 0, 1, 0.0, 256, 0, 0, 0, 600, (UnitType::Zerg_Drone, 1), &[(UnitType::Zerg_Hatchery, 1), (UnitType::Zerg_Drone, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Zerg_Evolution_Chamber"),
+  d(150, 200, 3, 2, 73, 64, 600, 0, 1, 0.0, 256, 0, 0, 0, 1200, (UnitType::Zerg_Drone, 1), &[(UnitType::Zerg_Hive, 1), (UnitType::Zerg_Drone, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Zerg_Ultralisk_Cavern"),
+  d(200, 150, 2, 2, 57, 57, 600, 0, 1, 0.0, 256, 0, 0, 0, 1800, (UnitType::Zerg_Drone, 1), &[(UnitType::Zerg_Lair, 1), (UnitType::Zerg_Drone, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Zerg_Spire"),
+  d(200, 0, 3, 2, 77, 47, 750, 0, 1, 0.0, 256, 0, 0, 0, 1200, (UnitType::Zerg_Drone, 1), &[(UnitType::Zerg_Hatchery, 1), (UnitType::Zerg_Drone, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Zerg_Spawning_Pool"),
+  d(75, 0, 2, 2, 48, 48, 400, 0, 0, 0.0, 320, 0, 0, 0, 300, (UnitType::Zerg_Drone, 1), &[(UnitType::Zerg_Drone, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Zerg_Creep_Colony"),
+  d(50, 0, 2, 2, 48, 48, 400, 0, 0, 0.0, 320, 0, 0, 0, 300, (UnitType::Zerg_Creep_Colony, 1), &[(UnitType::Zerg_Creep_Colony, 1), (UnitType::Zerg_Evolution_Chamber, 1)], true, false, false, true, false, true, false, false, false, false, false, false, false, false, false, "Zerg_Spore_Colony"),
+  d(1, 1, 3, 3, 96, 96, 0, 0, 1, 0.0, 224, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Unused_Zerg1"),
+  d(50, 0, 2, 2, 48, 48, 300, 0, 2, 0.0, 320, 0, 0, 0, 300, (UnitType::Zerg_Creep_Colony, 1), &[(UnitType::Zerg_Creep_Colony, 1), (UnitType::Zerg_Spawning_Pool, 1)], true, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Zerg_Sunken_Colony"),
+  d(1, 1, 5, 3, 160, 73, 5000, 0, 1, 0.0, 256, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, true, false, false, false, false, false, false, false, false, false, "Special_Overmind_With_Shell"),
+  d(1, 1, 5, 3, 160, 73, 2500, 0, 1, 0.0, 256, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, true, false, false, false, false, false, false, false, false, false, "Special_Overmind"),
+  d(50, 0, 4, 2, 128, 64, 750, 0, 1, 0.0, 224, 0, 0, 0, 600, (UnitType::Zerg_Drone, 1), &[(UnitType::Zerg_Drone, 1)], false, false, false, true, false, false, false, false, false, false, false, true, false, false, false, "Zerg_Extractor"),
+  d(0, 0, 2, 2, 64, 64, 250, 0, 1, 0.0, 256, 0, 0, 0, 0, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Special_Mature_Chrysalis"),
+  d(0, 0, 3, 2, 73, 64, 1500, 0, 1, 0.0, 256, 0, 0, 0, 0, (UnitType::None, 0), &[], false, false, false, true, false, true, false, false, false, false, false, false, false, false, false, "Special_Cerebrate"),
+  d(0, 0, 3, 2, 73, 64, 1500, 0, 1, 0.0, 256, 0, 0, 0, 0, (UnitType::None, 0), &[], false, false, false, true, false, true, false, false, false, false, false, false, false, false, false, "Special_Cerebrate_Daggoth"),
+  d(1, 1, 1, 1, 32, 32, 0, 0, 1, 0.0, 224, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Unused_Zerg2"),
+  d(400, 0, 4, 3, 113, 79, 750, 750, 1, 0.0, 352, 0, 0, 18, 1800, (UnitType::Protoss_Probe, 1), &[(UnitType::Protoss_Probe, 1)], false, false, false, true, false, false, false, false, false, false, false, false, true, false, false, "Protoss_Nexus"),
+  d(200, 200, 3, 2, 77, 37, 500, 500, 1, 0.0, 320, 0, 0, 0, 1200, (UnitType::Protoss_Probe, 1), &[(UnitType::Protoss_Cybernetics_Core, 1), (UnitType::Protoss_Probe, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, true, "Protoss_Robotics_Facility"),
+  d(100, 0, 2, 2, 33, 33, 300, 300, 0, 0.0, 256, 0, 0, 16, 450, (UnitType::Protoss_Probe, 1), &[(UnitType::Protoss_Probe, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Protoss_Pylon"),
+  d(100, 0, 4, 2, 97, 57, 450, 450, 1, 0.0, 320, 0, 0, 0, 600, (UnitType::Protoss_Probe, 1), &[(UnitType::Protoss_Probe, 1)], false, false, false, true, false, false, false, false, false, false, false, true, false, false, false, "Protoss_Assimilator"),
+  d(1, 1, 4, 3, 128, 96, 300, 300, 1, 0.0, 224, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, true, "Unused_Protoss1"),
+  d(50, 100, 3, 2, 89, 45, 250, 250, 1, 0.0, 320, 0, 0, 0, 450, (UnitType::Protoss_Probe, 1), &[(UnitType::Protoss_Probe, 1), (UnitType::Protoss_Robotics_Facility, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, true, "Protoss_Observatory"),
+  d(150, 0, 4, 3, 97, 73, 500, 500, 1, 0.0, 320, 0, 0, 0, 900, (UnitType::Protoss_Probe, 1), &[(UnitType::Protoss_Nexus, 1), (UnitType::Protoss_Probe, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, true, "Protoss_Gateway"),
+  d(1, 1, 3, 3, 96, 96, 0, 1, 1, 0.0, 320, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, true, "Unused_Protoss2"),
+  d(150, 0, 2, 2, 41, 33, 100, 100, 0, 0.0, 352, 0, 0, 0, 750, (UnitType::Protoss_Probe, 1), &[(UnitType::Protoss_Forge, 1), (UnitType::Protoss_Probe, 1)], true, false, false, true, false, true, false, false, false, false, false, false, false, false, true, "Protoss_Photon_Cannon"),
+  d(150, 100, 3, 2, 65, 49, 450, 450, 1, 0.0, 320, 0, 0, 0, 900, (UnitType::Protoss_Probe, 1), &[(UnitType::Protoss_Cybernetics_Core, 1), (UnitType::Protoss_Probe, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, true, "Protoss_Citadel_of_Adun"),
+  d(200, 0, 3, 2, 81, 49, 500, 500, 1, 0.0, 320, 0, 0, 0, 900, (UnitType::Protoss_Probe, 1), &[(UnitType::Protoss_Gateway, 1), (UnitType::Protoss_Probe, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, true, "Protoss_Cybernetics_Core"),
+  d(150, 200, 3, 2, 65, 49, 500, 500, 1, 0.0, 320, 0, 0, 0, 900, (UnitType::Protoss_Probe, 1), &[(UnitType::Protoss_Citadel_of_Adun, 1), (UnitType::Protoss_Probe, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, true, "Protoss_Templar_Archives"),
+  d(150, 0, 3, 2, 73, 45, 550, 550, 1, 0.0, 320, 0, 0, 0, 600, (UnitType::Protoss_Probe, 1), &[(UnitType::Protoss_Nexus, 1), (UnitType::Protoss_Probe, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, true, "Protoss_Forge"),
+  d(150, 150, 4, 3, 97, 73, 600, 600, 1, 0.0, 320, 0, 0, 0, 1050, (UnitType::Protoss_Probe, 1), &[(UnitType::Protoss_Cybernetics_Core, 1), (UnitType::Protoss_Probe, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, true, "Protoss_Stargate"),
+  d(150, 0, 4, 3, 128, 96, 2000, 0, 1, 0.0, 256, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Special_Stasis_Cell_Prison"),
+  d(300, 200, 3, 2, 88, 57, 500, 500, 1, 0.0, 320, 0, 0, 0, 900, (UnitType::Protoss_Probe, 1), &[(UnitType::Protoss_Stargate, 1), (UnitType::Protoss_Probe, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, true, "Protoss_Fleet_Beacon"),
+  d(200, 150, 3, 2, 89, 57, 500, 500, 1, 0.0, 320, 0, 0, 0, 900, (UnitType::Protoss_Probe, 1), &[(UnitType::Protoss_Stargate, 1), (UnitType::Protoss_Probe, 1), (UnitType::Protoss_Templar_Archives, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, true, "Protoss_Arbiter_Tribunal"),
+  d(150, 100, 3, 2, 65, 53, 450, 450, 1, 0.0, 320, 0, 0, 0, 450, (UnitType::Protoss_Probe, 1), &[(UnitType::Protoss_Probe, 1), (UnitType::Protoss_Robotics_Facility, 1)], false, false, false, true, false, false, false, false, false, false, false, false, false, false, true, "Protoss_Robotics_Support_Bay"),
+  d(100, 0, 3, 2, 65, 33, 200, 200, 1, 0.0, 320, 200, 0, 0, 450, (UnitType::Protoss_Probe, 1), &[(UnitType::Protoss_Gateway, 1), (UnitType::Protoss_Probe, 1)], false, false, false, true, false, false, true, false, false, false, false, false, false, false, true, "Protoss_Shield_Battery"),
+  d(250, 0, 4, 3, 128, 96, 100000, 0, 1, 0.0, 320, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Special_Khaydarin_Crystal_Form"),
+  d(250, 0, 7, 3, 224, 96, 1500, 0, 1, 0.0, 320, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Special_Protoss_Temple"),
+  d(1500, 500, 5, 4, 160, 98, 5000, 0, 1, 0.0, 320, 0, 0, 0, 4800, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Special_XelNaga_Temple"),
+  d(1, 1, 2, 1, 64, 32, 100000, 0, 0, 0.0, 288, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, true, false, false, false, false, "Resource_Mineral_Field"),
+  d(1, 1, 2, 1, 64, 32, 100000, 0, 0, 0.0, 288, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, true, false, false, false, false, "Resource_Mineral_Field_Type_2"),
+  d(1, 1, 2, 1, 64, 32, 100000, 0, 0, 0.0, 288, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, true, false, false, false, false, "Resource_Mineral_Field_Type_3"),
+  d(1, 1, 2, 2, 64, 64, 800, 0, 0, 0.0, 288, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Unused_Cave"),
+  d(1, 1, 2, 2, 64, 64, 800, 0, 0, 0.0, 288, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Unused_Cave_In"),
+  d(1, 1, 2, 2, 64, 64, 800, 0, 0, 0.0, 288, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Unused_Cantina"),
+  d(1, 1, 1, 1, 32, 32, 800, 0, 0, 0.0, 288, 0, 0, 0, 1, (UnitType::None, 0), &[], true, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Unused_Mining_Platform"),
+  d(1, 1, 1, 1, 32, 32, 800, 0, 0, 0.0, 288, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Unused_Independant_Command_Center"),
+  d(1, 1, 2, 2, 64, 64, 800, 0, 0, 0.0, 288, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Special_Independant_Starport"),
+  d(1, 1, 1, 1, 32, 32, 800, 0, 0, 0.0, 288, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Unused_Independant_Jump_Gate"),
+  d(1, 1, 1, 1, 32, 32, 800, 0, 0, 0.0, 288, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Unused_Ruins"),
+  d(1, 1, 1, 1, 32, 32, 800, 0, 0, 0.0, 288, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Unused_Khaydarin_Crystal_Formation"),
+  d(1, 1, 4, 2, 128, 64, 100000, 0, 0, 0.0, 288, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Resource_Vespene_Geyser"),
+  d(600, 200, 3, 2, 96, 64, 700, 0, 1, 0.0, 256, 0, 0, 0, 2400, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Special_Warp_Gate"),
+  d(1000, 400, 5, 3, 150, 86, 2000, 0, 1, 0.0, 320, 0, 0, 0, 4800, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Special_Psi_Disrupter"),
+  d(250, 0, 3, 2, 96, 64, 100000, 0, 0, 0.0, 256, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Unused_Zerg_Marker"),
+  d(50, 50, 3, 2, 96, 64, 100000, 0, 0, 0.0, 256, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Unused_Terran_Marker"),
+  d(100, 100, 3, 2, 96, 64, 100000, 0, 0, 0.0, 256, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Unused_Protoss_Marker"),
+  d(250, 0, 3, 2, 96, 64, 100000, 0, 0, 0.0, 256, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Special_Zerg_Beacon"),
+  d(50, 50, 3, 2, 96, 64, 100000, 0, 0, 0.0, 256, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Special_Terran_Beacon"),
+  d(100, 100, 3, 2, 96, 64, 100000, 0, 0, 0.0, 256, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Special_Protoss_Beacon"),
+  d(250, 0, 3, 2, 96, 64, 100000, 0, 0, 0.0, 256, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Special_Zerg_Flag_Beacon"),
+  d(50, 50, 3, 2, 96, 64, 100000, 0, 0, 0.0, 256, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Special_Terran_Flag_Beacon"),
+  d(100, 100, 3, 2, 96, 64, 100000, 0, 0, 0.0, 256, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Special_Protoss_Flag_Beacon"),
+  d(200, 50, 4, 3, 120, 72, 800, 0, 1, 0.0, 256, 0, 0, 0, 2400, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, false, false, false, "Special_Power_Generator"),
+  d(1000, 500, 3, 2, 96, 64, 2500, 0, 1, 0.0, 320, 0, 0, 0, 2400, (UnitType::None, 0), &[], false, false, false, true, false, true, false, false, false, false, false, false, false, false, false, "Special_Overmind_Cocoon"),
+  d(250, 200, 5, 5, 160, 160, 800, 0, 0, 0.0, 256, 0, 0, 0, 2400, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Spell_Dark_Swarm"),
+  d(1, 1, 2, 2, 64, 64, 50, 0, 0, 0.0, 192, 0, 0, 0, 1, (UnitType::None, 0), &[], true, false, false, false, false, true, false, false, false, false, false, false, false, false, false, "Special_Floor_Missile_Trap"),
+  d(1, 1, 8, 4, 256, 128, 100000, 0, 0, 0.0, 224, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Special_Floor_Hatch"),
+  d(1, 1, 3, 2, 70, 38, 100000, 0, 0, 0.0, 32, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Special_Upper_Level_Door"),
+  d(1, 1, 3, 2, 70, 38, 100000, 0, 0, 0.0, 32, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Special_Right_Upper_Level_Door"),
+  d(1, 1, 3, 2, 70, 38, 100000, 0, 0, 0.0, 32, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Special_Pit_Door"),
+  d(1, 1, 3, 2, 70, 38, 100000, 0, 0, 0.0, 32, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Special_Right_Pit_Door"),
+  d(1, 1, 2, 2, 64, 64, 50, 0, 0, 0.0, 192, 0, 0, 0, 1, (UnitType::None, 0), &[], true, false, false, false, false, true, false, false, false, false, false, false, false, false, false, "Special_Floor_Gun_Trap"),
+  d(1, 1, 1, 1, 32, 32, 50, 0, 0, 0.0, 192, 0, 0, 0, 1, (UnitType::None, 0), &[], true, false, false, false, false, true, false, false, false, false, false, false, false, false, false, "Special_Wall_Missile_Trap"),
+  d(1, 1, 1, 1, 32, 32, 50, 0, 0, 0.0, 96, 0, 0, 0, 1, (UnitType::None, 0), &[], true, false, false, false, false, true, false, false, false, false, false, false, false, false, false, "Special_Wall_Flame_Trap"),
+  d(1, 1, 1, 1, 32, 32, 50, 0, 0, 0.0, 192, 0, 0, 0, 1, (UnitType::None, 0), &[], true, false, false, false, false, true, false, false, false, false, false, false, false, false, false, "Special_Right_Wall_Missile_Trap"),
+  d(1, 1, 1, 1, 32, 32, 50, 0, 0, 0.0, 96, 0, 0, 0, 1, (UnitType::None, 0), &[], true, false, false, false, false, true, false, false, false, false, false, false, false, false, false, "Special_Right_Wall_Flame_Trap"),
+  d(0, 0, 4, 3, 97, 65, 800, 0, 0, 0.0, 32, 0, 0, 0, 0, (UnitType::None, 0), &[], false, false, false, true, false, false, false, false, false, false, false, false, true, false, false, "Special_Start_Location"),
+  d(1, 1, 1, 1, 32, 32, 100000, 0, 0, 0.0, 160, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Powerup_Flag"),
+  d(1, 1, 1, 1, 32, 32, 800, 0, 0, 0.0, 160, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Powerup_Young_Chrysalis"),
+  d(1, 1, 1, 1, 32, 32, 800, 0, 0, 0.0, 160, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Powerup_Psi_Emitter"),
+  d(1, 1, 1, 1, 32, 32, 800, 0, 0, 0.0, 160, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Powerup_Data_Disk"),
+  d(1, 1, 1, 1, 32, 32, 800, 0, 0, 0.0, 160, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Powerup_Khaydarin_Crystal"),
+  d(1, 1, 1, 1, 32, 32, 800, 0, 0, 0.0, 160, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Powerup_Mineral_Cluster_Type_1"),
+  d(1, 1, 1, 1, 32, 32, 800, 0, 0, 0.0, 160, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Powerup_Mineral_Cluster_Type_2"),
+  d(1, 1, 1, 1, 32, 32, 800, 0, 0, 0.0, 160, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Powerup_Protoss_Gas_Orb_Type_1"),
+  d(1, 1, 1, 1, 32, 32, 800, 0, 0, 0.0, 160, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Powerup_Protoss_Gas_Orb_Type_2"),
+  d(1, 1, 1, 1, 32, 32, 800, 0, 0, 0.0, 160, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Powerup_Zerg_Gas_Sac_Type_1"),
+  d(1, 1, 1, 1, 32, 32, 800, 0, 0, 0.0, 160, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Powerup_Zerg_Gas_Sac_Type_2"),
+  d(1, 1, 1, 1, 32, 32, 800, 0, 0, 0.0, 160, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Powerup_Terran_Gas_Tank_Type_1"),
+  d(1, 1, 1, 1, 32, 32, 800, 0, 0, 0.0, 160, 0, 0, 0, 1, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Powerup_Terran_Gas_Tank_Type_2"),
+  d(0, 0, 0, 0, 1, 1, 0, 0, 0, 0.0, 0, 0, 0, 0, 0, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "None"),
+  d(0, 0, 0, 0, 1, 1, 0, 0, 0, 0.0, 0, 0, 0, 0, 0, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "AllUnits"),
+  d(0, 0, 0, 0, 1, 1, 0, 0, 0, 0.0, 0, 0, 0, 0, 0, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Men"),
+  d(0, 0, 0, 0, 1, 1, 0, 0, 0, 0.0, 0, 0, 0, 0, 0, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Buildings"),
+  d(0, 0, 0, 0, 1, 1, 0, 0, 0, 0.0, 0, 0, 0, 0, 0, (UnitType::None, 0), &[], false, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Factories"),
+  d(0, 0, 0, 0, 1, 1, 0, 0, 0, 0.0, 0, 0, 0, 0, 0, (UnitType::Unknown, 1), &[(UnitType::Unknown, 1)], true, false, false, false, false, false, false, false, false, false, false, false, false, false, false, "Unknown"),
+];
+
+impl UnitType {
+  fn d(&self) -> &UnitTypeData {
+    &UNIT_TYPE_DATA[*self as usize]
+  }
+
+  pub fn mineral_price(&self) -> i32 {
+    self.d().mineral_price
+  }
+
+  pub fn gas_price(&self) -> i32 {
+    self.d().gas_price
+  }
+
+  pub fn tile_width(&self) -> i32 {
+    self.d().tile_width
+  }
+
+  pub fn tile_height(&self) -> i32 {
+    self.d().tile_height
+  }
+
+  pub fn width(&self) -> i32 {
+    self.d().width
+  }
+
+  pub fn height(&self) -> i32 {
+    self.d().height
+  }
+
+  pub fn max_hit_points(&self) -> i32 {
+    self.d().max_hit_points
+  }
+
+  pub fn max_shields(&self) -> i32 {
+    self.d().max_shields
+  }
+
+  pub fn armor(&self) -> i32 {
+    self.d().armor
+  }
+
+  pub fn top_speed(&self) -> f64 {
+    self.d().top_speed
+  }
+
+  pub fn sight_range(&self) -> i32 {
+    self.d().sight_range
+  }
+
+  pub fn max_energy(&self) -> i32 {
+    self.d().max_energy
+  }
+
+  pub fn supply_required(&self) -> i32 {
+    self.d().supply_required
+  }
+
+  pub fn supply_provided(&self) -> i32 {
+    self.d().supply_provided
+  }
+
+  pub fn build_time(&self) -> i32 {
+    self.d().build_time
+  }
+
+  pub fn what_builds(&self) -> (UnitType, i32) {
+    self.d().what_builds
+  }
+
+  pub fn required_units(&self) -> &'static [(UnitType, i32)] {
+    self.d().required_units
+  }
+
+  pub fn can_attack(&self) -> bool {
+    self.d().can_attack
+  }
+
+  pub fn can_move(&self) -> bool {
+    self.d().can_move
+  }
+
+  pub fn is_flyer(&self) -> bool {
+    self.d().is_flyer
+  }
+
+  pub fn is_building(&self) -> bool {
+    self.d().is_building
+  }
+
+  pub fn is_addon(&self) -> bool {
+    self.d().is_addon
+  }
+
+  pub fn is_detector(&self) -> bool {
+    self.d().is_detector
+  }
+
+  pub fn is_spellcaster(&self) -> bool {
+    self.d().is_spellcaster
+  }
+
+  pub fn has_permanent_cloak(&self) -> bool {
+    self.d().has_permanent_cloak
+  }
+
+  pub fn is_cloakable(&self) -> bool {
+    self.d().is_cloakable
+  }
+
+  pub fn is_burrowable(&self) -> bool {
+    self.d().is_burrowable
+  }
+
+  pub fn is_mineral_field(&self) -> bool {
+    self.d().is_mineral_field
+  }
+
+  pub fn is_refinery(&self) -> bool {
+    self.d().is_refinery
+  }
+
+  pub fn is_resource_depot(&self) -> bool {
+    self.d().is_resource_depot
+  }
+
+  pub fn is_worker(&self) -> bool {
+    self.d().is_worker
+  }
+
+  pub fn requires_psi(&self) -> bool {
+    self.d().requires_psi
+  }
+
+  pub fn name(&self) -> &'static str {
+    self.d().name
+  }
+
+  pub fn tile_size(&self) -> TilePosition {
+    TilePosition::new(self.tile_width(), self.tile_height())
+  }
+}
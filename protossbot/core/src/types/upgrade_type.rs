@@ -0,0 +1,60 @@
+//! Generated from the BWAPI headers shipped with bwapi_wrapper 3.6.3; keep in step with rsbwapi.
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum UpgradeType {
+  Terran_Infantry_Armor = 0,
+  Terran_Vehicle_Plating = 1,
+  Terran_Ship_Plating = 2,
+  Zerg_Carapace = 3,
+  Zerg_Flyer_Carapace = 4,
+  Protoss_Ground_Armor = 5,
+  Protoss_Air_Armor = 6,
+  Terran_Infantry_Weapons = 7,
+  Terran_Vehicle_Weapons = 8,
+  Terran_Ship_Weapons = 9,
+  Zerg_Melee_Attacks = 10,
+  Zerg_Missile_Attacks = 11,
+  Zerg_Flyer_Attacks = 12,
+  Protoss_Ground_Weapons = 13,
+  Protoss_Air_Weapons = 14,
+  Protoss_Plasma_Shields = 15,
+  U_238_Shells = 16,
+  Ion_Thrusters = 17,
+  Titan_Reactor = 19,
+  Ocular_Implants = 20,
+  Moebius_Reactor = 21,
+  Apollo_Reactor = 22,
+  Colossus_Reactor = 23,
+  Ventral_Sacs = 24,
+  Antennae = 25,
+  Pneumatized_Carapace = 26,
+  Metabolic_Boost = 27,
+  Adrenal_Glands = 28,
+  Muscular_Augments = 29,
+  Grooved_Spines = 30,
+  Gamete_Meiosis = 31,
+  Metasynaptic_Node = 32,
+  Singularity_Charge = 33,
+  Leg_Enhancements = 34,
+  Scarab_Damage = 35,
+  Reaver_Capacity = 36,
+  Gravitic_Drive = 37,
+  Sensor_Array = 38,
+  Gravitic_Boosters = 39,
+  Khaydarin_Amulet = 40,
+  Apial_Sensors = 41,
+  Gravitic_Thrusters = 42,
+  Carrier_Capacity = 43,
+  Khaydarin_Core = 44,
+  Argus_Jewel = 47,
+  Argus_Talisman = 49,
+  Caduceus_Reactor = 51,
+  Chitinous_Plating = 52,
+  Anabolic_Synthesis = 53,
+  Charon_Boosters = 54,
+  Upgrade_60 = 60,
+  None = 61,
+  Unknown,
+  MAX,
+}
@@ -0,0 +1,654 @@
+use rsbwapi::{Game, Player, Unit, UnitSizeType, WeaponType};
+
+use crate::{
+  log_info, log_warn,
+  logging::LogTarget,
+  state::{
+    enemy_memory::EnemyUnitInfo,
+    game_state::{GameState, Squad, SquadMode},
+  },
+  types::{Order, Position, TilePosition, UnitType},
+  utils::{
+    combat_sim::{self, DamageType, SimUnit, UnitSize, Weapon},
+    command_recorder::CommandRecord,
+    micro,
+    pathfinding::FlowField,
+    strategy_recognition::EnemyOpening,
+  },
+};
+
+const COMMAND_INTERVAL_FRAMES: i32 = 12;
+const RALLY_RANGE: i32 = 4 * 32;
+const TARGET_REACHED_RANGE: i32 = 6 * 32;
+const RALLY_INSET_TILES: i32 = 3;
+const STRATEGY_TRIGGER_CONFIDENCE: f32 = 0.6;
+/// How far ahead fights are simulated, about ten seconds
+const SIM_FRAMES: i32 = 24 * 10;
+/// Enemies within this range of the squad take part in the simulated fight
+const ENGAGEMENT_RANGE: i32 = 14 * 32;
+/// Mobile enemies not seen for this long have most likely moved on
+const STALE_ENEMY_FRAMES: i32 = 24 * 60;
+/// After retreating, wait this long before launching another attack
+const RETREAT_COOLDOWN_FRAMES: i32 = 24 * 20;
+
+pub fn on_frame(game: &Game, player: &Player, state: &mut GameState) {
+  let combat_units: Vec<Unit> = player
+    .get_units()
+    .into_iter()
+    .filter(|u| is_combat_unit(u))
+    .collect();
+
+  update_squads(&combat_units, state);
+  update_rally_position(player, state);
+  check_attack_trigger(game, player, &combat_units, state);
+  run_micro(game, player, &combat_units, state);
+
+  if game.get_frame_count() % COMMAND_INTERVAL_FRAMES != 0 {
+    return;
+  }
+
+  for squad_index in 0..state.army.squads.len() {
+    let squad = state.army.squads[squad_index].clone();
+    let members: Vec<&Unit> = combat_units
+      .iter()
+      .filter(|u| squad.unit_ids.contains(&u.get_id()))
+      .collect();
+    let free_members: Vec<&Unit> = members
+      .iter()
+      .copied()
+      .filter(|u| !state.army.micro_unit_ids.contains(&u.get_id()))
+      .collect();
+
+    match squad.mode {
+      SquadMode::Rally => command_rally(&free_members, state),
+      SquadMode::Attack => {
+        if should_retreat(game, player, &members, state) {
+          log_info!(
+            LogTarget::Army,
+            "Squad {} retreating from a losing fight",
+            squad.id
+          );
+          state.army.last_retreat_frame = game.get_frame_count();
+          let squad = &mut state.army.squads[squad_index];
+          squad.mode = SquadMode::Rally;
+          squad.target = None;
+          continue;
+        }
+        command_attack(&free_members, squad_index, state)
+      }
+      // Defending squads are driven by the defense manager
+      SquadMode::Defend => {}
+    }
+  }
+}
+
+/// Lets per-unit micro take over units that are fighting; squad orders skip those.
+fn run_micro(game: &Game, player: &Player, combat_units: &[Unit], state: &mut GameState) {
+  let enemies: Vec<Unit> = game
+    .get_all_units()
+    .into_iter()
+    .filter(|u| u.get_player().is_enemy(player))
+    .filter(|u| !u.get_type().is_building() || u.get_type().can_attack())
+    .collect();
+  let home = state.army.rally_position.or_else(|| {
+    state
+      .expansion
+      .main_base_id
+      .map(|id| state.terrain.base_locations[id].center())
+  });
+
+  state.army.micro_unit_ids.clear();
+  for unit in combat_units {
+    if micro::control_unit(game, unit, &enemies, home, state) {
+      state.army.micro_unit_ids.insert(unit.get_id());
+    }
+  }
+
+  let alive: Vec<usize> = combat_units.iter().map(|u| u.get_id()).collect();
+  state.army.recharging_ids.retain(|id| alive.contains(id));
+}
+
+pub fn is_combat_unit(unit: &Unit) -> bool {
+  let unit_type = unit.get_type();
+  unit.is_completed()
+    && !unit_type.is_worker()
+    && !unit_type.is_building()
+    && unit_type.can_attack()
+    // Arbiters cloak the army rather than fight with it
+    && unit_type != UnitType::Protoss_Arbiter
+}
+
+pub fn squad_center(units: &[&Unit]) -> Option<Position> {
+  if units.is_empty() {
+    return None;
+  }
+  let (sum_x, sum_y) = units.iter().fold((0, 0), |(x, y), u| {
+    let pos = u.get_position();
+    (x + pos.x, y + pos.y)
+  });
+  Some(Position {
+    x: sum_x / units.len() as i32,
+    y: sum_y / units.len() as i32,
+  })
+}
+
+fn update_squads(combat_units: &[Unit], state: &mut GameState) {
+  let alive: Vec<usize> = combat_units.iter().map(|u| u.get_id()).collect();
+
+  for squad in state.army.squads.iter_mut() {
+    squad.unit_ids.retain(|id| alive.contains(id));
+  }
+  state
+    .army
+    .squads
+    .retain(|squad| !squad.unit_ids.is_empty() || squad.mode == SquadMode::Rally);
+
+  // Squads coming back from an attack join whoever is already waiting at the rally
+  let rally_indices: Vec<usize> = (0..state.army.squads.len())
+    .filter(|&i| state.army.squads[i].mode == SquadMode::Rally)
+    .collect();
+  if let Some((&first, rest)) = rally_indices.split_first() {
+    for &index in rest.iter().rev() {
+      let merged = state.army.squads.remove(index);
+      state.army.squads[first].unit_ids.extend(merged.unit_ids);
+    }
+  }
+
+  let unassigned: Vec<usize> = alive
+    .into_iter()
+    .filter(|id| !state.army.squads.iter().any(|s| s.unit_ids.contains(id)))
+    .collect();
+  if unassigned.is_empty() {
+    return;
+  }
+
+  let rally_squad = rally_squad_index(state);
+  state.army.squads[rally_squad].unit_ids.extend(unassigned);
+}
+
+fn rally_squad_index(state: &mut GameState) -> usize {
+  if let Some(index) = state
+    .army
+    .squads
+    .iter()
+    .position(|s| s.mode == SquadMode::Rally)
+  {
+    return index;
+  }
+
+  let id = state.army.next_squad_id;
+  state.army.next_squad_id += 1;
+  state.army.squads.push(Squad {
+    id,
+    unit_ids: Vec::new(),
+    mode: SquadMode::Rally,
+    target: None,
+  });
+  state.army.squads.len() - 1
+}
+
+/// Rallies at the choke of our outermost base region that faces the enemy.
+fn update_rally_position(player: &Player, state: &mut GameState) {
+  if state.expansion.main_base_id.is_none() {
+    return;
+  }
+  let terrain = &state.terrain;
+
+  let nexus_tiles: Vec<TilePosition> = player
+    .get_units()
+    .iter()
+    .filter(|u| u.get_type() == UnitType::Protoss_Nexus && u.is_completed())
+    .map(|u| u.get_tile_position())
+    .collect();
+  let Some(home_base) = terrain
+    .base_locations
+    .iter()
+    .filter(|b| {
+      nexus_tiles
+        .iter()
+        .any(|t| (t.x - b.tile.x).abs() <= 4 && (t.y - b.tile.y).abs() <= 4)
+    })
+    .max_by_key(|b| {
+      state
+        .expansion
+        .ground_distance_from_main
+        .get(b.id)
+        .copied()
+        .flatten()
+        .unwrap_or(0)
+    })
+  else {
+    return;
+  };
+  let Some(home_region) = home_base.region_id else {
+    return;
+  };
+  let enemy_goal = state
+    .enemy_starts
+    .most_likely_enemy_main
+    .unwrap_or(TilePosition {
+      x: terrain.grid.width / 2,
+      y: terrain.grid.height / 2,
+    });
+  if state.army.rally_region == Some(home_region) && state.army.rally_facing == Some(enemy_goal) {
+    return;
+  }
+  let field = FlowField::towards(&terrain.grid, &[enemy_goal]);
+
+  let rally_position = match terrain
+    .chokepoints_of_region(home_region)
+    .into_iter()
+    .min_by_key(|c| field.distance_at(c.center).unwrap_or(i32::MAX))
+  {
+    Some(choke) => {
+      // Stand a few tiles inside our region instead of in the choke itself
+      let region_center = terrain.regions[home_region].center;
+      let rally_tile = step_towards(choke.center, region_center, RALLY_INSET_TILES);
+      log_info!(
+        LogTarget::Army,
+        "Army rallying at {:?} near choke {}",
+        rally_tile,
+        choke.id
+      );
+      rally_tile
+    }
+    None => {
+      // An island or a region the analysis didn't close off: wait in front of the depot,
+      // and remember it so the flow field isn't rebuilt every frame
+      let rally_tile = step_towards(home_base.tile, enemy_goal, RALLY_INSET_TILES);
+      log_info!(
+        LogTarget::Army,
+        "No choke out of region {}, army rallying at {:?} by the depot",
+        home_region,
+        rally_tile
+      );
+      rally_tile
+    }
+  };
+
+  state.army.rally_region = Some(home_region);
+  state.army.rally_facing = Some(enemy_goal);
+  state.army.rally_position = Some(Position {
+    x: rally_position.x * 32 + 16,
+    y: rally_position.y * 32 + 16,
+  });
+}
+
+/// The tile `tiles` tiles from `from` in the direction of `to`
+fn step_towards(from: TilePosition, to: TilePosition, tiles: i32) -> TilePosition {
+  let dx = to.x - from.x;
+  let dy = to.y - from.y;
+  let length = ((dx * dx + dy * dy) as f32).sqrt().max(1.0);
+  TilePosition {
+    x: from.x + (dx as f32 / length * tiles as f32) as i32,
+    y: from.y + (dy as f32 / length * tiles as f32) as i32,
+  }
+}
+
+fn check_attack_trigger(
+  game: &Game,
+  player: &Player,
+  combat_units: &[Unit],
+  state: &mut GameState,
+) {
+  if game.get_frame_count() - state.army.last_retreat_frame < RETREAT_COOLDOWN_FRAMES {
+    return;
+  }
+
+  let Some(rally_index) = state
+    .army
+    .squads
+    .iter()
+    .position(|s| s.mode == SquadMode::Rally)
+  else {
+    return;
+  };
+
+  let squad_supply: i32 = combat_units
+    .iter()
+    .filter(|u| {
+      state.army.squads[rally_index]
+        .unit_ids
+        .contains(&u.get_id())
+    })
+    .map(|u| u.get_type().supply_required())
+    .sum::<i32>()
+    / 2;
+
+  // Punish greedy openings with a smaller army
+  let greedy_enemy = state.enemy_strategy.opening == EnemyOpening::FastExpand
+    && state.enemy_strategy.confidence >= STRATEGY_TRIGGER_CONFIDENCE;
+  let required_supply = if greedy_enemy {
+    state.army.attack_supply / 2
+  } else {
+    state.army.attack_supply
+  };
+
+  if squad_supply < required_supply || squad_supply == 0 {
+    return;
+  }
+
+  let Some(target) = pick_attack_target(state) else {
+    return;
+  };
+
+  // Only go when the squad can beat what will defend the target
+  let members: Vec<&Unit> = combat_units
+    .iter()
+    .filter(|u| {
+      state.army.squads[rally_index]
+        .unit_ids
+        .contains(&u.get_id())
+    })
+    .collect();
+  let ours = our_sim_units(player, &members);
+  let frame = game.get_frame_count();
+  let enemies = enemy_sim_units(game, state, |info| defends_target(info, target, frame));
+  if !combat_sim::simulate_head_on(&ours, &enemies, SIM_FRAMES).is_favorable() {
+    return;
+  }
+
+  log_info!(
+    LogTarget::Army,
+    "Launching attack with {} supply towards {:?}",
+    squad_supply,
+    target
+  );
+  let squad = &mut state.army.squads[rally_index];
+  squad.mode = SquadMode::Attack;
+  squad.target = Some(target);
+}
+
+fn pick_attack_target(state: &GameState) -> Option<Position> {
+  let home = state.army.rally_position.or_else(|| {
+    state
+      .expansion
+      .main_base_id
+      .map(|id| state.terrain.base_locations[id].center())
+  })?;
+
+  state
+    .enemy_memory
+    .nearest_building(home)
+    .map(|info| info.last_position)
+    .or_else(|| {
+      state
+        .enemy_starts
+        .most_likely_enemy_main
+        .map(|tile| Position {
+          x: tile.x * 32 + 64,
+          y: tile.y * 32 + 48,
+        })
+    })
+}
+
+fn command_rally(members: &[&Unit], state: &mut GameState) {
+  let Some(rally) = state.army.rally_position else {
+    return;
+  };
+
+  for unit in members {
+    if distance(unit.get_position(), rally) <= RALLY_RANGE {
+      continue;
+    }
+    if unit.get_order() == Order::AttackMove && !unit.is_idle() {
+      continue;
+    }
+    let result = unit.attack(rally);
+    state.commands.record(
+      CommandRecord::new(
+        unit.get_id(),
+        "AttackMove",
+        "army_manager",
+        "gathering at rally",
+      )
+      .with_target(rally)
+      .with_result(&result),
+    );
+    if let Err(e) = result {
+      log_warn!(
+        LogTarget::Army,
+        "Rally command FAILED for unit {}: {:?}",
+        unit.get_id(),
+        e
+      );
+    }
+  }
+}
+
+fn command_attack(members: &[&Unit], squad_index: usize, state: &mut GameState) {
+  let Some(target) = state.army.squads[squad_index].target else {
+    return;
+  };
+
+  // Once the army stands on its target with nothing left there, move to the next one
+  let center = squad_center(members);
+  let target_cleared = center.is_some_and(|c| distance(c, target) <= TARGET_REACHED_RANGE)
+    && state
+      .enemy_memory
+      .buildings()
+      .all(|info| distance(info.last_position, target) > TARGET_REACHED_RANGE);
+  if target_cleared {
+    match pick_attack_target(state) {
+      Some(next) if next != target => {
+        log_info!(
+          LogTarget::Army,
+          "Attack target {:?} cleared, moving on to {:?}",
+          target,
+          next
+        );
+        state.army.squads[squad_index].target = Some(next);
+      }
+      _ => {
+        log_info!(
+          LogTarget::Army,
+          "No known enemy buildings left, army returning to rally"
+        );
+        let squad = &mut state.army.squads[squad_index];
+        squad.mode = SquadMode::Rally;
+        squad.target = None;
+      }
+    }
+    return;
+  }
+
+  for unit in members {
+    if unit.get_order() == Order::AttackMove && !unit.is_idle() {
+      continue;
+    }
+    if unit.is_attacking() {
+      continue;
+    }
+    let result = unit.attack(target);
+    state.commands.record(
+      CommandRecord::new(
+        unit.get_id(),
+        "AttackMove",
+        "army_manager",
+        "squad attacking",
+      )
+      .with_target(target)
+      .with_result(&result),
+    );
+    if let Err(e) = result {
+      log_warn!(
+        LogTarget::Army,
+        "Attack command FAILED for unit {}: {:?}",
+        unit.get_id(),
+        e
+      );
+    }
+  }
+}
+
+pub fn draw_debug(game: &Game, state: &GameState) {
+  if let Some(rally) = state.army.rally_position {
+    game.draw_text_map(rally, "Army rally");
+  }
+  for squad in &state.army.squads {
+    if let Some(target) = squad.target {
+      game.draw_text_map(
+        target,
+        &format!(
+          "Squad {} {:?} ({} units)",
+          squad.id,
+          squad.mode,
+          squad.unit_ids.len()
+        ),
+      );
+    }
+  }
+}
+
+fn should_retreat(game: &Game, player: &Player, members: &[&Unit], state: &GameState) -> bool {
+  let Some(center) = squad_center(members) else {
+    return false;
+  };
+  let enemies = enemy_sim_units(game, state, |info| {
+    distance(info.last_position, center) <= ENGAGEMENT_RANGE
+  });
+  if enemies.is_empty() {
+    return false;
+  }
+
+  let ours = our_sim_units(player, members);
+  !combat_sim::simulate(&ours, &enemies, SIM_FRAMES).is_favorable()
+}
+
+fn our_sim_units(player: &Player, members: &[&Unit]) -> Vec<SimUnit> {
+  members
+    .iter()
+    .map(|u| {
+      to_sim_unit(
+        u.get_type(),
+        u.get_position(),
+        u.get_hit_points(),
+        u.get_shields(),
+        Some(player),
+      )
+    })
+    .collect()
+}
+
+/// Remembered enemy units that can fight and pass `keep`.
+fn enemy_sim_units(
+  game: &Game,
+  state: &GameState,
+  keep: impl Fn(&EnemyUnitInfo) -> bool,
+) -> Vec<SimUnit> {
+  state
+    .enemy_memory
+    .alive()
+    .filter(|info| info.position_known && is_fighting_enemy(info))
+    .filter(|info| keep(info))
+    .map(|info| {
+      to_sim_unit(
+        info.unit_type,
+        info.last_position,
+        info.hit_points,
+        info.shields,
+        game.get_player(info.owner_id).as_ref(),
+      )
+    })
+    .collect()
+}
+
+/// Defenses around the target, and mobile units seen lately wherever they were since
+/// they can come to its aid
+fn defends_target(info: &EnemyUnitInfo, target: Position, frame: i32) -> bool {
+  if info.unit_type.is_building() {
+    return distance(info.last_position, target) <= ENGAGEMENT_RANGE;
+  }
+  frame - info.last_seen_frame <= STALE_ENEMY_FRAMES
+}
+
+fn is_fighting_enemy(info: &EnemyUnitInfo) -> bool {
+  let unit_type = info.unit_type;
+  if unit_type.is_building() {
+    return info.is_completed && unit_type.can_attack();
+  }
+  !unit_type.is_worker() && (unit_type.can_attack() || sim_weapon_type(unit_type).is_some())
+}
+
+/// Builds a simulator unit from BWAPI type data, with upgrades read from the owner.
+pub fn to_sim_unit(
+  unit_type: UnitType,
+  position: Position,
+  hit_points: i32,
+  shields: i32,
+  owner: Option<&Player>,
+) -> SimUnit {
+  let upgrade_level = |upgrade| owner.map_or(0, |p| p.get_upgrade_level(upgrade));
+  let ground = sim_weapon_type(unit_type).or_else(|| non_empty(unit_type.ground_weapon()));
+  let air = non_empty(unit_type.air_weapon());
+  let attack_upgrades = ground
+    .or(air)
+    .map_or(0, |w| upgrade_level(w.upgrade_type()));
+
+  SimUnit {
+    x: position.x as f32,
+    y: position.y as f32,
+    radius: (unit_type.width().max(unit_type.height()) / 2) as f32,
+    speed: unit_type.top_speed() as f32,
+    hit_points: hit_points as f32,
+    max_hit_points: unit_type.max_hit_points() as f32,
+    shields: shields as f32,
+    max_shields: unit_type.max_shields() as f32,
+    armor: unit_type.armor(),
+    size: match unit_type.size() {
+      UnitSizeType::Small => UnitSize::Small,
+      UnitSizeType::Medium => UnitSize::Medium,
+      _ => UnitSize::Large,
+    },
+    is_flyer: unit_type.is_flyer(),
+    ground_weapon: ground.map(|w| to_sim_weapon(unit_type, w)),
+    air_weapon: air.map(|w| to_sim_weapon(unit_type, w)),
+    attack_upgrades,
+    armor_upgrades: upgrade_level(unit_type.armor_upgrade()),
+    shield_upgrades: if unit_type.max_shields() > 0 {
+      upgrade_level(rsbwapi::UpgradeType::Protoss_Plasma_Shields)
+    } else {
+      0
+    },
+    value: (unit_type.mineral_price() + unit_type.gas_price()) as f32,
+    cooldown_remaining: 0,
+  }
+}
+
+/// Units whose damage comes from spawned projectiles rather than their own weapon
+fn sim_weapon_type(unit_type: UnitType) -> Option<WeaponType> {
+  match unit_type {
+    UnitType::Protoss_Reaver => Some(WeaponType::Scarab),
+    UnitType::Protoss_Carrier => Some(WeaponType::Pulse_Cannon),
+    _ => None,
+  }
+}
+
+fn non_empty(weapon: WeaponType) -> Option<WeaponType> {
+  (weapon != WeaponType::None).then_some(weapon)
+}
+
+fn to_sim_weapon(unit_type: UnitType, weapon: WeaponType) -> Weapon {
+  Weapon {
+    damage: weapon.damage_amount(),
+    damage_bonus: weapon.damage_bonus(),
+    // A carrier's eight interceptors all shoot at once
+    factor: if unit_type == UnitType::Protoss_Carrier {
+      weapon.damage_factor() * 8
+    } else {
+      weapon.damage_factor()
+    },
+    cooldown: weapon.damage_cooldown(),
+    min_range: weapon.min_range(),
+    max_range: weapon.max_range(),
+    damage_type: match weapon.damage_type() {
+      rsbwapi::DamageType::Concussive => DamageType::Concussive,
+      rsbwapi::DamageType::Explosive => DamageType::Explosive,
+      _ => DamageType::Normal,
+    },
+  }
+}
+
+fn distance(a: Position, b: Position) -> i32 {
+  (((a.x - b.x).pow(2) + (a.y - b.y).pow(2)) as f32).sqrt() as i32
+}
@@ -0,0 +1,56 @@
+use crate::types::{TilePosition, UnitType};
+
+use crate::world::GameWorld;
+
+pub fn get_base_anchor(world: &impl GameWorld) -> TilePosition {
+  world
+    .my_units()
+    .iter()
+    .find(|u| u.unit_type == UnitType::Protoss_Nexus && u.is_completed)
+    .map(|nexus| nexus.tile_position)
+    .unwrap_or_else(|| world.start_location())
+}
+
+pub fn find_build_location(
+  world: &impl GameWorld,
+  builder_id: usize,
+  building_type: UnitType,
+  anchor: TilePosition,
+  max_range: i32,
+) -> Option<TilePosition> {
+  let (map_width, map_height) = world.map_size();
+
+  for distance in 0..max_range {
+    for dx in -distance..=distance {
+      for dy in -distance..=distance {
+        if dx.abs() != distance && dy.abs() != distance {
+          continue;
+        }
+
+        let tile = TilePosition {
+          x: anchor.x + dx,
+          y: anchor.y + dy,
+        };
+
+        if tile.x < 0 || tile.y < 0 || tile.x >= map_width || tile.y >= map_height {
+          continue;
+        }
+
+        if is_valid_build_location(world, building_type, tile, builder_id) {
+          return Some(tile);
+        }
+      }
+    }
+  }
+
+  None
+}
+
+fn is_valid_build_location(
+  world: &impl GameWorld,
+  building_type: UnitType,
+  position: TilePosition,
+  builder_id: usize,
+) -> bool {
+  world.can_build_here(builder_id, position, building_type)
+}
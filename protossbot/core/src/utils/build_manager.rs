@@ -0,0 +1,766 @@
+use crate::{
+  log_debug, log_info, log_warn,
+  logging::LogTarget,
+  state::{
+    build_stages,
+    game_state::{BuildHistoryEntry, GameState, IntendedCommand},
+  },
+  types::{Order, Position, TilePosition, UnitType},
+  utils::{
+    build_location_utils, command_recorder::CommandRecord, expansion_manager, tech_requirements,
+  },
+  world::{GameWorld, UnitInfo},
+};
+
+pub fn on_frame(world: &impl GameWorld, state: &mut GameState) {
+  cleanup_stale_commands(world, state);
+  react_to_enemy_opening(state);
+  check_and_advance_stage(world, state);
+  state.stage_item_status = get_status_for_stage_items(world, state);
+
+  try_start_next_build(world, state);
+}
+
+pub fn on_building_create(unit_type: UnitType, state: &mut GameState) {
+  if let Some(entry) = state
+    .unit_build_history
+    .iter()
+    .rev()
+    .find(|e| e.unit_type == Some(unit_type))
+  {
+    if let Some(probe_id) = entry.assigned_unit_id {
+      // Remove the probe's intended command (PlaceBuilding order)
+      state.intended_commands.remove(&probe_id);
+      log_info!(
+        LogTarget::Build,
+        "Building {} started. Removed assignment for probe {}",
+        unit_type.name(),
+        probe_id
+      );
+    }
+  }
+}
+
+fn react_to_enemy_opening(state: &mut GameState) {
+  let opening = state.enemy_strategy.opening;
+  if state.enemy_strategy.confidence < 0.6 || state.reacted_to_opening == Some(opening) {
+    return;
+  }
+
+  state.reacted_to_opening = Some(opening);
+  let Some(stage) = build_stages::get_reaction_stage(opening) else {
+    return;
+  };
+
+  log_info!(
+    LogTarget::Build,
+    "Reacting to {} with stage '{}'",
+    opening.name(),
+    stage.name
+  );
+  let index = state.current_stage_index.min(state.build_stages.len());
+  state.build_stages.insert(index, stage);
+}
+
+fn cleanup_stale_commands(world: &impl GameWorld, state: &mut GameState) {
+  let units = world.my_units();
+  let scout_id = state.scouting.scout_id;
+  let pulled_worker_ids: Vec<usize> = state
+    .defense
+    .threats
+    .iter()
+    .flat_map(|t| t.pulled_worker_ids.iter().copied())
+    .collect();
+
+  state.intended_commands.retain(|unit_id, cmd| {
+    // Remove if unit no longer exists
+    let Some(unit) = units.iter().find(|u| u.id == *unit_id) else {
+      return false;
+    };
+
+    // The scouting manager owns its probe's commands until it releases the scout
+    if scout_id == Some(*unit_id) {
+      return true;
+    }
+
+    // Workers pulled into a fight stay there until the defense manager releases them
+    if pulled_worker_ids.contains(unit_id) {
+      return true;
+    }
+
+    // For PlaceBuilding orders, check if unit is actually constructing or idle
+    if cmd.order == Order::PlaceBuilding {
+      // Keep command only if unit is moving to build location or constructing
+      return unit.is_constructing || unit.order == Order::PlaceBuilding;
+    }
+    // For Train orders, check if the building is training
+    if cmd.order == Order::Train {
+      return unit.is_training;
+    }
+
+    false
+  });
+}
+
+fn try_start_next_build(world: &impl GameWorld, state: &mut GameState) {
+  let Some(unit_type) = get_next_thing_to_build(world, state) else {
+    return;
+  };
+
+  let reason = build_reason(world, state, unit_type);
+  let builder_id = if unit_type.is_building() {
+    let Some((builder, build_location)) = find_builder_and_location(world, unit_type, state) else {
+      log_warn!(
+        LogTarget::Placement,
+        "No valid build location found for {}",
+        unit_type.name()
+      );
+      return;
+    };
+
+    if !assign_builder_to_construct(world, &builder, unit_type, build_location, &reason, state) {
+      return;
+    }
+    builder.id
+  } else {
+    let Some(trainer) = find_builder_for_unit(world, unit_type, None, state) else {
+      return;
+    };
+
+    if !assign_trainer_to_train(world, &trainer, unit_type, &reason, state) {
+      return;
+    }
+    trainer.id
+  };
+
+  let entry = BuildHistoryEntry {
+    unit_type: Some(unit_type),
+    upgrade_type: None,
+    assigned_unit_id: Some(builder_id),
+  };
+
+  state.unit_build_history.push(entry);
+
+  let current_stage = &state.build_stages[state.current_stage_index];
+  log_info!(
+    LogTarget::Build,
+    "Started building {} with unit {} (Stage: {})",
+    unit_type.name(),
+    builder_id,
+    current_stage.name
+  );
+}
+
+/// Why the planner picked `unit_type`, for the command log
+fn build_reason(world: &impl GameWorld, state: &GameState, unit_type: UnitType) -> String {
+  if unit_type == UnitType::Protoss_Pylon && check_need_more_supply(world, state).is_some() {
+    return format!(
+      "supply {}/{}",
+      world.supply_used() / 2,
+      world.supply_total() / 2
+    );
+  }
+  if unit_type.is_resource_depot() && state.expansion.saturation_requested {
+    return "bases are saturated".to_string();
+  }
+
+  let Some(stage) = state.build_stages.get(state.current_stage_index) else {
+    return String::new();
+  };
+  format!(
+    "stage '{}' wants {} {}, have {}",
+    stage.name,
+    stage.desired_counts.get(&unit_type).copied().unwrap_or(0),
+    unit_type.name(),
+    count_units_of_type(world, state, unit_type)
+  )
+}
+
+fn get_status_for_stage_items(
+  world: &impl GameWorld,
+  state: &GameState,
+) -> std::collections::HashMap<String, String> {
+  let mut status_map = std::collections::HashMap::new();
+
+  let Some(current_stage) = state.build_stages.get(state.current_stage_index) else {
+    return status_map;
+  };
+
+  for (unit_type, &desired_count) in &current_stage.desired_counts {
+    let unit_name = unit_type.name().to_string();
+    let current_count = count_units_of_type(world, state, *unit_type);
+
+    if current_count >= desired_count {
+      status_map.insert(
+        unit_name,
+        format!("Complete ({}/{})", current_count, desired_count),
+      );
+      continue;
+    }
+
+    // BWAPI's placement check doesn't know about tech, so the command would only fail
+    if let Some(required) = missing_requirement(world, *unit_type) {
+      status_map.insert(
+        unit_name,
+        format!(
+          "Waiting for {} ({}/{})",
+          required.name(),
+          current_count,
+          desired_count
+        ),
+      );
+      continue;
+    }
+
+    if !can_afford_unit(world, *unit_type) {
+      let minerals_short = unit_type.mineral_price() - world.minerals();
+      let gas_short = unit_type.gas_price() - world.gas();
+      status_map.insert(
+        unit_name,
+        format!(
+          "Need {} minerals, {} gas ({}/{})",
+          minerals_short.max(0),
+          gas_short.max(0),
+          current_count,
+          desired_count
+        ),
+      );
+      continue;
+    }
+
+    if unit_type.is_building() && find_builder_for_unit(world, *unit_type, None, state).is_none() {
+      status_map.insert(
+        unit_name,
+        format!("No builder available ({}/{})", current_count, desired_count),
+      );
+      continue;
+    }
+
+    status_map.insert(
+      unit_name,
+      format!("Ready to build ({}/{})", current_count, desired_count),
+    );
+  }
+
+  status_map
+}
+
+fn get_next_thing_to_build(world: &impl GameWorld, state: &GameState) -> Option<UnitType> {
+  let current_stage = state.build_stages.get(state.current_stage_index)?;
+
+  if let Some(pylon) = check_need_more_supply(world, state) {
+    return Some(pylon);
+  }
+
+  if let Some(nexus) = check_need_expansion(world, state) {
+    return Some(nexus);
+  }
+
+  let status_map = get_status_for_stage_items(world, state);
+  let mut candidates = Vec::new();
+
+  for (unit_type, &desired_count) in &current_stage.desired_counts {
+    let current_count = count_units_of_type(world, state, *unit_type);
+
+    if current_count >= desired_count {
+      continue;
+    }
+
+    let status = status_map.get(unit_type.name());
+    if status.is_some() && status.unwrap().starts_with("Ready to build") {
+      candidates.push(*unit_type);
+    }
+  }
+
+  candidates
+    .into_iter()
+    .max_by_key(|unit_type| unit_type.mineral_price() + unit_type.gas_price())
+}
+
+fn check_need_more_supply(world: &impl GameWorld, state: &GameState) -> Option<UnitType> {
+  let supply_used = world.supply_used();
+  let supply_total = world.supply_total();
+
+  if supply_total == 0 {
+    return None;
+  }
+
+  let supply_remaining = supply_total - supply_used;
+  let threshold = ((supply_total as f32) * 0.15).ceil() as i32;
+
+  if supply_remaining <= threshold && supply_total < 400 {
+    let pylon_type = UnitType::Protoss_Pylon;
+
+    if can_afford_unit(world, pylon_type)
+      && find_builder_and_location(world, pylon_type, state).is_some()
+    {
+      return Some(pylon_type);
+    }
+  }
+
+  None
+}
+
+fn check_need_expansion(world: &impl GameWorld, state: &GameState) -> Option<UnitType> {
+  if !state.expansion.saturation_requested {
+    return None;
+  }
+
+  let nexus_type = UnitType::Protoss_Nexus;
+  if can_afford_unit(world, nexus_type)
+    && find_builder_and_location(world, nexus_type, state).is_some()
+  {
+    return Some(nexus_type);
+  }
+
+  None
+}
+
+fn find_builder_for_unit(
+  world: &impl GameWorld,
+  unit_type: UnitType,
+  near: Option<Position>,
+  state: &GameState,
+) -> Option<UnitInfo> {
+  let builder_type = unit_type.what_builds().0;
+
+  let mut candidates = world.my_units().into_iter().filter(|u| {
+    u.unit_type == builder_type
+      && !u.is_constructing
+      && !u.is_training
+      && (u.is_idle || u.is_gathering_minerals || u.is_gathering_gas)
+      && !state.intended_commands.contains_key(&u.id)
+      && !state.workers.gas_assignments.contains_key(&u.id)
+  });
+
+  let Some(target) = near else {
+    return candidates.next();
+  };
+
+  // Prefer workers that are not about to return cargo, then the closest one
+  candidates.min_by_key(|u| {
+    let pos = u.position;
+    let distance_sq = (pos.x - target.x).pow(2) + (pos.y - target.y).pow(2);
+    (u.is_carrying_minerals, distance_sq)
+  })
+}
+
+fn find_builder_and_location(
+  world: &impl GameWorld,
+  unit_type: UnitType,
+  state: &GameState,
+) -> Option<(UnitInfo, TilePosition)> {
+  // Any eligible worker can validate placement; the site itself is anchored on the base
+  let placement_checker = find_builder_for_unit(world, unit_type, None, state)?;
+  let build_location = if unit_type.is_resource_depot() {
+    // Depots go on the resource-optimal tile of the best ranked expansion
+    expansion_manager::next_expansion_tile(state)
+      .filter(|tile| world.can_build_here(placement_checker.id, *tile, unit_type))?
+  } else {
+    let anchor = build_location_utils::get_base_anchor(world);
+    build_location_utils::find_build_location(world, placement_checker.id, unit_type, anchor, 25)?
+  };
+
+  let site_center = Position {
+    x: build_location.x * 32 + unit_type.tile_width() * 16,
+    y: build_location.y * 32 + unit_type.tile_height() * 16,
+  };
+  let builder = find_builder_for_unit(world, unit_type, Some(site_center), state)?;
+
+  Some((builder, build_location))
+}
+
+fn assign_builder_to_construct(
+  world: &impl GameWorld,
+  builder: &UnitInfo,
+  unit_type: UnitType,
+  build_location: TilePosition,
+  reason: &str,
+  state: &mut GameState,
+) -> bool {
+  let builder_id = builder.id;
+
+  log_debug!(
+    LogTarget::Placement,
+    "Attempting to build {} at {:?} with worker {} (currently at {:?})",
+    unit_type.name(),
+    build_location,
+    builder_id,
+    builder.position
+  );
+
+  let result = world.build(builder_id, unit_type, build_location);
+  state.commands.record(
+    CommandRecord::new(builder_id, "Build", "build_manager", reason)
+      .with_target((unit_type, build_location))
+      .with_result(&result),
+  );
+
+  match result {
+    Ok(_) => {
+      log_debug!(
+        LogTarget::Build,
+        "Build command succeeded for {}",
+        unit_type.name()
+      );
+      let intended_cmd = IntendedCommand {
+        order: Order::PlaceBuilding,
+        target_position: Some(build_location.to_position()),
+        target_unit_id: None,
+      };
+      state.intended_commands.insert(builder_id, intended_cmd);
+      true
+    }
+    Err(e) => {
+      log_warn!(
+        LogTarget::Build,
+        "Build command FAILED for {}: {:?}",
+        unit_type.name(),
+        e
+      );
+      false
+    }
+  }
+}
+
+fn assign_trainer_to_train(
+  world: &impl GameWorld,
+  trainer: &UnitInfo,
+  unit_type: UnitType,
+  reason: &str,
+  state: &mut GameState,
+) -> bool {
+  let result = world.train(trainer.id, unit_type);
+  state.commands.record(
+    CommandRecord::new(trainer.id, "Train", "build_manager", reason)
+      .with_target(unit_type)
+      .with_result(&result),
+  );
+
+  match result {
+    Ok(_) => {
+      let intended_cmd = IntendedCommand {
+        order: Order::Train,
+        target_position: None,
+        target_unit_id: None,
+      };
+      state.intended_commands.insert(trainer.id, intended_cmd);
+      true
+    }
+    Err(e) => {
+      log_warn!(
+        LogTarget::Build,
+        "Train command FAILED for {}: {:?}",
+        unit_type.name(),
+        e
+      );
+      false
+    }
+  }
+}
+
+fn count_units_of_type(world: &impl GameWorld, _state: &GameState, unit_type: UnitType) -> i32 {
+  let existing = world
+    .my_units()
+    .iter()
+    .filter(|u| u.unit_type == unit_type)
+    .count() as i32;
+
+  existing
+}
+
+fn missing_requirement(world: &impl GameWorld, unit_type: UnitType) -> Option<UnitType> {
+  let units = world.my_units();
+  tech_requirements::missing_requirement(unit_type, |required| {
+    units
+      .iter()
+      .any(|u| u.unit_type == required && u.is_completed)
+  })
+}
+
+fn can_afford_unit(world: &impl GameWorld, unit_type: UnitType) -> bool {
+  let minerals = world.minerals();
+  let gas = world.gas();
+
+  minerals >= unit_type.mineral_price() && gas >= unit_type.gas_price()
+}
+
+fn check_and_advance_stage(world: &impl GameWorld, state: &mut GameState) {
+  let Some(current_stage) = state.build_stages.get(state.current_stage_index) else {
+    return;
+  };
+
+  let stage_complete = current_stage
+    .desired_counts
+    .iter()
+    .all(|(unit_type, &desired_count)| {
+      let current_count = count_units_of_type(world, state, *unit_type);
+      current_count >= desired_count
+    });
+
+  if stage_complete {
+    let next_stage_index = state.current_stage_index + 1;
+
+    if next_stage_index < state.build_stages.len() {
+      log_info!(
+        LogTarget::Build,
+        "Stage '{}' complete! Advancing to stage {}",
+        current_stage.name,
+        state.build_stages[next_stage_index].name
+      );
+      state.current_stage_index = next_stage_index;
+    }
+  }
+}
+
+pub fn print_debug_build_status(world: &impl GameWorld, state: &GameState) {
+  let mut y = 10;
+  let x = 3;
+
+  if let Some(current_stage) = state.build_stages.get(state.current_stage_index) {
+    let next_build = get_next_thing_to_build(world, state);
+    let next_build_str = if let Some(unit_type) = next_build {
+      format!(
+        "Next: {} ({}/{} M, {}/{} G)",
+        unit_type.name(),
+        world.minerals(),
+        unit_type.mineral_price(),
+        world.gas(),
+        unit_type.gas_price()
+      )
+    } else {
+      "Next: None".to_string()
+    };
+    world.draw_text_screen(x, y, &next_build_str);
+    y += 10;
+
+    if let Some(last_entry) = state.unit_build_history.last() {
+      let unit_name = if let Some(unit_type) = last_entry.unit_type {
+        unit_type.name()
+      } else if let Some(_upgrade) = last_entry.upgrade_type {
+        "Upgrade"
+      } else {
+        "Unknown"
+      };
+      world.draw_text_screen(x, y, &format!("Last Built: {}", unit_name));
+    } else {
+      world.draw_text_screen(x, y, "Last Built: None");
+    }
+    y += 10;
+
+    world.draw_text_screen(x, y, "Stage Progress:");
+    y += 10;
+
+    for (unit_type, &desired_count) in &current_stage.desired_counts {
+      let current_count = count_units_of_type(world, state, *unit_type);
+      world.draw_text_screen(
+        x + 10,
+        y,
+        &format!("{}: {}/{}", unit_type.name(), current_count, desired_count),
+      );
+      y += 10;
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    state::build_stages::BuildStage,
+    utils::strategy_recognition::EnemyOpening,
+    world::fake_world::{FakeCommand, FakeWorld},
+  };
+
+  fn world_with_base() -> (FakeWorld, usize, usize) {
+    let mut world = FakeWorld::default();
+    let nexus = world.add_unit(UnitType::Protoss_Nexus, TilePosition { x: 10, y: 10 });
+    let probe = world.add_unit(UnitType::Protoss_Probe, TilePosition { x: 8, y: 14 });
+    (world, nexus, probe)
+  }
+
+  fn state_with_stage(stage: BuildStage) -> GameState {
+    GameState {
+      build_stages: vec![stage],
+      ..GameState::default()
+    }
+  }
+
+  #[test]
+  fn trains_probe_from_idle_nexus() {
+    let (world, nexus, _) = world_with_base();
+    let mut state =
+      state_with_stage(BuildStage::new("Probes").with_unit(UnitType::Protoss_Probe, 4));
+
+    on_frame(&world, &mut state);
+
+    assert_eq!(
+      world.issued(),
+      vec![FakeCommand::Train {
+        unit_id: nexus,
+        unit_type: UnitType::Protoss_Probe
+      }]
+    );
+    assert_eq!(state.intended_commands[&nexus].order, Order::Train);
+  }
+
+  #[test]
+  fn builds_pylon_when_supply_is_low() {
+    let (mut world, _, probe) = world_with_base();
+    world.minerals = 100;
+    world.supply_used = 16;
+    let mut state =
+      state_with_stage(BuildStage::new("Probes").with_unit(UnitType::Protoss_Probe, 20));
+
+    on_frame(&world, &mut state);
+
+    let issued = world.issued();
+    assert_eq!(issued.len(), 1);
+    let FakeCommand::Build {
+      unit_id,
+      unit_type,
+      tile,
+    } = issued[0]
+    else {
+      panic!("expected a build command, got {:?}", issued[0]);
+    };
+    assert_eq!(unit_id, probe);
+    assert_eq!(unit_type, UnitType::Protoss_Pylon);
+    assert!(world.can_build_here(probe, tile, UnitType::Protoss_Pylon));
+    assert_eq!(state.intended_commands[&probe].order, Order::PlaceBuilding);
+  }
+
+  #[test]
+  fn waits_when_it_cannot_afford_the_next_item() {
+    let (mut world, _, _) = world_with_base();
+    world.minerals = 100;
+    let mut state =
+      state_with_stage(BuildStage::new("Gateway").with_unit(UnitType::Protoss_Gateway, 1));
+
+    on_frame(&world, &mut state);
+
+    assert!(world.issued().is_empty());
+    assert!(state.stage_item_status["Protoss_Gateway"].starts_with("Need"));
+  }
+
+  #[test]
+  fn skips_blocked_build_sites() {
+    let (mut world, _, probe) = world_with_base();
+    world.minerals = 150;
+    for x in 0..64 {
+      for y in 0..64 {
+        if (x, y) != (20, 20) && (x, y) != (21, 20) && (x, y) != (20, 21) && (x, y) != (21, 21) {
+          world.unbuildable.insert(TilePosition { x, y });
+        }
+      }
+    }
+    let mut state =
+      state_with_stage(BuildStage::new("Pylon").with_unit(UnitType::Protoss_Pylon, 1));
+
+    on_frame(&world, &mut state);
+
+    assert_eq!(
+      world.issued(),
+      vec![FakeCommand::Build {
+        unit_id: probe,
+        unit_type: UnitType::Protoss_Pylon,
+        tile: TilePosition { x: 20, y: 20 }
+      }]
+    );
+  }
+
+  #[test]
+  fn waits_for_missing_prerequisites() {
+    let (mut world, _, probe) = world_with_base();
+    world.add_unit(UnitType::Protoss_Gateway, TilePosition { x: 16, y: 10 });
+    world.minerals = 400;
+    world.gas = 200;
+    let mut state = state_with_stage(
+      BuildStage::new("Robotics")
+        .with_unit(UnitType::Protoss_Cybernetics_Core, 1)
+        .with_unit(UnitType::Protoss_Robotics_Facility, 1),
+    );
+
+    on_frame(&world, &mut state);
+
+    assert!(matches!(
+      world.issued()[..],
+      [FakeCommand::Build {
+        unit_id,
+        unit_type: UnitType::Protoss_Cybernetics_Core,
+        ..
+      }] if unit_id == probe
+    ));
+    assert_eq!(
+      state.stage_item_status["Protoss_Robotics_Facility"],
+      "Waiting for Protoss_Cybernetics_Core (0/1)"
+    );
+  }
+
+  #[test]
+  fn holds_four_pool_with_the_forge_before_cannons() {
+    let (mut world, _, _) = world_with_base();
+    world.add_unit(UnitType::Protoss_Pylon, TilePosition { x: 16, y: 10 });
+    world.add_unit(UnitType::Protoss_Gateway, TilePosition { x: 16, y: 14 });
+    world.minerals = 150;
+    let stage = build_stages::get_reaction_stage(EnemyOpening::FourPool).unwrap();
+
+    // Forge and Photon Cannon cost the same, so only the prerequisite check decides
+    for _ in 0..20 {
+      let mut state = state_with_stage(stage.clone());
+      on_frame(&world, &mut state);
+    }
+
+    assert!(world.issued().iter().all(|c| matches!(
+      c,
+      FakeCommand::Build {
+        unit_type: UnitType::Protoss_Forge,
+        ..
+      }
+    )));
+    assert_eq!(world.issued().len(), 20);
+  }
+
+  #[test]
+  fn advances_stage_once_counts_are_met() {
+    let (world, _, _) = world_with_base();
+    let mut state = GameState {
+      build_stages: vec![
+        BuildStage::new("Nexus").with_unit(UnitType::Protoss_Nexus, 1),
+        BuildStage::new("Probes").with_unit(UnitType::Protoss_Probe, 4),
+      ],
+      ..GameState::default()
+    };
+
+    on_frame(&world, &mut state);
+
+    assert_eq!(state.current_stage_index, 1);
+  }
+
+  #[test]
+  fn drops_commands_for_missing_or_finished_units() {
+    let (mut world, nexus, probe) = world_with_base();
+    world.unit_mut(nexus).is_training = true;
+    let mut state = state_with_stage(BuildStage::new("Empty"));
+    for (unit_id, order) in [
+      (nexus, Order::Train),
+      (probe, Order::PlaceBuilding),
+      (99, Order::Train),
+    ] {
+      state.intended_commands.insert(
+        unit_id,
+        IntendedCommand {
+          order,
+          target_position: None,
+          target_unit_id: None,
+        },
+      );
+    }
+
+    cleanup_stale_commands(&world, &mut state);
+
+    assert!(state.intended_commands.contains_key(&nexus));
+    assert!(!state.intended_commands.contains_key(&probe));
+    assert!(!state.intended_commands.contains_key(&99));
+  }
+}
@@ -0,0 +1,442 @@
+//! A small deterministic combat simulator in the spirit of FAP. Units walk towards
+//! their closest target, shoot whenever their weapon is off cooldown and the outcome
+//! is scored by the resource value each side has left. Nothing here touches BWAPI so
+//! it can be tested anywhere.
+
+const MIN_DAMAGE: f32 = 0.5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DamageType {
+  Normal,
+  Concussive,
+  Explosive,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnitSize {
+  Small,
+  Medium,
+  Large,
+}
+
+impl DamageType {
+  fn modifier(&self, size: UnitSize) -> f32 {
+    match (self, size) {
+      (DamageType::Normal, _) => 1.0,
+      (DamageType::Concussive, UnitSize::Small) => 1.0,
+      (DamageType::Concussive, UnitSize::Medium) => 0.5,
+      (DamageType::Concussive, UnitSize::Large) => 0.25,
+      (DamageType::Explosive, UnitSize::Small) => 0.5,
+      (DamageType::Explosive, UnitSize::Medium) => 0.75,
+      (DamageType::Explosive, UnitSize::Large) => 1.0,
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Weapon {
+  pub damage: i32,
+  /// Extra damage per attack upgrade level
+  pub damage_bonus: i32,
+  /// Number of hits per attack, e.g. 2 for Zealots
+  pub factor: i32,
+  pub cooldown: i32,
+  pub min_range: i32,
+  pub max_range: i32,
+  pub damage_type: DamageType,
+}
+
+#[derive(Clone, Debug)]
+pub struct SimUnit {
+  pub x: f32,
+  pub y: f32,
+  /// Half of the unit's larger dimension, ranges are measured edge to edge
+  pub radius: f32,
+  /// Pixels per frame
+  pub speed: f32,
+  pub hit_points: f32,
+  pub max_hit_points: f32,
+  pub shields: f32,
+  pub max_shields: f32,
+  pub armor: i32,
+  pub size: UnitSize,
+  pub is_flyer: bool,
+  pub ground_weapon: Option<Weapon>,
+  pub air_weapon: Option<Weapon>,
+  pub attack_upgrades: i32,
+  pub armor_upgrades: i32,
+  pub shield_upgrades: i32,
+  /// Resource cost used to score the outcome
+  pub value: f32,
+  pub cooldown_remaining: i32,
+}
+
+impl SimUnit {
+  pub fn is_alive(&self) -> bool {
+    self.hit_points > 0.0
+  }
+
+  fn weapon_against(&self, target: &SimUnit) -> Option<Weapon> {
+    if target.is_flyer {
+      self.air_weapon
+    } else {
+      self.ground_weapon
+    }
+  }
+
+  fn edge_distance(&self, other: &SimUnit) -> f32 {
+    let center = ((self.x - other.x).powi(2) + (self.y - other.y).powi(2)).sqrt();
+    (center - self.radius - other.radius).max(0.0)
+  }
+
+  /// Remaining value, scaled by how much of its hit points and shields are left
+  fn score(&self) -> f32 {
+    if !self.is_alive() {
+      return 0.0;
+    }
+    let max = self.max_hit_points + self.max_shields;
+    if max <= 0.0 {
+      return self.value;
+    }
+    self.value * (self.hit_points + self.shields) / max
+  }
+}
+
+#[derive(Clone, Debug)]
+pub struct SimResult {
+  pub frames: i32,
+  pub our_score_before: f32,
+  pub our_score_after: f32,
+  pub enemy_score_before: f32,
+  pub enemy_score_after: f32,
+  pub our_survivors: usize,
+  pub enemy_survivors: usize,
+}
+
+impl SimResult {
+  pub fn our_losses(&self) -> f32 {
+    self.our_score_before - self.our_score_after
+  }
+
+  pub fn enemy_losses(&self) -> f32 {
+    self.enemy_score_before - self.enemy_score_after
+  }
+
+  /// True when the fight costs the enemy more than it costs us and we are left standing.
+  pub fn is_favorable(&self) -> bool {
+    if self.our_survivors == 0 {
+      return self.enemy_survivors == 0 && self.enemy_score_before == 0.0;
+    }
+    self.enemy_survivors == 0 || self.enemy_losses() >= self.our_losses()
+  }
+}
+
+/// Applies one attack to the target the way Brood War does: shields soak damage minus
+/// shield upgrades first, the rest is reduced by armor and scaled by damage type.
+pub fn apply_attack(weapon: &Weapon, attack_upgrades: i32, target: &mut SimUnit) {
+  let per_hit = (weapon.damage + weapon.damage_bonus * attack_upgrades) as f32;
+  let shield_armor = target.shield_upgrades as f32;
+  let armor = (target.armor + target.armor_upgrades) as f32;
+  let modifier = weapon.damage_type.modifier(target.size);
+
+  for _ in 0..weapon.factor.max(1) {
+    let mut damage = per_hit;
+    if target.shields > 0.0 {
+      let to_shields = (damage - shield_armor).max(0.0);
+      if to_shields <= target.shields {
+        target.shields -= to_shields;
+        continue;
+      }
+      damage = to_shields - target.shields;
+      target.shields = 0.0;
+    }
+    target.hit_points -= ((damage - armor) * modifier).max(MIN_DAMAGE);
+  }
+}
+
+/// Runs the fight for up to `frames` frames or until one side is gone.
+pub fn simulate(ours: &[SimUnit], enemies: &[SimUnit], frames: i32) -> SimResult {
+  let mut ours = ours.to_vec();
+  let mut enemies = enemies.to_vec();
+  let our_score_before = total_score(&ours);
+  let enemy_score_before = total_score(&enemies);
+
+  let mut frame = 0;
+  while frame < frames && ours.iter().any(|u| u.is_alive()) && enemies.iter().any(|u| u.is_alive())
+  {
+    // Both sides act on the same snapshot so neither gets the first shot for free
+    let our_actions = plan_actions(&ours, &enemies);
+    let enemy_actions = plan_actions(&enemies, &ours);
+    apply_actions(&mut ours, &mut enemies, &our_actions);
+    apply_actions(&mut enemies, &mut ours, &enemy_actions);
+    frame += 1;
+  }
+
+  SimResult {
+    frames: frame,
+    our_score_before,
+    our_score_after: total_score(&ours),
+    enemy_score_before,
+    enemy_score_after: total_score(&enemies),
+    our_survivors: ours.iter().filter(|u| u.is_alive()).count(),
+    enemy_survivors: enemies.iter().filter(|u| u.is_alive()).count(),
+  }
+}
+
+/// Like `simulate`, but lines both sides up facing each other first. Useful for
+/// comparing armies whose real positions are far apart or only remembered.
+pub fn simulate_head_on(ours: &[SimUnit], enemies: &[SimUnit], frames: i32) -> SimResult {
+  const SPACING: f32 = 24.0;
+  const GAP: f32 = 8.0 * 32.0;
+
+  let line_up = |units: &[SimUnit], x: f32| -> Vec<SimUnit> {
+    units
+      .iter()
+      .enumerate()
+      .map(|(i, unit)| SimUnit {
+        x,
+        y: i as f32 * SPACING,
+        ..unit.clone()
+      })
+      .collect()
+  };
+
+  simulate(&line_up(ours, 0.0), &line_up(enemies, GAP), frames)
+}
+
+enum Action {
+  Idle,
+  Attack { target: usize, weapon: Weapon },
+  MoveTowards { x: f32, y: f32 },
+}
+
+fn plan_actions(side: &[SimUnit], opponents: &[SimUnit]) -> Vec<Action> {
+  side
+    .iter()
+    .map(|unit| {
+      if !unit.is_alive() {
+        return Action::Idle;
+      }
+
+      let Some((target, weapon)) = opponents
+        .iter()
+        .enumerate()
+        .filter(|(_, o)| o.is_alive())
+        .filter_map(|(i, o)| unit.weapon_against(o).map(|w| (i, w)))
+        .min_by(|a, b| {
+          unit
+            .edge_distance(&opponents[a.0])
+            .total_cmp(&unit.edge_distance(&opponents[b.0]))
+        })
+      else {
+        return Action::Idle;
+      };
+
+      let distance = unit.edge_distance(&opponents[target]);
+      if distance <= weapon.max_range as f32 && distance >= weapon.min_range as f32 {
+        if unit.cooldown_remaining > 0 {
+          Action::Idle
+        } else {
+          Action::Attack { target, weapon }
+        }
+      } else if unit.speed > 0.0 {
+        Action::MoveTowards {
+          x: opponents[target].x,
+          y: opponents[target].y,
+        }
+      } else {
+        Action::Idle
+      }
+    })
+    .collect()
+}
+
+fn apply_actions(side: &mut [SimUnit], opponents: &mut [SimUnit], actions: &[Action]) {
+  for (unit, action) in side.iter_mut().zip(actions) {
+    if unit.cooldown_remaining > 0 {
+      unit.cooldown_remaining -= 1;
+    }
+
+    match action {
+      Action::Idle => {}
+      Action::Attack { target, weapon } => {
+        apply_attack(weapon, unit.attack_upgrades, &mut opponents[*target]);
+        unit.cooldown_remaining = weapon.cooldown;
+      }
+      Action::MoveTowards { x, y } => {
+        let dx = x - unit.x;
+        let dy = y - unit.y;
+        let length = (dx * dx + dy * dy).sqrt();
+        if length > 0.0 {
+          let step = unit.speed.min(length);
+          unit.x += dx / length * step;
+          unit.y += dy / length * step;
+        }
+      }
+    }
+  }
+}
+
+fn total_score(units: &[SimUnit]) -> f32 {
+  units.iter().map(|u| u.score()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn zealot(x: f32) -> SimUnit {
+    SimUnit {
+      x,
+      y: 0.0,
+      radius: 11.0,
+      speed: 4.0,
+      hit_points: 100.0,
+      max_hit_points: 100.0,
+      shields: 60.0,
+      max_shields: 60.0,
+      armor: 1,
+      size: UnitSize::Small,
+      is_flyer: false,
+      ground_weapon: Some(Weapon {
+        damage: 8,
+        damage_bonus: 1,
+        factor: 2,
+        cooldown: 22,
+        min_range: 0,
+        max_range: 15,
+        damage_type: DamageType::Normal,
+      }),
+      air_weapon: None,
+      attack_upgrades: 0,
+      armor_upgrades: 0,
+      shield_upgrades: 0,
+      value: 100.0,
+      cooldown_remaining: 0,
+    }
+  }
+
+  fn zergling(x: f32) -> SimUnit {
+    SimUnit {
+      x,
+      y: 0.0,
+      radius: 8.0,
+      speed: 5.5,
+      hit_points: 35.0,
+      max_hit_points: 35.0,
+      shields: 0.0,
+      max_shields: 0.0,
+      armor: 0,
+      size: UnitSize::Small,
+      is_flyer: false,
+      ground_weapon: Some(Weapon {
+        damage: 5,
+        damage_bonus: 1,
+        factor: 1,
+        cooldown: 8,
+        min_range: 0,
+        max_range: 15,
+        damage_type: DamageType::Normal,
+      }),
+      air_weapon: None,
+      attack_upgrades: 0,
+      armor_upgrades: 0,
+      shield_upgrades: 0,
+      value: 25.0,
+      cooldown_remaining: 0,
+    }
+  }
+
+  fn dragoon_weapon() -> Weapon {
+    Weapon {
+      damage: 20,
+      damage_bonus: 2,
+      factor: 1,
+      cooldown: 30,
+      min_range: 0,
+      max_range: 128,
+      damage_type: DamageType::Explosive,
+    }
+  }
+
+  #[test]
+  fn explosive_damage_is_halved_against_small_units() {
+    let mut target = zergling(0.0);
+
+    apply_attack(&dragoon_weapon(), 0, &mut target);
+
+    assert_eq!(target.hit_points, 25.0);
+  }
+
+  #[test]
+  fn shields_absorb_damage_before_armor_applies() {
+    let mut target = zealot(0.0);
+    target.shields = 5.0;
+    target.shield_upgrades = 1;
+
+    // 22 damage: 1 blocked by shield upgrades, 5 soaked by shields, 16 - 1 armor to hp
+    apply_attack(&dragoon_weapon(), 1, &mut target);
+
+    assert_eq!(target.shields, 0.0);
+    assert_eq!(target.hit_points, 100.0 - (16.0 - 1.0) * 0.5);
+  }
+
+  #[test]
+  fn larger_army_wins_even_fight() {
+    let ours = vec![zealot(0.0), zealot(0.0), zealot(0.0), zealot(0.0)];
+    let enemies = vec![zealot(200.0)];
+
+    let result = simulate(&ours, &enemies, 24 * 20);
+
+    assert_eq!(result.enemy_survivors, 0);
+    assert!(result.our_survivors > 0);
+    assert!(result.is_favorable());
+  }
+
+  #[test]
+  fn outnumbered_army_is_told_to_retreat() {
+    let ours = vec![zealot(0.0)];
+    let enemies: Vec<SimUnit> = (0..12).map(|i| zergling(150.0 + i as f32 * 4.0)).collect();
+
+    let result = simulate(&ours, &enemies, 24 * 20);
+
+    assert_eq!(result.our_survivors, 0);
+    assert!(!result.is_favorable());
+  }
+
+  #[test]
+  fn ground_only_units_cannot_hit_flyers() {
+    let ours = vec![zealot(0.0)];
+    let mut flyer = zergling(20.0);
+    flyer.is_flyer = true;
+    flyer.ground_weapon = None;
+
+    let result = simulate(&ours, &[flyer], 24 * 5);
+
+    assert_eq!(result.enemy_losses(), 0.0);
+    assert_eq!(result.our_losses(), 0.0);
+  }
+
+  #[test]
+  fn static_units_do_not_chase() {
+    let mut cannon = zealot(0.0);
+    cannon.speed = 0.0;
+    let far_away = zergling(1000.0);
+    let mut idle = far_away.clone();
+    idle.ground_weapon = None;
+
+    let result = simulate(&[cannon], &[idle], 24 * 10);
+
+    assert_eq!(result.enemy_losses(), 0.0);
+  }
+
+  #[test]
+  fn head_on_lines_sides_up_within_reach() {
+    let ours = vec![zealot(-5000.0), zealot(7000.0)];
+    let enemies = vec![zergling(3000.0)];
+
+    let result = simulate_head_on(&ours, &enemies, 24 * 10);
+
+    assert_eq!(result.enemy_survivors, 0);
+  }
+}
@@ -0,0 +1,201 @@
+//! Audit trail of every order the bot gives. Each command is kept with the module that
+//! issued it, why, and whether BWAPI accepted it: the most recent ones in memory and,
+//! when a log file is open, all of them as JSON lines.
+
+use serde::Serialize;
+use std::{
+  collections::VecDeque,
+  fmt::Debug,
+  fs::File,
+  io::{LineWriter, Write},
+};
+
+use crate::{log_info, log_warn, logging::LogTarget};
+
+/// Commands kept in memory, older ones are dropped first
+pub const COMMAND_HISTORY_CAPACITY: usize = 2000;
+/// Ending of the per-game command log in the log folder, one JSON object per line
+pub const COMMAND_LOG_EXTENSION: &str = "commands.jsonl";
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CommandRecord {
+  pub frame: i32,
+  pub unit_id: usize,
+  pub order: String,
+  pub target: Option<String>,
+  /// Module that issued the command
+  pub source: &'static str,
+  pub reason: String,
+  /// Why BWAPI refused the command, `None` when it was accepted
+  pub error: Option<String>,
+}
+
+impl CommandRecord {
+  pub fn new(unit_id: usize, order: &str, source: &'static str, reason: impl Into<String>) -> Self {
+    Self {
+      frame: 0,
+      unit_id,
+      order: order.to_string(),
+      target: None,
+      source,
+      reason: reason.into(),
+      error: None,
+    }
+  }
+
+  pub fn with_target(mut self, target: impl Debug) -> Self {
+    self.target = Some(format!("{:?}", target));
+    self
+  }
+
+  pub fn with_result<T, E: Debug>(mut self, result: &Result<T, E>) -> Self {
+    self.error = result
+      .as_ref()
+      .err()
+      .map(|e| format!("{:?}", e).trim_matches('"').to_string());
+    self
+  }
+}
+
+pub struct CommandRecorder {
+  frame: i32,
+  records: VecDeque<CommandRecord>,
+  capacity: usize,
+  log: Option<LineWriter<File>>,
+}
+
+impl Default for CommandRecorder {
+  fn default() -> Self {
+    Self::with_capacity(COMMAND_HISTORY_CAPACITY)
+  }
+}
+
+impl CommandRecorder {
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self {
+      frame: 0,
+      records: VecDeque::with_capacity(capacity),
+      capacity,
+      log: None,
+    }
+  }
+
+  /// Starts writing every recorded command to `path`, replacing what was there
+  pub fn log_to(&mut self, path: &str) {
+    match File::create(path) {
+      Ok(file) => {
+        log_info!(LogTarget::Game, "Logging commands to {}", path);
+        self.log = Some(LineWriter::new(file));
+      }
+      Err(e) => log_warn!(
+        LogTarget::Game,
+        "Could not open command log {}: {}",
+        path,
+        e
+      ),
+    }
+  }
+
+  /// Frame stamped on the commands recorded from now on
+  pub fn set_frame(&mut self, frame: i32) {
+    self.frame = frame;
+  }
+
+  pub fn record(&mut self, mut record: CommandRecord) {
+    record.frame = self.frame;
+
+    if let Some(log) = &mut self.log {
+      let written = serde_json::to_string(&record)
+        .map_err(|e| e.to_string())
+        .and_then(|line| writeln!(log, "{}", line).map_err(|e| e.to_string()));
+      if let Err(e) = written {
+        log_warn!(
+          LogTarget::Game,
+          "Command log write FAILED, closing it: {}",
+          e
+        );
+        self.log = None;
+      }
+    }
+
+    if self.records.len() == self.capacity {
+      self.records.pop_front();
+    }
+    self.records.push_back(record);
+  }
+
+  /// Recorded commands, oldest first
+  pub fn recent(&self) -> impl Iterator<Item = &CommandRecord> {
+    self.records.iter()
+  }
+
+  pub fn for_unit(&self, unit_id: usize) -> impl Iterator<Item = &CommandRecord> {
+    self.records.iter().filter(move |r| r.unit_id == unit_id)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn stamps_the_current_frame() {
+    let mut recorder = CommandRecorder::default();
+    recorder.set_frame(42);
+
+    recorder.record(CommandRecord::new(7, "Move", "test", "because").with_target((1, 2)));
+
+    let record = recorder.recent().next().unwrap();
+    assert_eq!(record.frame, 42);
+    assert_eq!(record.target.as_deref(), Some("(1, 2)"));
+    assert_eq!(record.error, None);
+  }
+
+  #[test]
+  fn keeps_only_the_newest_commands() {
+    let mut recorder = CommandRecorder::with_capacity(3);
+
+    for unit_id in 0..5 {
+      recorder.record(CommandRecord::new(unit_id, "Stop", "test", ""));
+    }
+
+    let ids: Vec<usize> = recorder.recent().map(|r| r.unit_id).collect();
+    assert_eq!(ids, vec![2, 3, 4]);
+  }
+
+  #[test]
+  fn records_errors() {
+    let mut recorder = CommandRecorder::default();
+    let result: Result<(), String> = Err("Insufficient_Minerals".to_string());
+
+    recorder.record(CommandRecord::new(3, "Train", "test", "").with_result(&result));
+
+    let record = recorder.for_unit(3).next().unwrap();
+    assert_eq!(record.error.as_deref(), Some("Insufficient_Minerals"));
+  }
+
+  #[test]
+  fn writes_json_lines() {
+    let path = std::env::temp_dir().join(format!(
+      "protossbot_command_log_{}_writes_json_lines.jsonl",
+      std::process::id()
+    ));
+    let path = path.to_str().unwrap();
+    let mut recorder = CommandRecorder::default();
+    recorder.log_to(path);
+
+    recorder.record(CommandRecord::new(1, "Gather", "test", "idle worker"));
+    recorder.record(CommandRecord::new(2, "Gather", "test", "idle worker"));
+    drop(recorder);
+
+    let text = std::fs::read_to_string(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    let lines: Vec<serde_json::Value> = text
+      .lines()
+      .map(|line| serde_json::from_str(line).unwrap())
+      .collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[1]["unit_id"], 2);
+    assert_eq!(lines[1]["reason"], "idle worker");
+  }
+}
@@ -0,0 +1,623 @@
+#[cfg(feature = "bwapi")]
+use rsbwapi::{Game, Player, Unit};
+use std::collections::HashSet;
+
+#[cfg(feature = "bwapi")]
+use crate::{
+  log_info, log_warn,
+  logging::LogTarget,
+  state::game_state::{GameState, IntendedCommand, SquadMode},
+  types::Order,
+  utils::{army_manager, command_recorder::CommandRecord},
+};
+use crate::{
+  state::game_state::Threat,
+  types::{Position, UnitType},
+};
+
+const COMMAND_INTERVAL_FRAMES: i32 = 12;
+/// Enemies this close to one of our buildings under attack count as a threat
+/// even outside of our regions
+const BUILDING_THREAT_RANGE: i32 = 8 * 32;
+const CANNON_COVERAGE_RANGE: i32 = 7 * 32;
+const CANNON_STRENGTH: f32 = 2.0;
+const WORKER_STRENGTH: f32 = 0.5;
+const WORKER_PULL_RANGE: i32 = 12 * 32;
+/// Frames a region has to stay clear before defenders are released
+const RELEASE_DELAY_FRAMES: i32 = 48;
+
+#[cfg(feature = "bwapi")]
+pub fn on_frame(game: &Game, player: &Player, state: &mut GameState) {
+  let frame = game.get_frame_count();
+  let our_units = player.get_units();
+
+  let our_regions: HashSet<usize> = our_units
+    .iter()
+    .filter(|u| u.get_type().is_building())
+    .filter_map(|u| state.terrain.region_at(u.get_tile_position()))
+    .collect();
+
+  let threats = detect_threats(game, player, &our_units, &our_regions, state);
+  for threat in threats {
+    match state
+      .defense
+      .threats
+      .iter_mut()
+      .find(|t| t.region_id == threat.region_id)
+    {
+      Some(existing) => *existing = threat,
+      None => {
+        log_info!(
+          LogTarget::Defense,
+          "Enemy incursion in region {:?}: {} units, strength {:.1}",
+          threat.region_id,
+          threat.enemy_ids.len(),
+          threat.strength
+        );
+        state.defense.threats.push(threat);
+      }
+    }
+  }
+
+  release_cleared_threats(frame, player, state);
+
+  if frame % COMMAND_INTERVAL_FRAMES != 0 {
+    return;
+  }
+
+  let enemies: Vec<Unit> = game
+    .get_all_units()
+    .into_iter()
+    .filter(|u| u.get_player().is_enemy(player))
+    .collect();
+
+  for threat_index in 0..state.defense.threats.len() {
+    if state.defense.threats[threat_index].last_seen_frame != frame {
+      continue;
+    }
+    assign_squad(&our_units, threat_index, state);
+    command_defenders(&our_units, &enemies, threat_index, state);
+    pull_workers_if_needed(&our_units, &enemies, threat_index, state);
+  }
+}
+
+/// Rough fighting value of a unit type in supply, with attacking workers counting a little.
+pub fn threat_value(unit_type: UnitType) -> f32 {
+  if unit_type.is_worker() {
+    return WORKER_STRENGTH;
+  }
+  if !unit_type.can_attack() {
+    return 0.0;
+  }
+  if unit_type.is_building() {
+    // Proxied cannons and bunkers creeping into our base
+    return CANNON_STRENGTH;
+  }
+  (unit_type.supply_required() as f32 / 2.0).max(0.5)
+}
+
+/// What threat scoring needs to know about one enemy unit
+#[derive(Clone, Debug)]
+struct Intruder {
+  id: usize,
+  unit_type: UnitType,
+  position: Position,
+  region_id: Option<usize>,
+  /// Targeting one of our buildings or workers
+  attacking_base: bool,
+}
+
+#[cfg(feature = "bwapi")]
+fn detect_threats(
+  game: &Game,
+  player: &Player,
+  our_units: &[Unit],
+  our_regions: &HashSet<usize>,
+  state: &GameState,
+) -> Vec<Threat> {
+  let buildings_under_attack: Vec<Position> = our_units
+    .iter()
+    .filter(|u| u.get_type().is_building() && u.is_under_attack())
+    .map(|u| u.get_position())
+    .collect();
+
+  let intruders: Vec<Intruder> = game
+    .get_all_units()
+    .into_iter()
+    .filter(|u| u.get_player().is_enemy(player))
+    .map(|enemy| Intruder {
+      id: enemy.get_id(),
+      unit_type: enemy.get_type(),
+      position: enemy.get_position(),
+      region_id: state.terrain.region_at(enemy.get_tile_position()),
+      attacking_base: enemy.get_target().is_some_and(|target| {
+        target.get_player().get_id() == player.get_id()
+          && (target.get_type().is_building() || target.get_type().is_worker())
+      }),
+    })
+    .collect();
+
+  let mut threats = score_threats(
+    &intruders,
+    our_regions,
+    &buildings_under_attack,
+    game.get_frame_count(),
+  );
+
+  // Keep defenders already assigned to a region while it is still under threat
+  for threat in threats.iter_mut() {
+    if let Some(existing) = state
+      .defense
+      .threats
+      .iter()
+      .find(|t| t.region_id == threat.region_id)
+    {
+      threat.squad_id = existing.squad_id;
+      threat.pulled_worker_ids = existing.pulled_worker_ids.clone();
+    }
+  }
+
+  threats
+}
+
+/// Groups enemies inside our regions, or next to a building under attack, into one threat
+/// per region. A lone worker is only scouting unless it goes for our buildings or workers.
+fn score_threats(
+  intruders: &[Intruder],
+  our_regions: &HashSet<usize>,
+  buildings_under_attack: &[Position],
+  frame: i32,
+) -> Vec<Threat> {
+  let mut threats: Vec<Threat> = Vec::new();
+  for intruder in intruders {
+    let value = threat_value(intruder.unit_type);
+    if value <= 0.0 {
+      continue;
+    }
+
+    let in_our_region = intruder
+      .region_id
+      .is_some_and(|id| our_regions.contains(&id));
+    let near_attacked_building = buildings_under_attack
+      .iter()
+      .any(|pos| distance(*pos, intruder.position) <= BUILDING_THREAT_RANGE);
+    if !in_our_region && !near_attacked_building {
+      continue;
+    }
+
+    match threats
+      .iter_mut()
+      .find(|t| t.region_id == intruder.region_id)
+    {
+      Some(threat) => {
+        threat.enemy_ids.push(intruder.id);
+        threat.strength += value;
+      }
+      None => threats.push(Threat {
+        region_id: intruder.region_id,
+        center: intruder.position,
+        enemy_ids: vec![intruder.id],
+        strength: value,
+        squad_id: None,
+        pulled_worker_ids: Vec::new(),
+        last_seen_frame: frame,
+      }),
+    }
+  }
+
+  threats.retain(|threat| {
+    let &[only] = threat.enemy_ids.as_slice() else {
+      return true;
+    };
+    intruders
+      .iter()
+      .find(|i| i.id == only)
+      .is_some_and(|i| !i.unit_type.is_worker() || i.attacking_base)
+  });
+
+  for threat in threats.iter_mut() {
+    let positions: Vec<Position> = intruders
+      .iter()
+      .filter(|i| threat.enemy_ids.contains(&i.id))
+      .map(|i| i.position)
+      .collect();
+    threat.center = Position {
+      x: positions.iter().map(|p| p.x).sum::<i32>() / positions.len() as i32,
+      y: positions.iter().map(|p| p.y).sum::<i32>() / positions.len() as i32,
+    };
+  }
+
+  threats
+}
+
+#[cfg(feature = "bwapi")]
+fn release_cleared_threats(frame: i32, player: &Player, state: &mut GameState) {
+  let (cleared, active): (Vec<Threat>, Vec<Threat>) = state
+    .defense
+    .threats
+    .drain(..)
+    .partition(|t| frame - t.last_seen_frame > RELEASE_DELAY_FRAMES);
+  state.defense.threats = active;
+
+  for threat in cleared {
+    log_info!(
+      LogTarget::Defense,
+      "Region {:?} is clear, releasing defenders",
+      threat.region_id
+    );
+
+    if let Some(squad) = state
+      .army
+      .squads
+      .iter_mut()
+      .find(|s| Some(s.id) == threat.squad_id)
+    {
+      squad.mode = SquadMode::Rally;
+      squad.target = None;
+    }
+
+    for worker_id in threat.pulled_worker_ids {
+      state.intended_commands.remove(&worker_id);
+      if let Some(worker) = player
+        .get_units()
+        .into_iter()
+        .find(|u| u.get_id() == worker_id)
+      {
+        // Idle workers are picked up again by the worker manager
+        let result = worker.stop();
+        state.commands.record(
+          CommandRecord::new(worker_id, "Stop", "defense_manager", "threat cleared")
+            .with_result(&result),
+        );
+      }
+    }
+  }
+}
+
+#[cfg(feature = "bwapi")]
+fn assign_squad(our_units: &[Unit], threat_index: usize, state: &mut GameState) {
+  let threat = &state.defense.threats[threat_index];
+  if threat
+    .squad_id
+    .is_some_and(|id| state.army.squads.iter().any(|s| s.id == id))
+  {
+    return;
+  }
+
+  let center = threat.center;
+  let nearest = state
+    .army
+    .squads
+    .iter()
+    .filter(|s| s.mode != SquadMode::Defend && !s.unit_ids.is_empty())
+    .filter_map(|s| {
+      let members: Vec<&Unit> = our_units
+        .iter()
+        .filter(|u| s.unit_ids.contains(&u.get_id()))
+        .collect();
+      army_manager::squad_center(&members).map(|c| (distance(c, center), s.id))
+    })
+    .min_by_key(|(d, _)| *d)
+    .map(|(_, id)| id);
+
+  let Some(squad_id) = nearest else {
+    state.defense.threats[threat_index].squad_id = None;
+    return;
+  };
+
+  log_info!(
+    LogTarget::Defense,
+    "Squad {} defending region {:?}",
+    squad_id,
+    state.defense.threats[threat_index].region_id
+  );
+  if let Some(squad) = state.army.squads.iter_mut().find(|s| s.id == squad_id) {
+    squad.mode = SquadMode::Defend;
+    squad.target = Some(center);
+  }
+  state.defense.threats[threat_index].squad_id = Some(squad_id);
+}
+
+#[cfg(feature = "bwapi")]
+fn command_defenders(
+  our_units: &[Unit],
+  enemies: &[Unit],
+  threat_index: usize,
+  state: &mut GameState,
+) {
+  let threat = &state.defense.threats[threat_index];
+  let Some(squad) = state
+    .army
+    .squads
+    .iter_mut()
+    .find(|s| Some(s.id) == threat.squad_id)
+  else {
+    return;
+  };
+  squad.target = Some(threat.center);
+
+  for unit in our_units
+    .iter()
+    .filter(|u| squad.unit_ids.contains(&u.get_id()))
+  {
+    if unit.is_attacking() || state.army.micro_unit_ids.contains(&unit.get_id()) {
+      continue;
+    }
+    let target = nearest_enemy(enemies, &threat.enemy_ids, unit.get_position());
+    let (result, record) = match target {
+      Some(enemy) => (
+        unit.attack(enemy),
+        CommandRecord::new(
+          unit.get_id(),
+          "Attack",
+          "defense_manager",
+          "defending a base",
+        )
+        .with_target(enemy.get_id()),
+      ),
+      None => (
+        unit.attack(threat.center),
+        CommandRecord::new(
+          unit.get_id(),
+          "Attack",
+          "defense_manager",
+          "defending a base",
+        )
+        .with_target(threat.center),
+      ),
+    };
+    state.commands.record(record.with_result(&result));
+    if let Err(e) = result {
+      log_warn!(
+        LogTarget::Defense,
+        "Defend command FAILED for unit {}: {:?}",
+        unit.get_id(),
+        e
+      );
+    }
+  }
+}
+
+/// Pulls probes into the fight when the squad and cannons can't hold the threat alone.
+#[cfg(feature = "bwapi")]
+fn pull_workers_if_needed(
+  our_units: &[Unit],
+  enemies: &[Unit],
+  threat_index: usize,
+  state: &mut GameState,
+) {
+  let threat = &state.defense.threats[threat_index];
+
+  // Workers whose target died go after the next closest intruder
+  for worker in our_units
+    .iter()
+    .filter(|u| threat.pulled_worker_ids.contains(&u.get_id()) && u.is_idle())
+  {
+    if let Some(enemy) = nearest_enemy(enemies, &threat.enemy_ids, worker.get_position()) {
+      let result = worker.attack(enemy);
+      state.commands.record(
+        CommandRecord::new(
+          worker.get_id(),
+          "Attack",
+          "defense_manager",
+          "pulled worker retargeting",
+        )
+        .with_target(enemy.get_id())
+        .with_result(&result),
+      );
+      if result.is_ok() {
+        state.intended_commands.insert(
+          worker.get_id(),
+          IntendedCommand {
+            order: Order::AttackUnit,
+            target_position: None,
+            target_unit_id: Some(enemy.get_id()),
+          },
+        );
+      }
+    }
+  }
+
+  let squad_strength: f32 = state
+    .army
+    .squads
+    .iter()
+    .find(|s| Some(s.id) == threat.squad_id)
+    .map(|s| {
+      our_units
+        .iter()
+        .filter(|u| s.unit_ids.contains(&u.get_id()))
+        .map(|u| threat_value(u.get_type()))
+        .sum()
+    })
+    .unwrap_or(0.0);
+  let cannon_strength = our_units
+    .iter()
+    .filter(|u| {
+      u.get_type() == UnitType::Protoss_Photon_Cannon
+        && u.is_completed()
+        && distance(u.get_position(), threat.center) <= CANNON_COVERAGE_RANGE
+    })
+    .count() as f32
+    * CANNON_STRENGTH;
+  let pulled_strength = threat.pulled_worker_ids.len() as f32 * WORKER_STRENGTH;
+
+  let missing = threat.strength - squad_strength - cannon_strength - pulled_strength;
+  if missing <= 0.0 {
+    return;
+  }
+
+  let scout_id = state.scouting.scout_id;
+  let mut candidates: Vec<&Unit> = our_units
+    .iter()
+    .filter(|u| {
+      u.get_type().is_worker()
+        && u.is_completed()
+        && Some(u.get_id()) != scout_id
+        && !u.is_constructing()
+        && !threat.pulled_worker_ids.contains(&u.get_id())
+        && state
+          .intended_commands
+          .get(&u.get_id())
+          .is_none_or(|cmd| cmd.order == Order::MiningMinerals)
+        && distance(u.get_position(), threat.center) <= WORKER_PULL_RANGE
+    })
+    .collect();
+  candidates.sort_by_key(|u| distance(u.get_position(), threat.center));
+
+  let needed = (missing / WORKER_STRENGTH).ceil() as usize;
+  let enemy_ids = threat.enemy_ids.clone();
+  let mut pulled = Vec::new();
+  for worker in candidates.into_iter().take(needed) {
+    let Some(enemy) = nearest_enemy(enemies, &enemy_ids, worker.get_position()) else {
+      break;
+    };
+    let result = worker.attack(enemy);
+    state.commands.record(
+      CommandRecord::new(
+        worker.get_id(),
+        "Attack",
+        "defense_manager",
+        "pulling workers to defend",
+      )
+      .with_target(enemy.get_id())
+      .with_result(&result),
+    );
+    if result.is_ok() {
+      state.intended_commands.insert(
+        worker.get_id(),
+        IntendedCommand {
+          order: Order::AttackUnit,
+          target_position: None,
+          target_unit_id: Some(enemy.get_id()),
+        },
+      );
+      pulled.push(worker.get_id());
+    }
+  }
+
+  if !pulled.is_empty() {
+    log_info!(
+      LogTarget::Defense,
+      "Pulling {} workers to defend region {:?}",
+      pulled.len(),
+      state.defense.threats[threat_index].region_id
+    );
+    state.defense.threats[threat_index]
+      .pulled_worker_ids
+      .extend(pulled);
+  }
+}
+
+#[cfg(feature = "bwapi")]
+fn nearest_enemy<'a>(enemies: &'a [Unit], ids: &[usize], position: Position) -> Option<&'a Unit> {
+  enemies
+    .iter()
+    .filter(|u| ids.contains(&u.get_id()))
+    .min_by_key(|u| distance(u.get_position(), position))
+}
+
+fn distance(a: Position, b: Position) -> i32 {
+  (((a.x - b.x).pow(2) + (a.y - b.y).pow(2)) as f32).sqrt() as i32
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const MAIN: usize = 0;
+  const NATURAL: usize = 1;
+  const ELSEWHERE: usize = 2;
+
+  fn intruder(id: usize, unit_type: UnitType, x: i32, region_id: usize) -> Intruder {
+    Intruder {
+      id,
+      unit_type,
+      position: Position { x, y: 320 },
+      region_id: Some(region_id),
+      attacking_base: false,
+    }
+  }
+
+  fn score(intruders: &[Intruder]) -> Vec<Threat> {
+    score_threats(intruders, &HashSet::from([MAIN, NATURAL]), &[], 100)
+  }
+
+  #[test]
+  fn values_units_by_supply() {
+    assert_eq!(threat_value(UnitType::Zerg_Zergling), 0.5);
+    assert_eq!(threat_value(UnitType::Protoss_Zealot), 2.0);
+    assert_eq!(threat_value(UnitType::Protoss_Probe), WORKER_STRENGTH);
+    assert_eq!(
+      threat_value(UnitType::Protoss_Photon_Cannon),
+      CANNON_STRENGTH
+    );
+    assert_eq!(threat_value(UnitType::Zerg_Overlord), 0.0);
+  }
+
+  #[test]
+  fn ignores_a_lone_scouting_worker() {
+    let threats = score(&[intruder(1, UnitType::Protoss_Probe, 320, MAIN)]);
+
+    assert!(threats.is_empty());
+  }
+
+  #[test]
+  fn counts_a_worker_attacking_the_base() {
+    let mut probe = intruder(1, UnitType::Protoss_Probe, 320, MAIN);
+    probe.attacking_base = true;
+
+    let threats = score(&[probe]);
+
+    assert_eq!(threats.len(), 1);
+    assert_eq!(threats[0].strength, WORKER_STRENGTH);
+  }
+
+  #[test]
+  fn counts_a_worker_rush() {
+    let threats = score(&[
+      intruder(1, UnitType::Zerg_Drone, 320, MAIN),
+      intruder(2, UnitType::Zerg_Drone, 352, MAIN),
+      intruder(3, UnitType::Zerg_Drone, 384, MAIN),
+    ]);
+
+    assert_eq!(threats.len(), 1);
+    assert_eq!(threats[0].strength, 3.0 * WORKER_STRENGTH);
+    assert_eq!(threats[0].center, Position { x: 352, y: 320 });
+  }
+
+  #[test]
+  fn groups_enemies_by_region() {
+    let threats = score(&[
+      intruder(1, UnitType::Protoss_Zealot, 320, MAIN),
+      intruder(2, UnitType::Protoss_Probe, 352, MAIN),
+      intruder(3, UnitType::Protoss_Dragoon, 1600, NATURAL),
+    ]);
+
+    assert_eq!(threats.len(), 2);
+    let main = threats.iter().find(|t| t.region_id == Some(MAIN)).unwrap();
+    assert_eq!(main.enemy_ids, vec![1, 2]);
+    assert_eq!(main.strength, 2.0 + WORKER_STRENGTH);
+    assert_eq!(main.last_seen_frame, 100);
+    let natural = threats
+      .iter()
+      .find(|t| t.region_id == Some(NATURAL))
+      .unwrap();
+    assert_eq!(natural.enemy_ids, vec![3]);
+  }
+
+  #[test]
+  fn ignores_enemies_outside_our_regions_unless_a_building_is_hit() {
+    let zealot = intruder(1, UnitType::Protoss_Zealot, 320, ELSEWHERE);
+
+    assert!(score(std::slice::from_ref(&zealot)).is_empty());
+
+    let under_attack = [Position {
+      x: 320 + BUILDING_THREAT_RANGE,
+      y: 320,
+    }];
+    let threats = score_threats(&[zealot], &HashSet::from([MAIN]), &under_attack, 100);
+    assert_eq!(threats.len(), 1);
+    assert_eq!(threats[0].region_id, Some(ELSEWHERE));
+  }
+}
@@ -0,0 +1,416 @@
+#[cfg(feature = "bwapi")]
+use rsbwapi::{Game, Player, Unit};
+use std::collections::HashMap;
+
+use crate::{
+  log_info,
+  logging::LogTarget,
+  state::{
+    build_stages,
+    game_state::{GameState, ObserverPost},
+  },
+  types::{Position, UnitType},
+};
+#[cfg(feature = "bwapi")]
+use crate::{
+  state::game_state::SquadMode,
+  utils::{army_manager, spellcasting},
+};
+
+const ASSIGNMENT_INTERVAL_FRAMES: i32 = 24;
+/// A unit under attack with no known attacker this close may be hit by something cloaked,
+/// a little past siege tank range so a tank at the edge of vision explains the hit
+const INVISIBLE_ATTACKER_RANGE: i32 = 14 * 32;
+/// Hits nobody explains, counted at most once per this many frames
+const UNEXPLAINED_HIT_INTERVAL_FRAMES: i32 = 24;
+/// That many such hits within the window are taken as a cloaked attacker
+const UNEXPLAINED_HITS_NEEDED: usize = 3;
+const UNEXPLAINED_HIT_WINDOW_FRAMES: i32 = 24 * 20;
+
+/// Enemy types that are cloaked or burrowed themselves, or the tech that leads to them
+const CLOAK_WARNING_TYPES: [UnitType; 10] = [
+  UnitType::Protoss_Dark_Templar,
+  UnitType::Protoss_Templar_Archives,
+  UnitType::Protoss_Arbiter,
+  UnitType::Protoss_Arbiter_Tribunal,
+  UnitType::Zerg_Lurker,
+  UnitType::Zerg_Lurker_Egg,
+  UnitType::Terran_Control_Tower,
+  UnitType::Terran_Ghost,
+  UnitType::Terran_Covert_Ops,
+  UnitType::Terran_Vulture_Spider_Mine,
+];
+
+#[cfg(feature = "bwapi")]
+pub fn on_frame(game: &Game, player: &Player, state: &mut GameState) {
+  if state.detection.reason.is_none() {
+    if let Some(reason) = find_cloak_evidence(game, player, state) {
+      let our_types: Vec<UnitType> = player.get_units().iter().map(|u| u.get_type()).collect();
+      queue_detection(state, &our_types, reason);
+    }
+  }
+
+  if game.get_frame_count() % ASSIGNMENT_INTERVAL_FRAMES == 0 {
+    assign_observers(player, state);
+  }
+  move_observers(player, state);
+}
+
+#[cfg(feature = "bwapi")]
+fn find_cloak_evidence(game: &Game, player: &Player, state: &mut GameState) -> Option<String> {
+  if let Some(reason) = remembered_cloak_evidence(state) {
+    return Some(reason);
+  }
+
+  let enemies: Vec<Unit> = game
+    .get_all_units()
+    .into_iter()
+    .filter(|u| u.get_player().is_enemy(player))
+    .collect();
+
+  // BWAPI shows cloaked and burrowed enemies that are in sight, even when undetected
+  if let Some(hidden) = enemies
+    .iter()
+    .find(|u| (u.is_cloaked() || u.is_burrowed()) && !u.is_detected())
+  {
+    return Some(format!("undetected {}", hidden.get_type().name()));
+  }
+
+  let frame = game.get_frame_count();
+  if state
+    .detection
+    .unexplained_hit_frames
+    .last()
+    .is_some_and(|last| frame - last < UNEXPLAINED_HIT_INTERVAL_FRAMES)
+  {
+    return None;
+  }
+
+  // Attackers out of sight, like a sieged tank in the fog, are covered by what we remember
+  let known_attackers: Vec<Position> = enemies
+    .iter()
+    .filter(|e| e.get_type().can_attack())
+    .map(|e| e.get_position())
+    .chain(remembered_attackers(state))
+    .collect();
+  let hit_by_nothing = player.get_units().into_iter().find(|u| {
+    u.is_under_attack()
+      && !known_attackers
+        .iter()
+        .any(|p| distance(*p, u.get_position()) <= INVISIBLE_ATTACKER_RANGE)
+  })?;
+
+  let hits = &mut state.detection.unexplained_hit_frames;
+  if !record_unexplained_hit(hits, frame) {
+    return None;
+  }
+  Some(format!(
+    "{} unexplained hits, last on {}",
+    hits.len(),
+    hit_by_nothing.get_type().name()
+  ))
+}
+
+/// A cloak warning type anywhere in what we have seen of the enemy
+fn remembered_cloak_evidence(state: &GameState) -> Option<String> {
+  state
+    .enemy_memory
+    .units
+    .values()
+    .find(|info| CLOAK_WARNING_TYPES.contains(&info.unit_type))
+    .map(|info| format!("saw {}", info.unit_type.name()))
+}
+
+/// Last positions of remembered enemies that can attack and are still believed there
+fn remembered_attackers(state: &GameState) -> Vec<Position> {
+  state
+    .enemy_memory
+    .units
+    .values()
+    .filter(|info| info.position_known && !info.believed_dead)
+    .filter(|info| info.unit_type.can_attack())
+    .map(|info| info.last_position)
+    .collect()
+}
+
+/// Adds a hit at `frame` and tells whether enough recent ones have piled up
+fn record_unexplained_hit(hits: &mut Vec<i32>, frame: i32) -> bool {
+  hits.retain(|hit| frame - hit < UNEXPLAINED_HIT_WINDOW_FRAMES);
+  hits.push(frame);
+  hits.len() >= UNEXPLAINED_HITS_NEEDED
+}
+
+/// Inserts the detection stage with one more cannon per base, given the types of all
+/// of our units
+fn queue_detection(state: &mut GameState, our_types: &[UnitType], reason: String) {
+  let count = |unit_type: UnitType| our_types.iter().filter(|t| **t == unit_type).count() as i32;
+  let cannons = count(UnitType::Protoss_Photon_Cannon) + count(UnitType::Protoss_Nexus).max(1);
+
+  let stage = build_stages::get_detection_stage(cannons);
+  log_info!(
+    LogTarget::Strategy,
+    "Cloaked threat expected ({}), inserting stage '{}'",
+    reason,
+    stage.name
+  );
+  let index = state.current_stage_index.min(state.build_stages.len());
+  state.build_stages.insert(index, stage);
+  state.detection.reason = Some(reason);
+}
+
+/// Gives every observer a post: active squads first, then each base.
+#[cfg(feature = "bwapi")]
+fn assign_observers(player: &Player, state: &mut GameState) {
+  let units = player.get_units();
+  let observers: Vec<usize> = units
+    .iter()
+    .filter(|u| u.get_type() == UnitType::Protoss_Observer && u.is_completed())
+    .map(|u| u.get_id())
+    .collect();
+
+  let mut posts: Vec<ObserverPost> = state
+    .army
+    .squads
+    .iter()
+    .filter(|s| !s.unit_ids.is_empty())
+    .filter(|s| s.mode != SquadMode::Rally)
+    .map(|s| ObserverPost::Squad(s.id))
+    .collect();
+  if let Some(rally) = state
+    .army
+    .squads
+    .iter()
+    .find(|s| s.mode == SquadMode::Rally && !s.unit_ids.is_empty())
+  {
+    posts.push(ObserverPost::Squad(rally.id));
+  }
+  posts.extend(
+    units
+      .iter()
+      .filter(|u| u.get_type() == UnitType::Protoss_Nexus && u.is_completed())
+      .map(|u| ObserverPost::Base(u.get_position())),
+  );
+
+  let previous = std::mem::take(&mut state.detection.observer_posts);
+  state.detection.observer_posts = assign_posts(previous, &observers, posts);
+}
+
+/// Keeps observers on posts that still exist and hands the open posts, in order, to
+/// observers without one
+fn assign_posts(
+  previous: HashMap<usize, ObserverPost>,
+  observers: &[usize],
+  posts: Vec<ObserverPost>,
+) -> HashMap<usize, ObserverPost> {
+  let mut assigned: HashMap<usize, ObserverPost> = previous
+    .into_iter()
+    .filter(|(id, post)| observers.contains(id) && posts.contains(post))
+    .collect();
+
+  let open_posts: Vec<ObserverPost> = posts
+    .into_iter()
+    .filter(|post| !assigned.values().any(|p| p == post))
+    .collect();
+  let mut open_posts = open_posts.into_iter();
+  for &observer_id in observers {
+    if assigned.contains_key(&observer_id) {
+      continue;
+    }
+    let Some(post) = open_posts.next() else {
+      break;
+    };
+    log_info!(
+      LogTarget::Army,
+      "Observer {} posted to {:?}",
+      observer_id,
+      post
+    );
+    assigned.insert(observer_id, post);
+  }
+  assigned
+}
+
+#[cfg(feature = "bwapi")]
+fn move_observers(player: &Player, state: &mut GameState) {
+  let units = player.get_units();
+
+  for (observer_id, post) in &state.detection.observer_posts {
+    let Some(observer) = units.iter().find(|u| u.get_id() == *observer_id) else {
+      continue;
+    };
+    let target = match post {
+      ObserverPost::Squad(squad_id) => {
+        let Some(squad) = state.army.squads.iter().find(|s| s.id == *squad_id) else {
+          continue;
+        };
+        let members: Vec<&Unit> = units
+          .iter()
+          .filter(|u| squad.unit_ids.contains(&u.get_id()))
+          .collect();
+        let Some(center) = army_manager::squad_center(&members) else {
+          continue;
+        };
+        center
+      }
+      ObserverPost::Base(position) => *position,
+    };
+    spellcasting::follow(
+      observer,
+      target,
+      "detection_manager",
+      "observer post",
+      &mut state.commands,
+    );
+  }
+}
+
+fn distance(a: Position, b: Position) -> i32 {
+  (((a.x - b.x).pow(2) + (a.y - b.y).pow(2)) as f32).sqrt() as i32
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::state::enemy_memory::EnemyUnitInfo;
+
+  fn remember(state: &mut GameState, unit_id: usize, unit_type: UnitType) -> &mut EnemyUnitInfo {
+    state.enemy_memory.units.insert(
+      unit_id,
+      EnemyUnitInfo {
+        unit_id,
+        unit_type,
+        owner_id: 1,
+        last_position: Position {
+          x: unit_id as i32 * 32,
+          y: 0,
+        },
+        first_seen_frame: 0,
+        last_seen_frame: 0,
+        hit_points: 100,
+        shields: 0,
+        is_completed: true,
+        is_visible: false,
+        position_known: true,
+        believed_dead: false,
+      },
+    );
+    state.enemy_memory.units.get_mut(&unit_id).unwrap()
+  }
+
+  #[test]
+  fn remembered_cloak_tech_is_evidence() {
+    let mut state = GameState::default();
+    remember(&mut state, 1, UnitType::Protoss_Zealot);
+    assert_eq!(remembered_cloak_evidence(&state), None);
+
+    remember(&mut state, 2, UnitType::Protoss_Templar_Archives);
+
+    assert_eq!(
+      remembered_cloak_evidence(&state),
+      Some("saw Protoss_Templar_Archives".to_string())
+    );
+  }
+
+  #[test]
+  fn remembered_attackers_skip_dead_moved_and_harmless_units() {
+    let mut state = GameState::default();
+    remember(&mut state, 1, UnitType::Terran_Siege_Tank_Siege_Mode);
+    remember(&mut state, 2, UnitType::Terran_Marine).believed_dead = true;
+    remember(&mut state, 3, UnitType::Terran_Vulture).position_known = false;
+    remember(&mut state, 4, UnitType::Terran_Supply_Depot);
+
+    assert_eq!(remembered_attackers(&state), vec![Position { x: 32, y: 0 }]);
+  }
+
+  #[test]
+  fn detection_adds_a_cannon_per_base() {
+    let mut state = GameState {
+      current_stage_index: 1,
+      ..GameState::default()
+    };
+    let stages = state.build_stages.len();
+
+    queue_detection(
+      &mut state,
+      &[
+        UnitType::Protoss_Nexus,
+        UnitType::Protoss_Nexus,
+        UnitType::Protoss_Photon_Cannon,
+        UnitType::Protoss_Probe,
+      ],
+      "test".to_string(),
+    );
+
+    assert_eq!(state.build_stages.len(), stages + 1);
+    let stage = &state.build_stages[1];
+    assert_eq!(stage.name, "Detection");
+    assert_eq!(stage.desired_counts[&UnitType::Protoss_Photon_Cannon], 3);
+    assert_eq!(state.detection.reason, Some("test".to_string()));
+  }
+
+  #[test]
+  fn detection_wants_a_cannon_without_a_nexus() {
+    let mut state = GameState::default();
+
+    queue_detection(&mut state, &[UnitType::Protoss_Probe], "test".to_string());
+
+    assert_eq!(
+      state.build_stages[0].desired_counts[&UnitType::Protoss_Photon_Cannon],
+      1
+    );
+  }
+
+  #[test]
+  fn observers_keep_their_posts() {
+    let base = ObserverPost::Base(Position { x: 100, y: 100 });
+    let previous = HashMap::from([(2, base), (3, ObserverPost::Squad(9))]);
+
+    let assigned = assign_posts(
+      previous,
+      &[1, 2, 3],
+      vec![ObserverPost::Squad(1), ObserverPost::Squad(2), base],
+    );
+
+    // Observer 2 stays on its base, the gone squad 9 frees observer 3
+    assert_eq!(
+      assigned,
+      HashMap::from([
+        (1, ObserverPost::Squad(1)),
+        (2, base),
+        (3, ObserverPost::Squad(2)),
+      ])
+    );
+  }
+
+  #[test]
+  fn dead_observers_free_their_posts() {
+    let previous = HashMap::from([(5, ObserverPost::Squad(1))]);
+
+    let assigned = assign_posts(previous, &[6], vec![ObserverPost::Squad(1)]);
+
+    assert_eq!(assigned, HashMap::from([(6, ObserverPost::Squad(1))]));
+  }
+
+  #[test]
+  fn one_unexplained_hit_is_not_enough() {
+    let mut hits = Vec::new();
+
+    assert!(!record_unexplained_hit(&mut hits, 1000));
+    assert!(!record_unexplained_hit(&mut hits, 1024));
+    assert!(record_unexplained_hit(&mut hits, 1048));
+  }
+
+  #[test]
+  fn unexplained_hits_expire() {
+    let mut hits = Vec::new();
+
+    record_unexplained_hit(&mut hits, 0);
+    record_unexplained_hit(&mut hits, 24);
+
+    assert!(!record_unexplained_hit(
+      &mut hits,
+      24 + UNEXPLAINED_HIT_WINDOW_FRAMES
+    ));
+    assert_eq!(hits, vec![24 + UNEXPLAINED_HIT_WINDOW_FRAMES]);
+  }
+}
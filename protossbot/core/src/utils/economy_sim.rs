@@ -0,0 +1,637 @@
+//! Headless forward simulation of Protoss macro. The simulator implements `GameWorld`,
+//! so the real build planner makes every decision while the economy (mining, build
+//! times, supply, production queues and tech requirements) is modelled here. Running a
+//! list of build stages reports the frame each item finished, which lets us compare
+//! openings and catch build planner regressions without StarCraft.
+
+use crate::types::{Order, Position, TilePosition, UnitType};
+use std::cell::RefCell;
+
+use crate::{
+  state::{build_stages::BuildStage, game_state::GameState},
+  utils::{
+    build_manager, tech_requirements,
+    terrain_analysis::BaseLocation,
+    worker_management::{self, WORKERS_PER_REFINERY},
+  },
+  world::{GameWorld, UnitInfo},
+};
+
+/// Minerals one worker brings back per frame on a close patch
+const MINERALS_PER_WORKER_FRAME: f32 = 0.045;
+const GAS_PER_WORKER_FRAME: f32 = 0.035;
+/// Two workers per patch on an 8 patch base mine at the full rate, a third adds half
+const FULL_RATE_WORKERS_PER_BASE: usize = 16;
+const HALF_RATE_WORKERS_PER_BASE: usize = 8;
+/// Time for a probe to walk to the build site before the building warps in
+const BUILDER_TRAVEL_FRAMES: i32 = 96;
+const MAX_SUPPLY: i32 = 400;
+
+const START_TILE: TilePosition = TilePosition { x: 10, y: 10 };
+const NATURAL_TILE: TilePosition = TilePosition { x: 30, y: 20 };
+
+#[derive(Clone, Debug)]
+struct SimEntity {
+  id: usize,
+  unit_type: UnitType,
+  tile: TilePosition,
+  /// Frame the unit finished training or warping in
+  complete_frame: i32,
+  /// Building the probe is walking off to place, and the frame it gets there
+  placing: Option<(UnitType, TilePosition, i32)>,
+  /// Unit in production and the frame it pops out
+  training: Option<(UnitType, i32)>,
+  /// Assimilator the worker was sent to, workers without one mine minerals
+  gas_target: Option<usize>,
+}
+
+struct Economy {
+  frame: i32,
+  minerals: f32,
+  gas: f32,
+  entities: Vec<SimEntity>,
+  next_id: usize,
+  /// Finished units waiting to be picked up by the report
+  completed: Vec<(UnitType, i32)>,
+  /// Buildings placed this frame, the build planner is told about them like in a real game
+  placed: Vec<UnitType>,
+}
+
+/// In-memory economy the build planner can play against.
+pub struct EconomySim {
+  economy: RefCell<Economy>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct BuildReport {
+  /// Every unit and building the simulation finished, in completion order
+  pub completions: Vec<(UnitType, i32)>,
+  /// Frame at which the build planner moved past each stage
+  pub stage_frames: Vec<(String, i32)>,
+  /// Frame at which everything in the last stage had finished, if it did
+  pub finished_frame: Option<i32>,
+}
+
+impl BuildReport {
+  /// Frame the `nth` (0 based) unit of `unit_type` finished
+  pub fn completion_frame(&self, unit_type: UnitType, nth: usize) -> Option<i32> {
+    self
+      .completions
+      .iter()
+      .filter(|(t, _)| *t == unit_type)
+      .nth(nth)
+      .map(|(_, frame)| *frame)
+  }
+}
+
+/// Runs the build planner over `stages` from a standard start until the last stage
+/// is finished or `max_frames` have passed.
+pub fn simulate(stages: Vec<BuildStage>, max_frames: i32) -> BuildReport {
+  let sim = EconomySim::new();
+  let mut state = GameState {
+    build_stages: stages,
+    ..GameState::default()
+  };
+  add_expansions(&mut state);
+
+  let mut report = BuildReport::default();
+  let mut stage_index = state.current_stage_index;
+
+  while sim.frame_count() < max_frames {
+    build_manager::on_frame(&sim, &mut state);
+    worker_management::assign_idle_workers_to_minerals(&sim, &mut state);
+    worker_management::assign_workers_to_gas(&sim, &mut state);
+
+    if state.current_stage_index != stage_index {
+      let name = state.build_stages[stage_index].name.clone();
+      report.stage_frames.push((name, sim.frame_count()));
+      stage_index = state.current_stage_index;
+    }
+
+    if is_last_stage_done(&sim, &state) {
+      report.finished_frame = Some(sim.frame_count());
+      break;
+    }
+
+    let (completed, placed) = sim.step();
+    report.completions.extend(completed);
+    for unit_type in placed {
+      build_manager::on_building_create(unit_type, &mut state);
+    }
+  }
+
+  report
+}
+
+/// The planner only places a Nexus on a ranked expansion, so give it a natural to take.
+fn add_expansions(state: &mut GameState) {
+  for (id, tile) in [START_TILE, NATURAL_TILE].into_iter().enumerate() {
+    state.terrain.base_locations.push(BaseLocation {
+      id,
+      tile,
+      region_id: None,
+      minerals: Vec::new(),
+      geysers: Vec::new(),
+      total_minerals: 1500 * 8,
+      total_gas: 5000,
+      is_start_location: id == 0,
+    });
+  }
+  state.expansion.main_base_id = Some(0);
+  state.expansion.ranked_base_ids = vec![1];
+}
+
+fn is_last_stage_done(sim: &EconomySim, state: &GameState) -> bool {
+  if state.current_stage_index + 1 < state.build_stages.len() {
+    return false;
+  }
+  let Some(stage) = state.build_stages.last() else {
+    return true;
+  };
+
+  stage
+    .desired_counts
+    .iter()
+    .all(|(unit_type, &count)| sim.completed_count(*unit_type) >= count)
+}
+
+impl Default for EconomySim {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl EconomySim {
+  /// Standard start: a Nexus, four probes and 50 minerals
+  pub fn new() -> Self {
+    let mut economy = Economy {
+      frame: 0,
+      minerals: 50.0,
+      gas: 0.0,
+      entities: Vec::new(),
+      next_id: 1,
+      completed: Vec::new(),
+      placed: Vec::new(),
+    };
+    economy.spawn(UnitType::Protoss_Nexus, START_TILE, 0);
+    for _ in 0..4 {
+      economy.spawn(UnitType::Protoss_Probe, START_TILE, 0);
+    }
+
+    Self {
+      economy: RefCell::new(economy),
+    }
+  }
+
+  /// Advances one frame. Returns the units that finished and the buildings placed.
+  pub fn step(&self) -> (Vec<(UnitType, i32)>, Vec<UnitType>) {
+    let mut economy = self.economy.borrow_mut();
+    economy.step();
+    (
+      std::mem::take(&mut economy.completed),
+      std::mem::take(&mut economy.placed),
+    )
+  }
+
+  pub fn completed_count(&self, unit_type: UnitType) -> i32 {
+    let economy = self.economy.borrow();
+    economy
+      .entities
+      .iter()
+      .filter(|e| e.unit_type == unit_type && e.complete_frame <= economy.frame)
+      .count() as i32
+  }
+}
+
+impl Economy {
+  fn spawn(&mut self, unit_type: UnitType, tile: TilePosition, complete_frame: i32) {
+    self.entities.push(SimEntity {
+      id: self.next_id,
+      unit_type,
+      tile,
+      complete_frame,
+      placing: None,
+      training: None,
+      gas_target: None,
+    });
+    self.next_id += 1;
+  }
+
+  fn step(&mut self) {
+    self.mine();
+    self.frame += 1;
+    let frame = self.frame;
+
+    let mut new_units = Vec::new();
+    let mut new_buildings = Vec::new();
+    for entity in &mut self.entities {
+      if let Some((unit_type, tile, arrival)) = entity.placing {
+        if arrival <= frame {
+          entity.placing = None;
+          new_buildings.push((unit_type, tile));
+        }
+      }
+      if let Some((unit_type, done)) = entity.training {
+        if done <= frame {
+          entity.training = None;
+          new_units.push((unit_type, entity.tile));
+        }
+      }
+    }
+
+    for (unit_type, tile) in new_units {
+      self.spawn(unit_type, tile, frame);
+    }
+    for (unit_type, tile) in new_buildings {
+      self.spawn(unit_type, tile, frame + unit_type.build_time());
+      self.placed.push(unit_type);
+    }
+
+    let finished = self
+      .entities
+      .iter()
+      .filter(|e| e.complete_frame == frame)
+      .map(|e| (e.unit_type, frame));
+    self.completed.extend(finished);
+  }
+
+  fn mine(&mut self) {
+    let bases = self.completed_of(UnitType::Protoss_Nexus);
+    let workers: Vec<&SimEntity> = self
+      .entities
+      .iter()
+      .filter(|e| e.unit_type.is_worker() && self.is_complete(e) && e.placing.is_none())
+      .collect();
+
+    // Only workers the bot sent to a finished assimilator mine gas, up to saturation
+    let gas_workers: usize = self
+      .entities
+      .iter()
+      .filter(|e| e.unit_type == UnitType::Protoss_Assimilator && self.is_complete(e))
+      .map(|refinery| {
+        workers
+          .iter()
+          .filter(|w| w.gas_target == Some(refinery.id))
+          .count()
+          .min(WORKERS_PER_REFINERY)
+      })
+      .sum();
+    let mineral_workers = workers.iter().filter(|w| w.gas_target.is_none()).count();
+    let full_rate = mineral_workers.min(bases * FULL_RATE_WORKERS_PER_BASE);
+    let half_rate = (mineral_workers - full_rate).min(bases * HALF_RATE_WORKERS_PER_BASE);
+
+    self.minerals += (full_rate as f32 + half_rate as f32 * 0.5) * MINERALS_PER_WORKER_FRAME;
+    self.gas += gas_workers as f32 * GAS_PER_WORKER_FRAME;
+  }
+
+  fn is_complete(&self, entity: &SimEntity) -> bool {
+    entity.complete_frame <= self.frame
+  }
+
+  fn completed_of(&self, unit_type: UnitType) -> usize {
+    self
+      .entities
+      .iter()
+      .filter(|e| e.unit_type == unit_type && self.is_complete(e))
+      .count()
+  }
+
+  fn supply_used(&self) -> i32 {
+    self
+      .entities
+      .iter()
+      .map(|e| {
+        let training = e.training.map(|(t, _)| t.supply_required()).unwrap_or(0);
+        let own = if e.unit_type.is_building() {
+          0
+        } else {
+          e.unit_type.supply_required()
+        };
+        own + training
+      })
+      .sum()
+  }
+
+  fn supply_total(&self) -> i32 {
+    self
+      .entities
+      .iter()
+      .filter(|e| self.is_complete(e))
+      .map(|e| e.unit_type.supply_provided())
+      .sum::<i32>()
+      .min(MAX_SUPPLY)
+  }
+
+  /// Same error names BWAPI reports when a command can't be carried out
+  fn check_cost(&self, unit_type: UnitType) -> Result<(), String> {
+    if (self.minerals as i32) < unit_type.mineral_price() {
+      return Err("Insufficient_Minerals".to_string());
+    }
+    if (self.gas as i32) < unit_type.gas_price() {
+      return Err("Insufficient_Gas".to_string());
+    }
+    if tech_requirements::missing_requirement(unit_type, |t| self.completed_of(t) > 0).is_some() {
+      return Err("Insufficient_Tech".to_string());
+    }
+    Ok(())
+  }
+
+  fn spend(&mut self, unit_type: UnitType) {
+    self.minerals -= unit_type.mineral_price() as f32;
+    self.gas -= unit_type.gas_price() as f32;
+  }
+
+  fn entity(&self, unit_id: usize) -> Result<&SimEntity, String> {
+    self
+      .entities
+      .iter()
+      .find(|e| e.id == unit_id)
+      .ok_or_else(|| format!("unit {} not found", unit_id))
+  }
+
+  fn info(&self, entity: &SimEntity) -> UnitInfo {
+    let complete = self.is_complete(entity);
+    let busy = entity.placing.is_some() || entity.training.is_some();
+    let is_worker = entity.unit_type.is_worker();
+
+    UnitInfo {
+      id: entity.id,
+      unit_type: entity.unit_type,
+      position: Position {
+        x: entity.tile.x * 32 + entity.unit_type.tile_width() * 16,
+        y: entity.tile.y * 32 + entity.unit_type.tile_height() * 16,
+      },
+      tile_position: entity.tile,
+      order: if entity.placing.is_some() {
+        Order::PlaceBuilding
+      } else if is_worker && complete {
+        if entity.gas_target.is_some() {
+          Order::HarvestGas
+        } else {
+          Order::MiningMinerals
+        }
+      } else {
+        Order::None
+      },
+      is_completed: complete,
+      is_idle: complete && !busy && !is_worker,
+      is_constructing: false,
+      is_training: entity.training.is_some(),
+      is_gathering_minerals: complete && !busy && is_worker && entity.gas_target.is_none(),
+      is_gathering_gas: complete && !busy && entity.gas_target.is_some(),
+      is_carrying_minerals: false,
+    }
+  }
+}
+
+impl GameWorld for EconomySim {
+  fn frame_count(&self) -> i32 {
+    self.economy.borrow().frame
+  }
+
+  fn map_size(&self) -> (i32, i32) {
+    (128, 128)
+  }
+
+  fn my_units(&self) -> Vec<UnitInfo> {
+    let economy = self.economy.borrow();
+    economy.entities.iter().map(|e| economy.info(e)).collect()
+  }
+
+  fn minerals(&self) -> i32 {
+    self.economy.borrow().minerals as i32
+  }
+
+  fn gas(&self) -> i32 {
+    self.economy.borrow().gas as i32
+  }
+
+  fn supply_used(&self) -> i32 {
+    self.economy.borrow().supply_used()
+  }
+
+  fn supply_total(&self) -> i32 {
+    self.economy.borrow().supply_total()
+  }
+
+  fn start_location(&self) -> TilePosition {
+    START_TILE
+  }
+
+  fn mineral_fields(&self) -> Vec<UnitInfo> {
+    Vec::new()
+  }
+
+  /// Placement isn't modelled beyond one refinery per base
+  fn can_build_here(&self, _builder_id: usize, _tile: TilePosition, unit_type: UnitType) -> bool {
+    if unit_type != UnitType::Protoss_Assimilator {
+      return true;
+    }
+    let economy = self.economy.borrow();
+    let count = |t: UnitType| economy.entities.iter().filter(|e| e.unit_type == t).count();
+    count(UnitType::Protoss_Assimilator) < count(UnitType::Protoss_Nexus)
+  }
+
+  fn build(&self, unit_id: usize, unit_type: UnitType, tile: TilePosition) -> Result<(), String> {
+    let mut economy = self.economy.borrow_mut();
+    let builder = economy.entity(unit_id)?;
+    if !builder.unit_type.is_worker() || builder.placing.is_some() {
+      return Err("Unit_Busy".to_string());
+    }
+    economy.check_cost(unit_type)?;
+
+    // Resources are taken as soon as the probe is sent so nothing gets spent twice
+    economy.spend(unit_type);
+    let arrival = economy.frame + BUILDER_TRAVEL_FRAMES;
+    if let Some(builder) = economy.entities.iter_mut().find(|e| e.id == unit_id) {
+      builder.placing = Some((unit_type, tile, arrival));
+      // Builders go back to minerals afterwards, like an idle probe in game
+      builder.gas_target = None;
+    }
+    Ok(())
+  }
+
+  fn train(&self, unit_id: usize, unit_type: UnitType) -> Result<(), String> {
+    let mut economy = self.economy.borrow_mut();
+    let trainer = economy.entity(unit_id)?;
+    if trainer.unit_type != unit_type.what_builds().0
+      || trainer.training.is_some()
+      || !economy.is_complete(trainer)
+    {
+      return Err("Unit_Busy".to_string());
+    }
+    economy.check_cost(unit_type)?;
+    if economy.supply_used() + unit_type.supply_required() > economy.supply_total() {
+      return Err("Insufficient_Supply".to_string());
+    }
+
+    economy.spend(unit_type);
+    let done = economy.frame + unit_type.build_time();
+    if let Some(trainer) = economy.entities.iter_mut().find(|e| e.id == unit_id) {
+      trainer.training = Some((unit_type, done));
+    }
+    Ok(())
+  }
+
+  /// Workers sent to an assimilator mine gas from it, anything else is mineral mining
+  fn gather(&self, unit_id: usize, target_id: usize) -> Result<(), String> {
+    let mut economy = self.economy.borrow_mut();
+    if !economy.entity(unit_id)?.unit_type.is_worker() {
+      return Err("Incompatible_UnitType".to_string());
+    }
+    let gas_target = economy
+      .entities
+      .iter()
+      .find(|e| e.id == target_id && e.unit_type == UnitType::Protoss_Assimilator)
+      .map(|e| e.id);
+    if let Some(worker) = economy.entities.iter_mut().find(|e| e.id == unit_id) {
+      worker.gas_target = gas_target;
+    }
+    Ok(())
+  }
+
+  fn draw_text_screen(&self, _x: i32, _y: i32, _text: &str) {}
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::state::build_stages;
+
+  const TEN_MINUTES: i32 = 24 * 60 * 10;
+
+  #[test]
+  fn workers_mine_at_a_steady_rate() {
+    let sim = EconomySim::new();
+
+    for _ in 0..1000 {
+      sim.step();
+    }
+
+    let expected = 50.0 + 4.0 * MINERALS_PER_WORKER_FRAME * 1000.0;
+    assert!((sim.economy.borrow().minerals - expected).abs() < 0.5);
+  }
+
+  #[test]
+  fn only_workers_sent_to_gas_mine_it() {
+    let sim = EconomySim::new();
+    sim
+      .economy
+      .borrow_mut()
+      .spawn(UnitType::Protoss_Assimilator, START_TILE, 0);
+    let assimilator = sim.economy.borrow().next_id - 1;
+
+    for _ in 0..100 {
+      sim.step();
+    }
+    assert_eq!(sim.gas(), 0);
+
+    sim.gather(2, assimilator).unwrap();
+    for _ in 0..100 {
+      sim.step();
+    }
+    let economy = sim.economy.borrow();
+    assert!((economy.gas - 100.0 * GAS_PER_WORKER_FRAME).abs() < 0.01);
+  }
+
+  #[test]
+  fn first_probe_takes_its_build_time() {
+    let stages = vec![BuildStage::new("Probes").with_unit(UnitType::Protoss_Probe, 5)];
+
+    let report = simulate(stages, TEN_MINUTES);
+
+    assert_eq!(
+      report.completion_frame(UnitType::Protoss_Probe, 0),
+      Some(UnitType::Protoss_Probe.build_time())
+    );
+    assert!(report.finished_frame.is_some());
+  }
+
+  #[test]
+  fn pylon_finishes_before_supply_runs_out() {
+    let stages = vec![BuildStage::new("Probes").with_unit(UnitType::Protoss_Probe, 12)];
+
+    let report = simulate(stages, TEN_MINUTES);
+
+    let pylon = report.completion_frame(UnitType::Protoss_Pylon, 0).unwrap();
+    let last_probe = report.completion_frame(UnitType::Protoss_Probe, 7).unwrap();
+    assert!(pylon < last_probe);
+    assert!(report.finished_frame.is_some());
+  }
+
+  #[test]
+  fn buildings_wait_for_their_requirements() {
+    let stages = vec![
+      BuildStage::new("Forge").with_unit(UnitType::Protoss_Forge, 1),
+      BuildStage::new("Cannon").with_unit(UnitType::Protoss_Photon_Cannon, 1),
+    ];
+
+    let report = simulate(stages, TEN_MINUTES);
+
+    let forge = report.completion_frame(UnitType::Protoss_Forge, 0).unwrap();
+    let cannon = report
+      .completion_frame(UnitType::Protoss_Photon_Cannon, 0)
+      .unwrap();
+    assert!(cannon >= forge + UnitType::Protoss_Photon_Cannon.build_time());
+  }
+
+  #[test]
+  fn missing_tech_is_never_built() {
+    let stages = vec![BuildStage::new("Cannon").with_unit(UnitType::Protoss_Photon_Cannon, 1)];
+
+    let report = simulate(stages, 3000);
+
+    assert_eq!(
+      report.completion_frame(UnitType::Protoss_Photon_Cannon, 0),
+      None
+    );
+    assert_eq!(report.finished_frame, None);
+  }
+
+  #[test]
+  fn default_opening_finishes_in_ten_minutes() {
+    let stages = build_stages::get_build_stages();
+    let stage_count = stages.len();
+
+    let report = simulate(stages, TEN_MINUTES);
+
+    assert!(report.finished_frame.is_some(), "{:?}", report.stage_frames);
+    assert_eq!(report.stage_frames.len(), stage_count - 1);
+    assert!(report
+      .completion_frame(UnitType::Protoss_Nexus, 0)
+      .is_some());
+  }
+
+  #[test]
+  fn detection_stage_gets_its_observers() {
+    // Inserted the way the detection manager does once cloak is expected after the opening
+    let mut stages = build_stages::get_build_stages();
+    stages.insert(1, build_stages::get_detection_stage(1));
+    stages.truncate(2);
+
+    let report = simulate(stages, TEN_MINUTES);
+
+    assert!(report.finished_frame.is_some(), "{:?}", report.stage_frames);
+    let assimilator = report
+      .completion_frame(UnitType::Protoss_Assimilator, 0)
+      .unwrap();
+    let observer = report
+      .completion_frame(UnitType::Protoss_Observer, 1)
+      .unwrap();
+    assert!(observer > assimilator);
+  }
+
+  #[test]
+  fn every_strategy_finishes_in_ten_minutes() {
+    for strategy in build_stages::STRATEGIES {
+      let report = simulate(build_stages::get_strategy_stages(strategy), TEN_MINUTES);
+
+      assert!(
+        report.finished_frame.is_some(),
+        "{} stalled: {:?}",
+        strategy,
+        report.stage_frames
+      );
+    }
+  }
+}
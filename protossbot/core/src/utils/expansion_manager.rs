@@ -0,0 +1,385 @@
+#[cfg(feature = "bwapi")]
+use rsbwapi::{Game, Player};
+
+#[cfg(feature = "bwapi")]
+use crate::{log_info, logging::LogTarget, types::UnitType, world::bwapi_world};
+use crate::{
+  state::game_state::GameState,
+  types::{Order, TilePosition},
+  utils::{
+    pathfinding::FlowField,
+    terrain_analysis::{BaseLocation, DEPOT_HEIGHT, DEPOT_WIDTH},
+  },
+  world::UnitInfo,
+};
+
+const WORKERS_PER_MINERAL_PATCH: usize = 2;
+const ENEMY_DISTANCE_WEIGHT: f32 = 0.5;
+const BASE_OCCUPIED_RANGE: i32 = 4;
+const RANKING_INTERVAL_FRAMES: i32 = 24;
+
+#[cfg(feature = "bwapi")]
+pub fn on_frame(game: &Game, player: &Player, state: &mut GameState) {
+  if state.terrain.base_locations.is_empty() {
+    return;
+  }
+
+  if state.expansion.main_base_id.is_none() {
+    initialize_main_base(player, state);
+  }
+
+  if game.get_frame_count() % RANKING_INTERVAL_FRAMES == 0 {
+    let visible_depots: Vec<TilePosition> = game
+      .get_all_units()
+      .iter()
+      .filter(|u| u.get_type().is_resource_depot())
+      .map(|u| u.get_tile_position())
+      .collect();
+    state.expansion.ranked_base_ids = rank_expansions(state, &visible_depots);
+  }
+
+  let units = player.get_units();
+  let nexus_tiles: Vec<TilePosition> = units
+    .iter()
+    .filter(|u| u.get_type() == UnitType::Protoss_Nexus && u.is_completed())
+    .map(|u| u.get_tile_position())
+    .collect();
+  let unit_infos: Vec<UnitInfo> = units.iter().map(bwapi_world::unit_info).collect();
+  let worker_count = mineral_worker_count(state, &unit_infos);
+  let nexus_under_construction = units
+    .iter()
+    .any(|u| u.get_type() == UnitType::Protoss_Nexus && !u.is_completed());
+
+  state.expansion.saturation_requested = is_saturated(state, &nexus_tiles, worker_count)
+    && !is_expansion_in_progress(state, nexus_under_construction);
+}
+
+pub fn next_expansion_tile(state: &GameState) -> Option<TilePosition> {
+  state
+    .expansion
+    .ranked_base_ids
+    .first()
+    .map(|&id| state.terrain.base_locations[id].tile)
+}
+
+#[cfg(feature = "bwapi")]
+fn initialize_main_base(player: &Player, state: &mut GameState) {
+  let start = player.get_start_location();
+  let Some(main_base) = state
+    .terrain
+    .base_locations
+    .iter()
+    .min_by_key(|b| (b.tile.x - start.x).pow(2) + (b.tile.y - start.y).pow(2))
+  else {
+    return;
+  };
+
+  let field = FlowField::towards(&state.terrain.grid, &[main_base.tile]);
+  state.expansion.ground_distance_from_main = state
+    .terrain
+    .base_locations
+    .iter()
+    .map(|b| distance_at(&field, b.tile))
+    .collect();
+  state.expansion.main_base_id = Some(main_base.id);
+
+  log_info!(
+    LogTarget::Game,
+    "Main base is base {} at {:?}",
+    main_base.id,
+    main_base.tile
+  );
+}
+
+/// Untaken, reachable bases ordered by ground distance from our main, pushed back
+/// when they sit close to a possible enemy start location.
+fn rank_expansions(state: &GameState, visible_depots: &[TilePosition]) -> Vec<usize> {
+  let enemy_starts: Vec<TilePosition> = state
+    .enemy_starts
+    .candidates
+    .iter()
+    .filter(|c| c.probability > 0.0)
+    .map(|c| c.tile)
+    .collect();
+
+  let mut scored: Vec<(f32, usize)> = state
+    .terrain
+    .base_locations
+    .iter()
+    .filter(|b| Some(b.id) != state.expansion.main_base_id)
+    .filter(|b| !is_base_taken(state, b, visible_depots))
+    .filter_map(|b| {
+      let ground_distance = state
+        .expansion
+        .ground_distance_from_main
+        .get(b.id)
+        .copied()??;
+      let enemy_distance = enemy_starts
+        .iter()
+        .map(|s| (((s.x - b.tile.x).pow(2) + (s.y - b.tile.y).pow(2)) as f32).sqrt() * 32.0)
+        .fold(f32::MAX, f32::min);
+      let enemy_distance = if enemy_starts.is_empty() {
+        0.0
+      } else {
+        enemy_distance
+      };
+      Some((
+        ground_distance as f32 - ENEMY_DISTANCE_WEIGHT * enemy_distance,
+        b.id,
+      ))
+    })
+    .collect();
+
+  scored.sort_by(|a, b| a.0.total_cmp(&b.0));
+  scored.into_iter().map(|(_, id)| id).collect()
+}
+
+fn is_base_taken(state: &GameState, base: &BaseLocation, visible_depots: &[TilePosition]) -> bool {
+  let is_near_base = |tile: TilePosition| {
+    (tile.x - base.tile.x).abs() <= BASE_OCCUPIED_RANGE
+      && (tile.y - base.tile.y).abs() <= BASE_OCCUPIED_RANGE
+  };
+
+  let visible_depot = visible_depots.iter().any(|tile| is_near_base(*tile));
+  let remembered_enemy_depot = state
+    .enemy_memory
+    .buildings()
+    .any(|info| info.unit_type.is_resource_depot() && is_near_base(info.last_tile()));
+
+  visible_depot || remembered_enemy_depot
+}
+
+/// Workers on the mineral lines: gathering minerals, or idle with nothing planned for
+/// them. Gas miners, builders and scouts don't fill a mineral line.
+fn mineral_worker_count(state: &GameState, units: &[UnitInfo]) -> usize {
+  units
+    .iter()
+    .filter(|u| u.unit_type.is_worker())
+    .filter(|u| {
+      u.is_gathering_minerals || (u.is_idle && !state.intended_commands.contains_key(&u.id))
+    })
+    .count()
+}
+
+fn is_saturated(state: &GameState, nexus_tiles: &[TilePosition], worker_count: usize) -> bool {
+  let mineral_patches: usize = state
+    .terrain
+    .base_locations
+    .iter()
+    .filter(|base| {
+      nexus_tiles.iter().any(|tile| {
+        (tile.x - base.tile.x).abs() <= BASE_OCCUPIED_RANGE
+          && (tile.y - base.tile.y).abs() <= BASE_OCCUPIED_RANGE
+      })
+    })
+    .map(|base| base.minerals.len())
+    .sum();
+
+  mineral_patches > 0 && worker_count >= mineral_patches * WORKERS_PER_MINERAL_PATCH
+}
+
+fn is_expansion_in_progress(state: &GameState, nexus_under_construction: bool) -> bool {
+  let nexus_assigned = next_expansion_tile(state).is_some_and(|tile| {
+    state.intended_commands.values().any(|cmd| {
+      cmd.order == Order::PlaceBuilding && cmd.target_position == Some(tile.to_position())
+    })
+  });
+
+  nexus_under_construction || nexus_assigned
+}
+
+fn distance_at(field: &FlowField, tile: TilePosition) -> Option<i32> {
+  // Depot tiles may start on the edge of walkable terrain, so check the whole footprint
+  (0..DEPOT_HEIGHT)
+    .flat_map(|dy| (0..DEPOT_WIDTH).map(move |dx| (dx, dy)))
+    .filter_map(|(dx, dy)| {
+      field.distance_at(TilePosition {
+        x: tile.x + dx,
+        y: tile.y + dy,
+      })
+    })
+    .min()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::state::{
+    enemy_memory::EnemyUnitInfo,
+    game_state::{IntendedCommand, StartLocationCandidate},
+  };
+  use crate::types::{Position, UnitType};
+  use crate::world::fake_world::fake_unit;
+
+  fn tile(x: i32, y: i32) -> TilePosition {
+    TilePosition { x, y }
+  }
+
+  fn base(id: usize, tile: TilePosition) -> BaseLocation {
+    BaseLocation {
+      id,
+      tile,
+      region_id: None,
+      minerals: (0..8)
+        .map(|y| TilePosition {
+          x: tile.x + 7,
+          y: tile.y + y - 2,
+        })
+        .collect(),
+      geysers: Vec::new(),
+      total_minerals: 8 * 1500,
+      total_gas: 0,
+      is_start_location: false,
+    }
+  }
+
+  /// Main at the origin and expansions along the top edge with the given ground distances
+  fn state_with_bases(distances: &[Option<i32>]) -> GameState {
+    let mut state = GameState::default();
+    state.terrain.base_locations = (0..distances.len())
+      .map(|id| base(id, tile(id as i32 * 20, 0)))
+      .collect();
+    state.expansion.main_base_id = Some(0);
+    state.expansion.ground_distance_from_main = distances.to_vec();
+    state
+  }
+
+  fn enemy_start(tile: TilePosition) -> StartLocationCandidate {
+    StartLocationCandidate {
+      tile,
+      weight: 1.0,
+      probability: 1.0,
+      scouted_empty: false,
+      enemy_confirmed: false,
+      distances: FlowField::default(),
+    }
+  }
+
+  #[test]
+  fn ranks_reachable_bases_by_ground_distance() {
+    let state = state_with_bases(&[Some(0), Some(3000), Some(1000), None]);
+
+    assert_eq!(rank_expansions(&state, &[]), vec![2, 1]);
+  }
+
+  #[test]
+  fn skips_bases_with_a_depot() {
+    let mut state = state_with_bases(&[Some(0), Some(1000), Some(2000), Some(3000)]);
+    state.enemy_memory.units.insert(
+      7,
+      EnemyUnitInfo {
+        unit_id: 7,
+        unit_type: UnitType::Zerg_Hatchery,
+        owner_id: 1,
+        last_position: Position {
+          x: 60 * 32 + 64,
+          y: 48,
+        },
+        first_seen_frame: 0,
+        last_seen_frame: 0,
+        hit_points: 1250,
+        shields: 0,
+        is_completed: true,
+        is_visible: false,
+        position_known: true,
+        believed_dead: false,
+      },
+    );
+
+    assert_eq!(rank_expansions(&state, &[tile(21, 1)]), vec![2]);
+  }
+
+  #[test]
+  fn pushes_back_bases_near_the_enemy() {
+    let mut state = state_with_bases(&[Some(0), Some(1000), Some(1500)]);
+    state.terrain.base_locations[2].tile = tile(100, 0);
+    state.enemy_starts.candidates.push(enemy_start(tile(20, 0)));
+
+    assert_eq!(rank_expansions(&state, &[]), vec![2, 1]);
+  }
+
+  #[test]
+  fn requests_an_expansion_once_mineral_lines_are_saturated() {
+    let state = state_with_bases(&[Some(0), Some(1000)]);
+    let nexus = [tile(0, 0)];
+    let patches = 8;
+
+    assert!(!is_saturated(
+      &state,
+      &nexus,
+      patches * WORKERS_PER_MINERAL_PATCH - 1
+    ));
+    assert!(is_saturated(
+      &state,
+      &nexus,
+      patches * WORKERS_PER_MINERAL_PATCH
+    ));
+    assert!(!is_saturated(&state, &[], 40));
+  }
+
+  #[test]
+  fn gas_and_scout_workers_do_not_saturate_minerals() {
+    let mut state = state_with_bases(&[Some(0), Some(1000)]);
+    let nexus = [tile(0, 0)];
+    let mut probes: Vec<UnitInfo> = (1..=16)
+      .map(|id| fake_unit(id, UnitType::Protoss_Probe, tile(5, 0)))
+      .collect();
+    for probe in &mut probes[..13] {
+      probe.is_idle = false;
+      probe.is_gathering_minerals = true;
+    }
+    for probe in &mut probes[13..15] {
+      probe.is_idle = false;
+      probe.is_gathering_gas = true;
+    }
+    // The scout stands idle for a moment between waypoints
+    state.scouting.scout_id = Some(16);
+    state.intended_commands.insert(
+      16,
+      IntendedCommand {
+        order: Order::Move,
+        target_position: Some(tile(60, 60).to_position()),
+        target_unit_id: None,
+      },
+    );
+
+    let worker_count = mineral_worker_count(&state, &probes);
+
+    assert_eq!(worker_count, 13);
+    assert!(!is_saturated(&state, &nexus, worker_count));
+
+    state.intended_commands.clear();
+    probes.extend((17..=19).map(|id| fake_unit(id, UnitType::Protoss_Probe, tile(5, 0))));
+    let worker_count = mineral_worker_count(&state, &probes);
+    assert_eq!(worker_count, 17);
+    assert!(is_saturated(&state, &nexus, worker_count));
+  }
+
+  #[test]
+  fn counts_every_base_with_a_finished_nexus() {
+    let state = state_with_bases(&[Some(0), Some(1000)]);
+    let nexus = [tile(0, 0), tile(21, 1)];
+
+    assert!(!is_saturated(&state, &nexus, 16));
+    assert!(is_saturated(&state, &nexus, 32));
+  }
+
+  #[test]
+  fn waits_while_an_expansion_is_under_way() {
+    let mut state = state_with_bases(&[Some(0), Some(1000)]);
+    state.expansion.ranked_base_ids = vec![1];
+
+    assert!(!is_expansion_in_progress(&state, false));
+    assert!(is_expansion_in_progress(&state, true));
+
+    state.intended_commands.insert(
+      5,
+      IntendedCommand {
+        order: Order::PlaceBuilding,
+        target_position: Some(tile(20, 0).to_position()),
+        target_unit_id: None,
+      },
+    );
+    assert!(is_expansion_in_progress(&state, false));
+  }
+}
@@ -0,0 +1,324 @@
+//! Per-game summary written when the game ends, one JSON file per game so results can be
+//! aggregated across many games.
+
+#[cfg(feature = "bwapi")]
+use rsbwapi::{Game, Player};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::{
+  log_info, log_warn,
+  logging::{self, LogTarget},
+  state::game_state::GameState,
+  types::UnitType,
+  utils::results_store::{GameResult, ResultsStore, RESULTS_PATH},
+};
+
+/// Income, supply and production are sampled this often, about once per game second
+pub const STATS_INTERVAL_FRAMES: i32 = 24;
+/// The income curve keeps one point per this many frames, about every ten game seconds
+const INCOME_INTERVAL_FRAMES: i32 = 240;
+const MAX_SUPPLY: i32 = 400;
+
+const PRODUCTION_TYPES: [UnitType; 4] = [
+  UnitType::Protoss_Nexus,
+  UnitType::Protoss_Gateway,
+  UnitType::Protoss_Robotics_Facility,
+  UnitType::Protoss_Stargate,
+];
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IncomeSample {
+  pub frame: i32,
+  /// Total gathered so far, not the bank
+  pub minerals: i32,
+  pub gas: i32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StageTimestamp {
+  pub frame: i32,
+  pub name: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SupplyBlock {
+  pub start_frame: i32,
+  pub end_frame: i32,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GameStats {
+  pub map: String,
+  pub opponent: String,
+  pub opponent_race: String,
+  /// Build the bot played this game
+  pub strategy: String,
+  /// Unix time the game started at, also part of the log and stats file names
+  pub started_at: u64,
+  /// `None` until the game ends
+  pub won: Option<bool>,
+  pub duration_frames: i32,
+  pub income: Vec<IncomeSample>,
+  pub units_produced: BTreeMap<String, u32>,
+  pub units_lost: BTreeMap<String, u32>,
+  pub stages: Vec<StageTimestamp>,
+  pub supply_blocks: Vec<SupplyBlock>,
+  pub supply_blocked_frames: i32,
+  /// Frames production buildings spent with an empty queue, summed per building type
+  pub idle_production_frames: BTreeMap<String, i32>,
+  #[serde(skip)]
+  supply_block_start: Option<i32>,
+}
+
+impl GameStats {
+  pub fn start(
+    &mut self,
+    map: &str,
+    (opponent, opponent_race): (&str, &str),
+    strategy: &str,
+    started_at: u64,
+  ) {
+    *self = Self {
+      map: map.to_string(),
+      opponent: opponent.to_string(),
+      opponent_race: opponent_race.to_string(),
+      strategy: strategy.to_string(),
+      started_at,
+      ..Self::default()
+    };
+  }
+
+  pub fn record_income(&mut self, frame: i32, minerals: i32, gas: i32) {
+    self.income.push(IncomeSample {
+      frame,
+      minerals,
+      gas,
+    });
+  }
+
+  pub fn record_stage(&mut self, frame: i32, name: &str) {
+    if self.stages.last().is_some_and(|s| s.name == name) {
+      return;
+    }
+    self.stages.push(StageTimestamp {
+      frame,
+      name: name.to_string(),
+    });
+  }
+
+  pub fn update_supply(&mut self, frame: i32, used: i32, total: i32) {
+    let blocked = used >= total && total < MAX_SUPPLY;
+    match (blocked, self.supply_block_start) {
+      (true, None) => self.supply_block_start = Some(frame),
+      (false, Some(_)) => self.end_supply_block(frame),
+      _ => {}
+    }
+  }
+
+  fn end_supply_block(&mut self, frame: i32) {
+    let Some(start_frame) = self.supply_block_start.take() else {
+      return;
+    };
+    self.supply_blocked_frames += frame - start_frame;
+    self.supply_blocks.push(SupplyBlock {
+      start_frame,
+      end_frame: frame,
+    });
+  }
+
+  pub fn add_idle_production(&mut self, unit_type: UnitType, frames: i32) {
+    *self
+      .idle_production_frames
+      .entry(unit_type.name().to_string())
+      .or_default() += frames;
+  }
+
+  pub fn unit_produced(&mut self, unit_type: UnitType) {
+    *self
+      .units_produced
+      .entry(unit_type.name().to_string())
+      .or_default() += 1;
+  }
+
+  pub fn unit_lost(&mut self, unit_type: UnitType) {
+    *self
+      .units_lost
+      .entry(unit_type.name().to_string())
+      .or_default() += 1;
+  }
+
+  /// Closes an open supply block and records the result
+  pub fn finish(&mut self, frame: i32, won: bool) {
+    self.end_supply_block(frame);
+    self.duration_frames = frame;
+    self.won = Some(won);
+  }
+
+  pub fn to_json(&self) -> Result<String, String> {
+    serde_json::to_string_pretty(self).map_err(|e| e.to_string())
+  }
+
+  pub fn save(&self, path: &str) -> Result<(), String> {
+    std::fs::write(path, self.to_json()?).map_err(|e| e.to_string())
+  }
+}
+
+/// Name and race of the opponent, "Unknown" for both when there is no enemy player
+#[cfg(feature = "bwapi")]
+pub fn opponent(game: &Game) -> (String, String) {
+  game.enemy().map_or_else(
+    || ("Unknown".to_string(), "Unknown".to_string()),
+    |enemy| (enemy.get_name(), format!("{:?}", enemy.get_race())),
+  )
+}
+
+/// Call once the strategy for the game has been chosen
+#[cfg(feature = "bwapi")]
+pub fn on_start(game: &Game, state: &mut GameState, started_at: u64) {
+  let (opponent, race) = opponent(game);
+  let strategy = state.strategy;
+  state.stats.start(
+    &game.map_file_name(),
+    (&opponent, &race),
+    strategy,
+    started_at,
+  );
+}
+
+#[cfg(feature = "bwapi")]
+pub fn on_frame(game: &Game, player: &Player, state: &mut GameState) {
+  let frame = game.get_frame_count();
+  if let Some(stage) = state.build_stages.get(state.current_stage_index) {
+    state.stats.record_stage(frame, &stage.name);
+  }
+
+  if frame % STATS_INTERVAL_FRAMES != 0 {
+    return;
+  }
+  if frame % INCOME_INTERVAL_FRAMES == 0 {
+    state
+      .stats
+      .record_income(frame, player.gathered_minerals(), player.gathered_gas());
+  }
+  state
+    .stats
+    .update_supply(frame, player.supply_used(), player.supply_total());
+
+  for unit in player.get_units() {
+    if PRODUCTION_TYPES.contains(&unit.get_type()) && unit.is_completed() && !unit.is_training() {
+      state
+        .stats
+        .add_idle_production(unit.get_type(), STATS_INTERVAL_FRAMES);
+    }
+  }
+}
+
+/// Writes the summary next to the game log and adds the result to the results store.
+/// Tournaments only get the result.
+pub fn on_end(frame: i32, is_winner: bool, state: &mut GameState) {
+  state.stats.finish(frame, is_winner);
+
+  if state.data.mode.writes_debug_files() {
+    write_report(&state.stats);
+  }
+
+  let results_path = state.data.learning_path(RESULTS_PATH);
+  let result = GameResult::from_stats(&state.stats);
+  if let Err(e) = ResultsStore::new(&results_path).append(&result) {
+    log_warn!(
+      LogTarget::Game,
+      "Could not append the result to {}: {}",
+      results_path,
+      e
+    );
+  }
+}
+
+fn write_report(stats: &GameStats) {
+  let path = logging::game_file_path(&stats.map, stats.started_at, "stats.json");
+  match stats.save(&path) {
+    Ok(()) => log_info!(LogTarget::Game, "Game stats written to {}", path),
+    Err(e) => log_warn!(
+      LogTarget::Game,
+      "Could not write game stats to {}: {}",
+      path,
+      e
+    ),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn measures_supply_blocks() {
+    let mut stats = GameStats::default();
+
+    stats.update_supply(100, 18, 18);
+    stats.update_supply(124, 18, 18);
+    stats.update_supply(148, 18, 34);
+    stats.update_supply(500, 400, 400);
+    stats.finish(600, true);
+
+    assert_eq!(
+      stats.supply_blocks,
+      vec![SupplyBlock {
+        start_frame: 100,
+        end_frame: 148
+      }]
+    );
+    assert_eq!(stats.supply_blocked_frames, 48);
+  }
+
+  #[test]
+  fn closes_a_supply_block_at_the_end_of_the_game() {
+    let mut stats = GameStats::default();
+
+    stats.update_supply(1000, 40, 40);
+    stats.finish(1200, false);
+
+    assert_eq!(stats.supply_blocked_frames, 200);
+    assert_eq!(stats.won, Some(false));
+  }
+
+  #[test]
+  fn records_each_stage_once() {
+    let mut stats = GameStats::default();
+
+    stats.record_stage(0, "Opening");
+    stats.record_stage(1, "Opening");
+    stats.record_stage(2000, "Midgame");
+
+    let names: Vec<&str> = stats.stages.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["Opening", "Midgame"]);
+    assert_eq!(stats.stages[1].frame, 2000);
+  }
+
+  #[test]
+  fn counts_units_by_type_in_json() {
+    let mut stats = GameStats::default();
+    stats.start(
+      "(2)Destination.scx",
+      ("UAlbertaBot", "Zerg"),
+      "cannon_expand",
+      1_700_000_000,
+    );
+
+    stats.unit_produced(UnitType::Protoss_Zealot);
+    stats.unit_produced(UnitType::Protoss_Zealot);
+    stats.unit_lost(UnitType::Protoss_Probe);
+    stats.add_idle_production(UnitType::Protoss_Gateway, 24);
+    stats.add_idle_production(UnitType::Protoss_Gateway, 24);
+    stats.finish(24 * 60 * 10, true);
+
+    let json: serde_json::Value = serde_json::from_str(&stats.to_json().unwrap()).unwrap();
+    assert_eq!(json["map"], "(2)Destination.scx");
+    assert_eq!(json["won"], true);
+    assert_eq!(json["units_produced"]["Protoss_Zealot"], 2);
+    assert_eq!(json["units_lost"]["Protoss_Probe"], 1);
+    assert_eq!(json["idle_production_frames"]["Protoss_Gateway"], 48);
+    assert!(json.get("supply_block_start").is_none());
+  }
+}
@@ -0,0 +1,298 @@
+use rsbwapi::{Game, Unit, WeaponType};
+
+use crate::{
+  log_debug, log_warn,
+  logging::LogTarget,
+  state::game_state::GameState,
+  types::{Order, Position, UnitType},
+  utils::{
+    command_recorder::{CommandRecord, CommandRecorder},
+    target_priority::TargetPriorityTable,
+  },
+};
+
+/// Enemies further away than this are left to the squad's attack-move
+const ENGAGE_RANGE: i32 = 10 * 32;
+/// Ground weapons this short are melee
+const MELEE_RANGE: i32 = 32;
+/// Dragoons step back when a melee unit gets this close while reloading
+const KITE_TRIGGER_RANGE: i32 = 3 * 32;
+const KITE_STEP: i32 = 2 * 32;
+const ZEALOT_TARGET_RANGE: i32 = 4 * 32;
+/// Fraction of max shields below which a damaged unit goes back to recharge
+const RECHARGE_START_SHIELDS: f32 = 0.1;
+const RECHARGE_DONE_SHIELDS: f32 = 0.6;
+/// Units above this fraction of max hit points keep fighting without shields
+const DAMAGED_HIT_POINTS: f32 = 0.7;
+const HOME_RANGE: i32 = 6 * 32;
+
+/// Runs per-unit micro for one combat unit. Returns true when micro issued an order
+/// for the unit this frame (or is deliberately holding it), so squad orders must
+/// leave it alone.
+pub fn control_unit(
+  game: &Game,
+  unit: &Unit,
+  enemies: &[Unit],
+  home: Option<Position>,
+  state: &mut GameState,
+) -> bool {
+  let position = unit.get_position();
+  let nearby: Vec<&Unit> = enemies
+    .iter()
+    .filter(|e| can_target(unit, e) && distance(e.get_position(), position) <= ENGAGE_RANGE)
+    .collect();
+
+  if recharge_shields(unit, &nearby, home, state) {
+    return true;
+  }
+  if nearby.is_empty() {
+    return false;
+  }
+
+  let priorities = &state.target_priorities;
+  let commands = &mut state.commands;
+  match unit.get_type() {
+    UnitType::Protoss_Dragoon => control_dragoon(game, unit, &nearby, priorities, commands),
+    UnitType::Protoss_Zealot => control_zealot(unit, &nearby, priorities, commands),
+    _ => false,
+  }
+}
+
+/// Sends units with depleted shields and damaged hit points home until their shields
+/// come back, letting them fight again if the enemy follows them there.
+fn recharge_shields(
+  unit: &Unit,
+  nearby: &[&Unit],
+  home: Option<Position>,
+  state: &mut GameState,
+) -> bool {
+  let unit_type = unit.get_type();
+  if unit_type.max_shields() == 0 {
+    return false;
+  }
+  let Some(home) = home else {
+    return false;
+  };
+
+  let unit_id = unit.get_id();
+  let shields = unit.get_shields() as f32 / unit_type.max_shields() as f32;
+  let hit_points = unit.get_hit_points() as f32 / unit_type.max_hit_points() as f32;
+  let recharging = state.army.recharging_ids.contains(&unit_id);
+
+  if recharging && shields >= RECHARGE_DONE_SHIELDS {
+    log_debug!(
+      LogTarget::Micro,
+      "Unit {} recharged, rejoining squad",
+      unit_id
+    );
+    state.army.recharging_ids.remove(&unit_id);
+    return false;
+  }
+  if !recharging && (shields > RECHARGE_START_SHIELDS || hit_points >= DAMAGED_HIT_POINTS) {
+    return false;
+  }
+  if !recharging {
+    log_debug!(
+      LogTarget::Micro,
+      "Unit {} pulling back to recharge shields",
+      unit_id
+    );
+    state.army.recharging_ids.insert(unit_id);
+  }
+
+  if distance(unit.get_position(), home) <= HOME_RANGE {
+    // Nowhere left to run, defend home like everybody else
+    return nearby.is_empty();
+  }
+
+  if unit.get_order() != Order::Move || unit.is_idle() {
+    let result = unit.move_(home);
+    state.commands.record(
+      CommandRecord::new(unit_id, "Move", "micro", "recharging shields")
+        .with_target(home)
+        .with_result(&result),
+    );
+    if let Err(e) = result {
+      log_warn!(
+        LogTarget::Micro,
+        "Recharge move FAILED for unit {}: {:?}",
+        unit_id,
+        e
+      );
+    }
+  }
+  true
+}
+
+/// Shoots whenever the weapon is ready and steps away from melee units while it reloads.
+fn control_dragoon(
+  game: &Game,
+  unit: &Unit,
+  nearby: &[&Unit],
+  priorities: &TargetPriorityTable,
+  commands: &mut CommandRecorder,
+) -> bool {
+  let position = unit.get_position();
+
+  if unit.get_ground_weapon_cooldown() > 0 {
+    // Leaving before the shot is fired cancels it
+    if unit.is_attack_frame() {
+      return true;
+    }
+
+    let closest_melee = nearby
+      .iter()
+      .filter(|e| is_melee(e.get_type()))
+      .min_by_key(|e| distance(e.get_position(), position));
+    if let Some(melee) = closest_melee {
+      if distance(melee.get_position(), position) <= KITE_TRIGGER_RANGE {
+        let away = step_away(game, position, melee.get_position(), KITE_STEP);
+        // Still stepping back towards about the same spot, re-issuing would only spam orders
+        let already_kiting = unit.get_order() == Order::Move
+          && !unit.is_idle()
+          && unit
+            .get_target_position()
+            .is_some_and(|current| distance(current, away) <= KITE_STEP);
+        if already_kiting {
+          return true;
+        }
+        let result = unit.move_(away);
+        commands.record(
+          CommandRecord::new(
+            unit.get_id(),
+            "Move",
+            "micro",
+            "kiting melee while reloading",
+          )
+          .with_target(away)
+          .with_result(&result),
+        );
+        if let Err(e) = result {
+          log_warn!(
+            LogTarget::Micro,
+            "Kite move FAILED for unit {}: {:?}",
+            unit.get_id(),
+            e
+          );
+        }
+        return true;
+      }
+    }
+    return false;
+  }
+
+  let in_range = nearby.iter().filter(|e| unit.is_in_weapon_range(e));
+  let Some(target) = best_target(unit, in_range, priorities) else {
+    return false;
+  };
+  attack_if_new_target(unit, target, "best target in range", commands)
+}
+
+/// Goes for the highest priority target within reach instead of the closest.
+fn control_zealot(
+  unit: &Unit,
+  nearby: &[&Unit],
+  priorities: &TargetPriorityTable,
+  commands: &mut CommandRecorder,
+) -> bool {
+  let position = unit.get_position();
+  let Some(target) = best_target(
+    nearby
+      .iter()
+      .filter(|e| distance(e.get_position(), position) <= ZEALOT_TARGET_RANGE),
+    priorities,
+  ) else {
+    return false;
+  };
+  attack_if_new_target(unit, target, "best target within reach", commands)
+}
+
+/// Picks by priority table score first, then the most valuable, most damaged target.
+fn best_target<'a>(
+  attacker: &Unit,
+  candidates: impl Iterator<Item = &'a &'a Unit>,
+  priorities: &TargetPriorityTable,
+) -> Option<&'a Unit> {
+  let attacker_name = attacker.get_type().name();
+  candidates
+    .copied()
+    .map(|e| {
+      (
+        priorities.score(attacker_name, e.get_type().name()),
+        finish_value(e),
+        e,
+      )
+    })
+    .max_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)))
+    .map(|(_, _, e)| e)
+}
+
+/// Higher for expensive units that are close to dying
+fn finish_value(enemy: &Unit) -> f32 {
+  let unit_type = enemy.get_type();
+  let value = (unit_type.mineral_price() + unit_type.gas_price()) as f32 + 25.0;
+  let remaining = (enemy.get_hit_points() + enemy.get_shields()).max(1) as f32;
+  value / remaining
+}
+
+fn attack_if_new_target(
+  unit: &Unit,
+  target: &Unit,
+  reason: &str,
+  commands: &mut CommandRecorder,
+) -> bool {
+  let already_targeted = unit
+    .get_target()
+    .is_some_and(|current| current.get_id() == target.get_id());
+  if !already_targeted {
+    let result = unit.attack(target);
+    commands.record(
+      CommandRecord::new(unit.get_id(), "Attack", "micro", reason)
+        .with_target(target.get_id())
+        .with_result(&result),
+    );
+    if let Err(e) = result {
+      log_warn!(
+        LogTarget::Micro,
+        "Micro attack FAILED for unit {}: {:?}",
+        unit.get_id(),
+        e
+      );
+    }
+  }
+  true
+}
+
+fn can_target(unit: &Unit, enemy: &Unit) -> bool {
+  let unit_type = unit.get_type();
+  let enemy_type = enemy.get_type();
+  if enemy.is_cloaked() && !enemy.is_detected() {
+    return false;
+  }
+  if enemy_type.is_flyer() {
+    unit_type.air_weapon() != WeaponType::None
+  } else {
+    unit_type.ground_weapon() != WeaponType::None
+  }
+}
+
+fn is_melee(unit_type: UnitType) -> bool {
+  unit_type.can_attack()
+    && !unit_type.is_building()
+    && unit_type.ground_weapon() != WeaponType::None
+    && unit_type.ground_weapon().max_range() <= MELEE_RANGE
+}
+
+fn step_away(game: &Game, from: Position, threat: Position, step: i32) -> Position {
+  let dx = (from.x - threat.x) as f32;
+  let dy = (from.y - threat.y) as f32;
+  let length = (dx * dx + dy * dy).sqrt().max(1.0);
+  Position {
+    x: (from.x + (dx / length * step as f32) as i32).clamp(0, game.map_width() * 32 - 1),
+    y: (from.y + (dy / length * step as f32) as i32).clamp(0, game.map_height() * 32 - 1),
+  }
+}
+
+fn distance(a: Position, b: Position) -> i32 {
+  (((a.x - b.x).pow(2) + (a.y - b.y).pow(2)) as f32).sqrt() as i32
+}
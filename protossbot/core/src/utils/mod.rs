@@ -0,0 +1,24 @@
+#[cfg(feature = "bwapi")]
+pub mod army_manager;
+pub mod build_location_utils;
+pub mod build_manager;
+pub mod combat_sim;
+pub mod command_recorder;
+pub mod defense_manager;
+pub mod detection_manager;
+pub mod economy_sim;
+pub mod expansion_manager;
+pub mod game_stats;
+#[cfg(feature = "bwapi")]
+pub mod micro;
+pub mod pathfinding;
+pub mod results_store;
+pub mod scouting_manager;
+pub mod spellcasting;
+pub mod start_location_inference;
+pub mod strategy_recognition;
+pub mod strategy_selection;
+pub mod target_priority;
+pub mod tech_requirements;
+pub mod terrain_analysis;
+pub mod worker_management;
@@ -0,0 +1,291 @@
+use crate::types::TilePosition;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::utils::terrain_analysis::MapGrid;
+
+const STRAIGHT_COST: i32 = 10;
+const DIAGONAL_COST: i32 = 14;
+const TILE_SIZE: i32 = 32;
+
+const STEPS: [(i32, i32, i32); 8] = [
+  (1, 0, STRAIGHT_COST),
+  (-1, 0, STRAIGHT_COST),
+  (0, 1, STRAIGHT_COST),
+  (0, -1, STRAIGHT_COST),
+  (1, 1, DIAGONAL_COST),
+  (1, -1, DIAGONAL_COST),
+  (-1, 1, DIAGONAL_COST),
+  (-1, -1, DIAGONAL_COST),
+];
+
+#[derive(Clone, Debug)]
+pub struct Path {
+  pub tiles: Vec<TilePosition>,
+  /// Ground length of the path in pixels
+  pub length: i32,
+}
+
+/// Ground distances from every walkable tile to the nearest of a set of goals.
+/// Units anywhere on the map can follow `next_step` downhill to reach a goal.
+#[derive(Clone, Debug, Default)]
+pub struct FlowField {
+  width: i32,
+  height: i32,
+  costs: Vec<i32>,
+}
+
+impl FlowField {
+  pub fn towards(grid: &MapGrid, goals: &[TilePosition]) -> Self {
+    let mut costs = vec![i32::MAX; (grid.width * grid.height) as usize];
+    let mut open = BinaryHeap::new();
+
+    for goal in goals {
+      if let Some(index) = walkable_index(grid, goal.x, goal.y) {
+        costs[index] = 0;
+        open.push(Reverse((0, goal.x, goal.y)));
+      }
+    }
+
+    while let Some(Reverse((cost, x, y))) = open.pop() {
+      if cost > costs[(y * grid.width + x) as usize] {
+        continue;
+      }
+      for (nx, ny, step_cost) in neighbors(grid, x, y) {
+        let index = (ny * grid.width + nx) as usize;
+        let next_cost = cost + step_cost;
+        if next_cost < costs[index] {
+          costs[index] = next_cost;
+          open.push(Reverse((next_cost, nx, ny)));
+        }
+      }
+    }
+
+    Self {
+      width: grid.width,
+      height: grid.height,
+      costs,
+    }
+  }
+
+  /// Ground distance in pixels from `tile` to the closest goal
+  pub fn distance_at(&self, tile: TilePosition) -> Option<i32> {
+    self
+      .cost_at(tile.x, tile.y)
+      .map(|cost| cost * TILE_SIZE / STRAIGHT_COST)
+  }
+
+  /// The neighboring tile one step closer to a goal
+  pub fn next_step(&self, tile: TilePosition) -> Option<TilePosition> {
+    let current = self.cost_at(tile.x, tile.y)?;
+    STEPS
+      .iter()
+      .filter_map(|&(dx, dy, _)| {
+        let cost = self.cost_at(tile.x + dx, tile.y + dy)?;
+        (cost < current).then_some((
+          cost,
+          TilePosition {
+            x: tile.x + dx,
+            y: tile.y + dy,
+          },
+        ))
+      })
+      .min_by_key(|(cost, _)| *cost)
+      .map(|(_, next)| next)
+  }
+
+  fn cost_at(&self, x: i32, y: i32) -> Option<i32> {
+    if x < 0 || y < 0 || x >= self.width || y >= self.height {
+      return None;
+    }
+    let cost = self.costs[(y * self.width + x) as usize];
+    (cost != i32::MAX).then_some(cost)
+  }
+}
+
+/// A* over walkable tiles with octile steps; diagonal moves may not cut corners.
+pub fn find_path(grid: &MapGrid, start: TilePosition, goal: TilePosition) -> Option<Path> {
+  let start_index = walkable_index(grid, start.x, start.y)?;
+  let goal_index = walkable_index(grid, goal.x, goal.y)?;
+
+  let mut costs = vec![i32::MAX; (grid.width * grid.height) as usize];
+  let mut came_from: Vec<Option<usize>> = vec![None; costs.len()];
+  let mut open = BinaryHeap::new();
+
+  costs[start_index] = 0;
+  open.push(Reverse((
+    octile_heuristic(start, goal),
+    0,
+    start.x,
+    start.y,
+  )));
+
+  while let Some(Reverse((_, cost, x, y))) = open.pop() {
+    let index = (y * grid.width + x) as usize;
+    if index == goal_index {
+      return Some(Path {
+        tiles: reconstruct(grid, &came_from, goal_index),
+        length: cost * TILE_SIZE / STRAIGHT_COST,
+      });
+    }
+    if cost > costs[index] {
+      continue;
+    }
+
+    for (nx, ny, step_cost) in neighbors(grid, x, y) {
+      let next_index = (ny * grid.width + nx) as usize;
+      let next_cost = cost + step_cost;
+      if next_cost < costs[next_index] {
+        costs[next_index] = next_cost;
+        came_from[next_index] = Some(index);
+        let estimate = next_cost + octile_heuristic(TilePosition { x: nx, y: ny }, goal);
+        open.push(Reverse((estimate, next_cost, nx, ny)));
+      }
+    }
+  }
+
+  None
+}
+
+/// Ground distance in pixels between two tiles, or `None` when unreachable
+pub fn ground_distance(grid: &MapGrid, start: TilePosition, goal: TilePosition) -> Option<i32> {
+  find_path(grid, start, goal).map(|path| path.length)
+}
+
+fn walkable_index(grid: &MapGrid, x: i32, y: i32) -> Option<usize> {
+  grid
+    .is_walkable(x, y)
+    .then_some((y * grid.width + x) as usize)
+}
+
+fn neighbors(grid: &MapGrid, x: i32, y: i32) -> impl Iterator<Item = (i32, i32, i32)> + '_ {
+  STEPS.iter().filter_map(move |&(dx, dy, cost)| {
+    let (nx, ny) = (x + dx, y + dy);
+    if !grid.is_walkable(nx, ny) {
+      return None;
+    }
+    if dx != 0 && dy != 0 && !(grid.is_walkable(x + dx, y) && grid.is_walkable(x, y + dy)) {
+      return None;
+    }
+    Some((nx, ny, cost))
+  })
+}
+
+fn octile_heuristic(from: TilePosition, to: TilePosition) -> i32 {
+  let dx = (from.x - to.x).abs();
+  let dy = (from.y - to.y).abs();
+  STRAIGHT_COST * dx.max(dy) + (DIAGONAL_COST - STRAIGHT_COST) * dx.min(dy)
+}
+
+fn reconstruct(
+  grid: &MapGrid,
+  came_from: &[Option<usize>],
+  goal_index: usize,
+) -> Vec<TilePosition> {
+  let mut tiles = Vec::new();
+  let mut current = Some(goal_index);
+  while let Some(index) = current {
+    tiles.push(TilePosition {
+      x: index as i32 % grid.width,
+      y: index as i32 / grid.width,
+    });
+    current = came_from[index];
+  }
+  tiles.reverse();
+  tiles
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn grid_from_rows(rows: &[&str]) -> MapGrid {
+    let walkable: Vec<bool> = rows
+      .iter()
+      .flat_map(|row| row.chars().map(|c| c != '#'))
+      .collect();
+    MapGrid {
+      width: rows[0].len() as i32,
+      height: rows.len() as i32,
+      buildable: walkable.clone(),
+      walkable,
+    }
+  }
+
+  fn tile(x: i32, y: i32) -> TilePosition {
+    TilePosition { x, y }
+  }
+
+  #[test]
+  fn straight_line_distance_on_open_grid() {
+    let grid = grid_from_rows(&["......", "......", "......"]);
+
+    let path = find_path(&grid, tile(0, 1), tile(5, 1)).unwrap();
+
+    assert_eq!(path.tiles.first(), Some(&tile(0, 1)));
+    assert_eq!(path.tiles.last(), Some(&tile(5, 1)));
+    assert_eq!(path.length, 5 * TILE_SIZE);
+  }
+
+  #[test]
+  fn path_goes_around_walls() {
+    let grid = grid_from_rows(&["........", ".######.", "......#.", "......#."]);
+
+    let straight = 5 * TILE_SIZE;
+    let distance = ground_distance(&grid, tile(3, 2), tile(7, 3)).unwrap();
+
+    assert!(
+      distance > straight,
+      "{} should exceed {}",
+      distance,
+      straight
+    );
+  }
+
+  #[test]
+  fn unreachable_goal_has_no_path() {
+    let grid = grid_from_rows(&["..#..", "..#..", "..#.."]);
+
+    assert!(find_path(&grid, tile(0, 0), tile(4, 2)).is_none());
+    assert!(FlowField::towards(&grid, &[tile(4, 2)])
+      .distance_at(tile(0, 0))
+      .is_none());
+  }
+
+  #[test]
+  fn diagonal_moves_do_not_cut_corners() {
+    let grid = grid_from_rows(&[".#", ".."]);
+
+    let path = find_path(&grid, tile(0, 0), tile(1, 1)).unwrap();
+
+    assert_eq!(path.tiles, vec![tile(0, 0), tile(0, 1), tile(1, 1)]);
+  }
+
+  #[test]
+  fn flow_field_matches_a_star_and_leads_to_goal() {
+    let grid = grid_from_rows(&[
+      "..........",
+      "...####...",
+      "......#...",
+      "..#...#...",
+      "..#.......",
+    ]);
+    let goal = tile(8, 0);
+    let field = FlowField::towards(&grid, &[goal]);
+
+    let start = tile(4, 2);
+    assert_eq!(
+      field.distance_at(start),
+      ground_distance(&grid, start, goal)
+    );
+
+    let mut current = start;
+    for _ in 0..50 {
+      if current == goal {
+        break;
+      }
+      current = field.next_step(current).unwrap();
+    }
+    assert_eq!(current, goal);
+  }
+}
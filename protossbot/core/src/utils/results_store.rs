@@ -0,0 +1,261 @@
+//! Results of every game played, appended one JSON line per game so they survive
+//! `auto_restart`, and the win rates the dashboard shows from them.
+
+use serde::{Deserialize, Serialize};
+use std::{
+  collections::BTreeMap,
+  fs::OpenOptions,
+  io::{BufRead, BufReader, Write},
+};
+
+use crate::{log_warn, logging::LogTarget, utils::game_stats::GameStats};
+
+/// Learning data, resolved through `DataPaths::learning_path` so it outlives each game
+pub const RESULTS_PATH: &str = "results.jsonl";
+/// Games averaged into each point of the win rate over time
+pub const ROLLING_WINDOW: usize = 10;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GameResult {
+  pub started_at: u64,
+  pub map: String,
+  /// Missing from results stored before opponents were told apart by name
+  #[serde(default)]
+  pub opponent: String,
+  pub opponent_race: String,
+  pub strategy: String,
+  pub won: bool,
+  pub duration_frames: i32,
+}
+
+impl GameResult {
+  pub fn from_stats(stats: &GameStats) -> Self {
+    Self {
+      started_at: stats.started_at,
+      map: stats.map.clone(),
+      opponent: stats.opponent.clone(),
+      opponent_race: stats.opponent_race.clone(),
+      strategy: stats.strategy.clone(),
+      won: stats.won.unwrap_or(false),
+      duration_frames: stats.duration_frames,
+    }
+  }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct WinRate {
+  pub key: String,
+  pub games: u32,
+  pub wins: u32,
+  pub win_rate: f32,
+}
+
+impl WinRate {
+  fn add(&mut self, won: bool) {
+    self.games += 1;
+    if won {
+      self.wins += 1;
+    }
+    self.win_rate = self.wins as f32 / self.games as f32;
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct WinRatePoint {
+  pub game: usize,
+  pub started_at: u64,
+  /// Win rate over the last [`ROLLING_WINDOW`] games up to this one
+  pub win_rate: f32,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct ResultsSummary {
+  pub total: WinRate,
+  pub by_map: Vec<WinRate>,
+  pub by_race: Vec<WinRate>,
+  pub by_strategy: Vec<WinRate>,
+  pub over_time: Vec<WinRatePoint>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ResultsStore {
+  path: String,
+}
+
+impl ResultsStore {
+  pub fn new(path: &str) -> Self {
+    Self {
+      path: path.to_string(),
+    }
+  }
+
+  pub fn append(&self, result: &GameResult) -> Result<(), String> {
+    let line = serde_json::to_string(result).map_err(|e| e.to_string())?;
+    let mut file = OpenOptions::new()
+      .create(true)
+      .append(true)
+      .open(&self.path)
+      .map_err(|e| e.to_string())?;
+    writeln!(file, "{}", line).map_err(|e| e.to_string())
+  }
+
+  /// Every stored result in the order played. A missing file is an empty store and
+  /// unreadable lines are skipped.
+  pub fn load(&self) -> Vec<GameResult> {
+    let Ok(file) = std::fs::File::open(&self.path) else {
+      return Vec::new();
+    };
+
+    let mut results: Vec<GameResult> = BufReader::new(file)
+      .lines()
+      .map_while(Result::ok)
+      .filter(|line| !line.trim().is_empty())
+      .filter_map(|line| match serde_json::from_str(&line) {
+        Ok(result) => Some(result),
+        Err(e) => {
+          log_warn!(
+            LogTarget::Strategy,
+            "Skipping bad line in {}: {}",
+            self.path,
+            e
+          );
+          None
+        }
+      })
+      .collect();
+    results.sort_by_key(|r| r.started_at);
+    results
+  }
+}
+
+fn group_by(results: &[GameResult], key: impl Fn(&GameResult) -> &str) -> Vec<WinRate> {
+  let mut groups: BTreeMap<&str, WinRate> = BTreeMap::new();
+  for result in results {
+    let group = groups.entry(key(result)).or_insert_with(|| WinRate {
+      key: key(result).to_string(),
+      ..WinRate::default()
+    });
+    group.add(result.won);
+  }
+  groups.into_values().collect()
+}
+
+/// Results must be in the order played, as [`ResultsStore::load`] returns them
+pub fn summarize(results: &[GameResult]) -> ResultsSummary {
+  let mut total = WinRate {
+    key: "all".to_string(),
+    ..WinRate::default()
+  };
+  for result in results {
+    total.add(result.won);
+  }
+
+  let over_time = results
+    .iter()
+    .enumerate()
+    .map(|(index, result)| {
+      let window = &results[(index + 1).saturating_sub(ROLLING_WINDOW)..=index];
+      let wins = window.iter().filter(|r| r.won).count();
+      WinRatePoint {
+        game: index + 1,
+        started_at: result.started_at,
+        win_rate: wins as f32 / window.len() as f32,
+      }
+    })
+    .collect();
+
+  ResultsSummary {
+    total,
+    by_map: group_by(results, |r| &r.map),
+    by_race: group_by(results, |r| &r.opponent_race),
+    by_strategy: group_by(results, |r| &r.strategy),
+    over_time,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn result(started_at: u64, map: &str, race: &str, won: bool) -> GameResult {
+    GameResult {
+      started_at,
+      map: map.to_string(),
+      opponent: format!("{} bot", race),
+      opponent_race: race.to_string(),
+      strategy: "cannon_expand".to_string(),
+      won,
+      duration_frames: 24 * 60 * 12,
+    }
+  }
+
+  #[test]
+  fn groups_win_rates() {
+    let results = vec![
+      result(1, "Destination", "Zerg", true),
+      result(2, "Destination", "Terran", false),
+      result(3, "Python", "Zerg", true),
+      result(4, "Destination", "Zerg", false),
+    ];
+
+    let summary = summarize(&results);
+
+    assert_eq!(summary.total.games, 4);
+    assert_eq!(summary.total.wins, 2);
+    let destination = summary
+      .by_map
+      .iter()
+      .find(|w| w.key == "Destination")
+      .unwrap();
+    assert_eq!((destination.games, destination.wins), (3, 1));
+    let zerg = summary.by_race.iter().find(|w| w.key == "Zerg").unwrap();
+    assert!((zerg.win_rate - 2.0 / 3.0).abs() < 1e-6);
+    assert_eq!(summary.by_strategy.len(), 1);
+  }
+
+  #[test]
+  fn rolls_the_win_rate_over_recent_games() {
+    let results: Vec<GameResult> = (0..ROLLING_WINDOW as u64 + 5)
+      .map(|i| result(i, "Python", "Protoss", i >= 5))
+      .collect();
+
+    let summary = summarize(&results);
+
+    assert_eq!(summary.over_time[0].win_rate, 0.0);
+    assert_eq!(summary.over_time.last().unwrap().win_rate, 1.0);
+  }
+
+  #[test]
+  fn appends_and_loads_results() {
+    let path = std::env::temp_dir().join(format!(
+      "protossbot_results_{}_appends_and_loads.jsonl",
+      std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    let store = ResultsStore::new(path.to_str().unwrap());
+
+    store.append(&result(20, "Python", "Zerg", true)).unwrap();
+    store
+      .append(&result(10, "Destination", "Zerg", false))
+      .unwrap();
+    std::fs::OpenOptions::new()
+      .append(true)
+      .open(&path)
+      .and_then(|mut file| writeln!(file, "not json"))
+      .unwrap();
+
+    let loaded = store.load();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(loaded.len(), 2);
+    assert_eq!(loaded[0].map, "Destination");
+  }
+
+  #[test]
+  fn missing_store_is_empty() {
+    let store = ResultsStore::new("does/not/exist.jsonl");
+
+    let summary = summarize(&store.load());
+    assert_eq!(summary.total.games, 0);
+    assert!(summary.over_time.is_empty());
+  }
+}
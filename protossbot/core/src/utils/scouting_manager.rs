@@ -0,0 +1,486 @@
+#[cfg(feature = "bwapi")]
+use rsbwapi::{Game, Player, Unit};
+use serde::Deserialize;
+
+#[cfg(feature = "bwapi")]
+use crate::{
+  log_debug,
+  state::game_state::IntendedCommand,
+  types::{Order, UnitType},
+  utils::command_recorder::CommandRecord,
+};
+use crate::{
+  log_info, log_warn,
+  logging::LogTarget,
+  state::game_state::{GameState, ScoutPhase},
+  types::{Position, TilePosition},
+  utils::terrain_analysis::TerrainAnalysis,
+};
+
+const DEFAULT_CONFIG: &str = include_str!("../../config/scouting.yaml");
+/// Optional override next to the bot, same format as the built-in config
+pub const SCOUTING_CONFIG_PATH: &str = "scouting.yaml";
+
+const ARRIVAL_RANGE: i32 = 5 * 32;
+const WAYPOINT_RANGE: i32 = 2 * 32;
+const CIRCLE_RADIUS_TILES: i32 = 9;
+const CIRCLE_WAYPOINTS: usize = 8;
+const CIRCLE_LAPS: usize = 2;
+/// A waypoint the scout can't get to in this long, because of a unit in the way or a
+/// ramp we misjudged, is skipped
+const WAYPOINT_TIMEOUT_FRAMES: i32 = 24 * 6;
+const RECORD_RANGE: i32 = 20 * 32;
+const THREAT_RANGE: i32 = 7 * 32;
+const HARASS_RANGE: i32 = 3 * 32;
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ScoutingConfig {
+  /// Supply as shown in game at which a probe is sent out to scout
+  pub scout_supply: i32,
+}
+
+impl Default for ScoutingConfig {
+  fn default() -> Self {
+    serde_yaml::from_str(DEFAULT_CONFIG).expect("built-in scouting config is valid")
+  }
+}
+
+impl ScoutingConfig {
+  /// Loads the config from `path`, falling back to the built-in one when the file is
+  /// missing or invalid.
+  pub fn load(path: &str) -> Self {
+    let Ok(text) = std::fs::read_to_string(path) else {
+      return Self::default();
+    };
+
+    match serde_yaml::from_str(&text) {
+      Ok(config) => {
+        log_info!(LogTarget::Game, "Loaded scouting config from {}", path);
+        config
+      }
+      Err(e) => {
+        log_warn!(
+          LogTarget::Game,
+          "Invalid scouting config in {}: {}, using defaults",
+          path,
+          e
+        );
+        Self::default()
+      }
+    }
+  }
+}
+
+#[cfg(feature = "bwapi")]
+pub fn on_frame(game: &Game, player: &Player, state: &mut GameState) {
+  match state.scouting.phase {
+    ScoutPhase::Waiting => try_start_scouting(player, state),
+    ScoutPhase::Done => {}
+    _ => {
+      let scout_id = state.scouting.scout_id;
+      let Some(scout) = player
+        .get_units()
+        .into_iter()
+        .find(|u| Some(u.get_id()) == scout_id)
+      else {
+        finish_scouting(state, "scout lost");
+        return;
+      };
+
+      if dodge_or_harass(game, player, &scout, state) {
+        return;
+      }
+
+      match state.scouting.phase {
+        ScoutPhase::Searching => search_start_locations(&scout, state),
+        ScoutPhase::Circling => circle_enemy_main(game, &scout, state),
+        ScoutPhase::Returning => return_home(player, &scout, state),
+        _ => {}
+      }
+    }
+  }
+}
+
+#[cfg(feature = "bwapi")]
+pub fn on_unit_destroy(unit: &Unit, state: &mut GameState) {
+  if state.scouting.scout_id == Some(unit.get_id()) {
+    log_info!(LogTarget::Scouting, "Scout {} died", unit.get_id());
+    finish_scouting(state, "scout died");
+  }
+}
+
+#[cfg(feature = "bwapi")]
+fn try_start_scouting(player: &Player, state: &mut GameState) {
+  if player.supply_used() < state.scouting.scout_supply * 2 {
+    return;
+  }
+  if state.enemy_starts.candidates.is_empty() {
+    return;
+  }
+
+  let Some(scout) = player.get_units().into_iter().find(|u| {
+    u.get_type() == UnitType::Protoss_Probe
+      && u.is_completed()
+      && u.is_gathering_minerals()
+      && !u.is_carrying_minerals()
+      && !state.intended_commands.contains_key(&u.get_id())
+  }) else {
+    return;
+  };
+
+  log_info!(
+    LogTarget::Scouting,
+    "Sending probe {} to scout",
+    scout.get_id()
+  );
+  state.scouting.scout_id = Some(scout.get_id());
+  state.scouting.phase = ScoutPhase::Searching;
+  state.scouting.circle_waypoint = 0;
+  state.scouting.waypoint_started_frame = None;
+}
+
+#[cfg(feature = "bwapi")]
+fn search_start_locations(scout: &Unit, state: &mut GameState) {
+  let confirmed = state
+    .enemy_starts
+    .candidates
+    .iter()
+    .find(|c| c.enemy_confirmed)
+    .map(|c| c.tile);
+  if let Some(enemy_main) = confirmed {
+    log_info!(
+      LogTarget::Scouting,
+      "Scout found enemy main at {:?}, circling",
+      enemy_main
+    );
+    state.scouting.enemy_main = Some(enemy_main);
+    state.scouting.phase = ScoutPhase::Circling;
+    return;
+  }
+
+  let scout_tile = scout.get_tile_position();
+  let Some(target) = state
+    .enemy_starts
+    .candidates
+    .iter()
+    .filter(|c| !c.scouted_empty)
+    .min_by_key(|c| c.distances.distance_at(scout_tile).unwrap_or(i32::MAX))
+    .map(|c| start_location_center(c.tile))
+  else {
+    finish_scouting_with_return(state, "no start locations left to scout");
+    return;
+  };
+
+  move_scout(scout, target, "checking a possible enemy start", state);
+}
+
+#[cfg(feature = "bwapi")]
+fn circle_enemy_main(game: &Game, scout: &Unit, state: &mut GameState) {
+  let Some(enemy_main) = state.scouting.enemy_main else {
+    state.scouting.phase = ScoutPhase::Searching;
+    return;
+  };
+
+  if state.scouting.circle_waypoint >= CIRCLE_WAYPOINTS * CIRCLE_LAPS {
+    finish_scouting_with_return(state, "finished circling enemy main");
+    return;
+  }
+
+  let frame = game.get_frame_count();
+  let started = *state.scouting.waypoint_started_frame.get_or_insert(frame);
+  let waypoint = circle_waypoint(&state.terrain, enemy_main, state.scouting.circle_waypoint);
+
+  let reached = distance(scout.get_position(), waypoint) <= WAYPOINT_RANGE;
+  if reached || frame - started > WAYPOINT_TIMEOUT_FRAMES {
+    if !reached {
+      log_debug!(
+        LogTarget::Scouting,
+        "Scout could not reach waypoint {:?}, skipping it",
+        waypoint
+      );
+    }
+    state.scouting.circle_waypoint += 1;
+    state.scouting.waypoint_started_frame = None;
+    return;
+  }
+
+  move_scout(scout, waypoint, "circling the enemy main", state);
+}
+
+#[cfg(feature = "bwapi")]
+fn return_home(player: &Player, scout: &Unit, state: &mut GameState) {
+  let home = start_location_center(player.get_start_location());
+
+  if distance(scout.get_position(), home) <= ARRIVAL_RANGE {
+    log_info!(
+      LogTarget::Scouting,
+      "Scout {} returned home",
+      scout.get_id()
+    );
+    state.intended_commands.remove(&scout.get_id());
+    state.scouting.scout_id = None;
+    state.scouting.phase = ScoutPhase::Done;
+    return;
+  }
+
+  move_scout(scout, home, "returning home", state);
+}
+
+/// Runs from nearby threats once shields are down and picks at enemy workers when
+/// it is safe to do so. Returns true when it issued an order this frame.
+#[cfg(feature = "bwapi")]
+fn dodge_or_harass(game: &Game, player: &Player, scout: &Unit, state: &mut GameState) -> bool {
+  let scout_pos = scout.get_position();
+  let scout_type = scout.get_type();
+
+  let enemies: Vec<Unit> = game
+    .get_all_units()
+    .into_iter()
+    .filter(|u| u.get_player().is_enemy(player) && !u.get_type().is_building())
+    .collect();
+
+  let nearest_threat = enemies
+    .iter()
+    .filter(|u| !u.get_type().is_worker() && u.get_type().can_attack())
+    .map(|u| (distance(u.get_position(), scout_pos), u))
+    .filter(|(d, _)| *d <= THREAT_RANGE)
+    .min_by_key(|(d, _)| *d);
+
+  if scout.get_hit_points() * 2 < scout_type.max_hit_points()
+    && state.scouting.phase != ScoutPhase::Returning
+  {
+    finish_scouting_with_return(state, "scout badly damaged");
+  }
+
+  if scout.get_shields() * 2 < scout_type.max_shields() {
+    if let Some((_, threat)) = nearest_threat {
+      let threat_pos = threat.get_position();
+      let away = Position {
+        x: scout_pos.x + (scout_pos.x - threat_pos.x),
+        y: scout_pos.y + (scout_pos.y - threat_pos.y),
+      };
+      move_scout(scout, clamp_to_map(game, away), "dodging a threat", state);
+      return true;
+    }
+  }
+
+  if state.scouting.phase == ScoutPhase::Circling
+    && nearest_threat.is_none()
+    && scout.get_shields() == scout_type.max_shields()
+  {
+    let worker = enemies
+      .iter()
+      .filter(|u| u.get_type().is_worker())
+      .find(|u| distance(u.get_position(), scout_pos) <= HARASS_RANGE);
+    if let Some(worker) = worker {
+      if scout.get_order() != Order::AttackUnit {
+        let result = scout.attack(worker);
+        state.commands.record(
+          CommandRecord::new(
+            scout.get_id(),
+            "Attack",
+            "scouting_manager",
+            "harassing workers",
+          )
+          .with_target(worker.get_id())
+          .with_result(&result),
+        );
+        if result.is_ok() {
+          state.intended_commands.insert(
+            scout.get_id(),
+            IntendedCommand {
+              order: Order::AttackUnit,
+              target_position: None,
+              target_unit_id: Some(worker.get_id()),
+            },
+          );
+        }
+      }
+      return true;
+    }
+  }
+
+  false
+}
+
+#[cfg(feature = "bwapi")]
+fn move_scout(scout: &Unit, target: Position, reason: &str, state: &mut GameState) {
+  let already_moving = state
+    .intended_commands
+    .get(&scout.get_id())
+    .is_some_and(|cmd| cmd.order == Order::Move && cmd.target_position == Some(target));
+  if already_moving && !scout.is_idle() {
+    return;
+  }
+
+  let result = scout.move_(target);
+  state.commands.record(
+    CommandRecord::new(scout.get_id(), "Move", "scouting_manager", reason)
+      .with_target(target)
+      .with_result(&result),
+  );
+  if result.is_ok() {
+    state.intended_commands.insert(
+      scout.get_id(),
+      IntendedCommand {
+        order: Order::Move,
+        target_position: Some(target),
+        target_unit_id: None,
+      },
+    );
+  }
+}
+
+fn finish_scouting_with_return(state: &mut GameState, reason: &str) {
+  log_info!(LogTarget::Scouting, "Scout returning home: {}", reason);
+  state.scouting.phase = ScoutPhase::Returning;
+}
+
+fn finish_scouting(state: &mut GameState, reason: &str) {
+  log_info!(LogTarget::Scouting, "Scouting finished: {}", reason);
+  if let Some(scout_id) = state.scouting.scout_id.take() {
+    state.intended_commands.remove(&scout_id);
+  }
+  state.scouting.phase = ScoutPhase::Done;
+}
+
+/// Point on a circle around the enemy main, moved onto the closest walkable tile of the
+/// main's own region so the scout never heads for a cliff, water or the low ground
+fn circle_waypoint(terrain: &TerrainAnalysis, enemy_main: TilePosition, index: usize) -> Position {
+  let center = start_location_center(enemy_main);
+  let angle = (index % CIRCLE_WAYPOINTS) as f32 / CIRCLE_WAYPOINTS as f32 * std::f32::consts::TAU;
+  let radius = (CIRCLE_RADIUS_TILES * 32) as f32;
+  let ideal = TilePosition {
+    x: (center.x + (angle.cos() * radius) as i32) / 32,
+    y: (center.y + (angle.sin() * radius) as i32) / 32,
+  };
+
+  let main_region = terrain.region_at(TilePosition {
+    x: center.x / 32,
+    y: center.y / 32,
+  });
+  let in_main = |tile: &TilePosition| {
+    terrain.is_walkable(*tile) && (main_region.is_none() || terrain.region_at(*tile) == main_region)
+  };
+
+  // The ideal point is CIRCLE_RADIUS_TILES from the center, so the main is always in reach
+  (0..=CIRCLE_RADIUS_TILES)
+    .find_map(|ring| {
+      (-ring..=ring)
+        .flat_map(|dx| (-ring..=ring).map(move |dy| (dx, dy)))
+        .filter(|(dx, dy)| dx.abs() == ring || dy.abs() == ring)
+        .map(|(dx, dy)| TilePosition {
+          x: ideal.x + dx,
+          y: ideal.y + dy,
+        })
+        .filter(in_main)
+        .min_by_key(|tile| (tile.x - ideal.x).pow(2) + (tile.y - ideal.y).pow(2))
+    })
+    .map(|tile| Position {
+      x: tile.x * 32 + 16,
+      y: tile.y * 32 + 16,
+    })
+    .unwrap_or(center)
+}
+
+#[cfg(feature = "bwapi")]
+fn clamp_to_map(game: &Game, position: Position) -> Position {
+  Position {
+    x: position.x.clamp(0, game.map_width() * 32 - 1),
+    y: position.y.clamp(0, game.map_height() * 32 - 1),
+  }
+}
+
+fn start_location_center(tile: TilePosition) -> Position {
+  Position {
+    x: tile.x * 32 + 64,
+    y: tile.y * 32 + 48,
+  }
+}
+
+fn distance(a: Position, b: Position) -> i32 {
+  (((a.x - b.x).pow(2) + (a.y - b.y).pow(2)) as f32).sqrt() as i32
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::utils::terrain_analysis::MapGrid;
+
+  const ENEMY_MAIN: TilePosition = TilePosition { x: 10, y: 10 };
+
+  /// 40x40 map with the enemy main west of a cliff at x 18..20 and low ground east of it
+  fn terrain() -> TerrainAnalysis {
+    let (width, height) = (40, 40);
+    let mut walkable = Vec::new();
+    let mut region_map = Vec::new();
+    for _y in 0..height {
+      for x in 0..width {
+        let cliff = (18..20).contains(&x);
+        walkable.push(!cliff);
+        region_map.push(match x {
+          x if x < 18 => Some(0),
+          x if x >= 20 => Some(1),
+          _ => None,
+        });
+      }
+    }
+
+    TerrainAnalysis {
+      grid: MapGrid {
+        width,
+        height,
+        buildable: walkable.clone(),
+        walkable,
+      },
+      region_map,
+      ..TerrainAnalysis::default()
+    }
+  }
+
+  fn tile_of(position: Position) -> TilePosition {
+    TilePosition {
+      x: position.x / 32,
+      y: position.y / 32,
+    }
+  }
+
+  #[test]
+  fn waypoints_in_the_open_stay_on_the_circle() {
+    let terrain = terrain();
+
+    // A quarter turn points straight down from the center at (12, 11)
+    let waypoint = circle_waypoint(&terrain, ENEMY_MAIN, CIRCLE_WAYPOINTS / 4);
+
+    assert_eq!(tile_of(waypoint), TilePosition { x: 12, y: 20 });
+  }
+
+  #[test]
+  fn waypoints_past_the_cliff_snap_back_into_the_main() {
+    let terrain = terrain();
+
+    // Due east of the center lands on the low ground across the cliff
+    let waypoint = circle_waypoint(&terrain, ENEMY_MAIN, 0);
+
+    let tile = tile_of(waypoint);
+    assert_eq!(terrain.region_at(tile), Some(0));
+    assert_eq!(tile, TilePosition { x: 17, y: 11 });
+  }
+
+  #[test]
+  fn every_waypoint_is_walkable_in_the_main() {
+    let terrain = terrain();
+
+    for index in 0..CIRCLE_WAYPOINTS {
+      let tile = tile_of(circle_waypoint(&terrain, ENEMY_MAIN, index));
+      assert!(terrain.is_walkable(tile), "{:?}", tile);
+      assert_eq!(terrain.region_at(tile), Some(0), "{:?}", tile);
+    }
+  }
+
+  #[test]
+  fn loads_the_built_in_config() {
+    assert_eq!(ScoutingConfig::default().scout_supply, 9);
+    assert_eq!(ScoutingConfig::load("does/not/exist.yaml").scout_supply, 9);
+  }
+}
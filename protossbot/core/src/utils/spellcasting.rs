@@ -0,0 +1,550 @@
+#[cfg(feature = "bwapi")]
+use rsbwapi::{Game, Player, TechType, Unit};
+
+use crate::types::Position;
+#[cfg(feature = "bwapi")]
+use crate::{
+  log_info, log_warn,
+  logging::LogTarget,
+  state::game_state::{GameState, SquadMode},
+  types::{Order, UnitType},
+  utils::{
+    army_manager,
+    command_recorder::{CommandRecord, CommandRecorder},
+  },
+};
+
+const STORM_ENERGY: i32 = 75;
+const HALLUCINATION_ENERGY: i32 = 100;
+/// Templars below this energy and unable to storm again soon become archons
+const ARCHON_ENERGY: i32 = 40;
+/// Templars farther apart than this would walk across the map to merge
+const ARCHON_PAIR_RANGE: i32 = 6 * 32;
+const STORM_RANGE: i32 = 9 * 32;
+/// Psionic Storm covers 3x3 tiles around its center
+const STORM_RADIUS: i32 = 48;
+/// Extra room kept between friendly units and the storm's edge
+const FRIENDLY_MARGIN: i32 = 16;
+/// A storm has to hit at least this much enemy value to be worth casting
+const MIN_STORM_VALUE: f32 = 200.0;
+/// Storms last a few seconds; don't stack a second one on the same spot
+const STORM_DURATION_FRAMES: i32 = 24 * 3;
+const ENGAGE_RANGE: i32 = 12 * 32;
+const FOLLOW_RANGE: i32 = 3 * 32;
+const TEMPLAR_FOLLOW_DISTANCE: i32 = 3 * 32;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StormUnit {
+  pub position: Position,
+  pub value: f32,
+}
+
+/// Picks the storm center that hits the most enemy value within cast range of the
+/// caster without catching any friendly unit in the splash.
+pub fn find_storm_target(
+  caster: Position,
+  enemies: &[StormUnit],
+  friends: &[Position],
+  recent_storms: &[Position],
+) -> Option<Position> {
+  let safe = |center: Position| {
+    friends
+      .iter()
+      .all(|f| distance(*f, center) > STORM_RADIUS + FRIENDLY_MARGIN)
+      && recent_storms
+        .iter()
+        .all(|s| distance(*s, center) > STORM_RADIUS)
+  };
+  let covered = |center: Position| -> Vec<&StormUnit> {
+    enemies
+      .iter()
+      .filter(|e| distance(e.position, center) <= STORM_RADIUS)
+      .collect()
+  };
+
+  enemies
+    .iter()
+    .filter_map(|candidate| {
+      let hit = covered(candidate.position);
+      // Centering on the cluster catches more of it than centering on one unit
+      let center = Position {
+        x: hit.iter().map(|e| e.position.x).sum::<i32>() / hit.len() as i32,
+        y: hit.iter().map(|e| e.position.y).sum::<i32>() / hit.len() as i32,
+      };
+      [center, candidate.position]
+        .into_iter()
+        .filter(|c| distance(caster, *c) <= STORM_RANGE && safe(*c))
+        .map(|c| (covered(c).iter().map(|e| e.value).sum::<f32>(), c))
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+    })
+    .filter(|(value, _)| *value >= MIN_STORM_VALUE)
+    .max_by(|a, b| a.0.total_cmp(&b.0))
+    .map(|(_, center)| center)
+}
+
+/// Pairs up templars that are low on energy, closest first. A templar with no partner
+/// within `ARCHON_PAIR_RANGE` stays on its own.
+pub fn archon_pairs(templars: &[(usize, Position, i32)]) -> Vec<(usize, usize)> {
+  let mut low: Vec<(usize, Position)> = templars
+    .iter()
+    .filter(|(_, _, energy)| *energy < ARCHON_ENERGY)
+    .map(|(id, pos, _)| (*id, *pos))
+    .collect();
+
+  let mut pairs = Vec::new();
+  while low.len() >= 2 {
+    let (first_id, first_pos) = low.remove(0);
+    let (index, (_, second_pos)) = low
+      .iter()
+      .enumerate()
+      .min_by_key(|(_, (_, pos))| distance(*pos, first_pos))
+      .unwrap();
+    if distance(*second_pos, first_pos) > ARCHON_PAIR_RANGE {
+      continue;
+    }
+    let (second_id, _) = low.remove(index);
+    pairs.push((first_id, second_id));
+  }
+  pairs
+}
+
+#[cfg(feature = "bwapi")]
+pub fn on_frame(game: &Game, player: &Player, state: &mut GameState) {
+  let frame = game.get_frame_count();
+  state
+    .spells
+    .recent_storms
+    .retain(|(_, cast_frame)| frame - cast_frame < STORM_DURATION_FRAMES);
+
+  let our_units = player.get_units();
+  let templars: Vec<&Unit> = our_units
+    .iter()
+    .filter(|u| u.get_type() == UnitType::Protoss_High_Templar && u.is_completed())
+    .collect();
+  let mut busy: Vec<usize> = Vec::new();
+
+  cast_storms(game, player, &templars, &mut busy, state);
+  cast_hallucinations(
+    game,
+    player,
+    &our_units,
+    &templars,
+    &mut busy,
+    &mut state.commands,
+  );
+  merge_archons(player, &templars, &mut busy, &mut state.commands);
+
+  let Some(squad_center) = followed_squad_center(&our_units, state) else {
+    return;
+  };
+
+  // Templars trail the army, everything else that supports it stays on top of it
+  let rally = state.army.rally_position.unwrap_or(squad_center);
+  let behind = step_towards(squad_center, rally, TEMPLAR_FOLLOW_DISTANCE);
+  for unit in &our_units {
+    if busy.contains(&unit.get_id()) || !unit.is_completed() {
+      continue;
+    }
+    let target = match unit.get_type() {
+      UnitType::Protoss_High_Templar => behind,
+      UnitType::Protoss_Arbiter => squad_center,
+      _ => continue,
+    };
+    follow(
+      unit,
+      target,
+      "spellcasting",
+      "staying with the army",
+      &mut state.commands,
+    );
+  }
+}
+
+/// Center of the squad support units should stay with: attacking squads first.
+#[cfg(feature = "bwapi")]
+fn followed_squad_center(our_units: &[Unit], state: &GameState) -> Option<Position> {
+  let priority = |mode: SquadMode| match mode {
+    SquadMode::Attack => 0,
+    SquadMode::Defend => 1,
+    SquadMode::Rally => 2,
+  };
+
+  state
+    .army
+    .squads
+    .iter()
+    .filter(|s| !s.unit_ids.is_empty())
+    .min_by_key(|s| (priority(s.mode), std::cmp::Reverse(s.unit_ids.len())))
+    .and_then(|s| {
+      let members: Vec<&Unit> = our_units
+        .iter()
+        .filter(|u| s.unit_ids.contains(&u.get_id()))
+        .collect();
+      army_manager::squad_center(&members)
+    })
+}
+
+#[cfg(feature = "bwapi")]
+fn cast_storms(
+  game: &Game,
+  player: &Player,
+  templars: &[&Unit],
+  busy: &mut Vec<usize>,
+  state: &mut GameState,
+) {
+  // Without the research every cast fails, and would be retried each frame
+  if !player.has_researched(TechType::Psionic_Storm) {
+    return;
+  }
+
+  let all_units = game.get_all_units();
+  let enemies: Vec<StormUnit> = all_units
+    .iter()
+    .filter(|u| u.get_player().is_enemy(player))
+    .filter(|u| !u.get_type().is_building() && !u.is_under_storm())
+    .filter(|u| !u.is_cloaked() || u.is_detected())
+    .map(|u| StormUnit {
+      position: u.get_position(),
+      value: (u.get_type().mineral_price() + u.get_type().gas_price()) as f32,
+    })
+    .collect();
+  let friends: Vec<Position> = player
+    .get_units()
+    .iter()
+    .filter(|u| !u.get_type().is_building())
+    .map(|u| u.get_position())
+    .collect();
+
+  for templar in templars {
+    if templar.get_order() == Order::CastPsionicStorm {
+      busy.push(templar.get_id());
+      continue;
+    }
+    if templar.get_energy() < STORM_ENERGY {
+      continue;
+    }
+
+    let nearby: Vec<StormUnit> = enemies
+      .iter()
+      .filter(|e| distance(e.position, templar.get_position()) <= ENGAGE_RANGE)
+      .copied()
+      .collect();
+    let recent: Vec<Position> = state.spells.recent_storms.iter().map(|(p, _)| *p).collect();
+    let Some(target) = find_storm_target(templar.get_position(), &nearby, &friends, &recent) else {
+      continue;
+    };
+
+    log_info!(
+      LogTarget::Spells,
+      "Templar {} casting storm at {:?}",
+      templar.get_id(),
+      target
+    );
+    let result = templar.use_tech(TechType::Psionic_Storm, target);
+    state.commands.record(
+      CommandRecord::new(
+        templar.get_id(),
+        "UseTech",
+        "spellcasting",
+        "storm on enemy cluster",
+      )
+      .with_target((TechType::Psionic_Storm, target))
+      .with_result(&result),
+    );
+    match result {
+      Ok(_) => {
+        busy.push(templar.get_id());
+        state
+          .spells
+          .recent_storms
+          .push((target, game.get_frame_count()));
+      }
+      Err(e) => log_warn!(
+        LogTarget::Spells,
+        "Storm FAILED for templar {}: {:?}",
+        templar.get_id(),
+        e
+      ),
+    }
+  }
+}
+
+/// Sends hallucinated copies of our strongest unit ahead when we are already fighting.
+#[cfg(feature = "bwapi")]
+fn cast_hallucinations(
+  game: &Game,
+  player: &Player,
+  our_units: &[Unit],
+  templars: &[&Unit],
+  busy: &mut Vec<usize>,
+  commands: &mut CommandRecorder,
+) {
+  if !player.has_researched(TechType::Hallucination) {
+    return;
+  }
+
+  let enemies: Vec<Position> = game
+    .get_all_units()
+    .iter()
+    .filter(|u| u.get_player().is_enemy(player) && u.get_type().can_attack())
+    .map(|u| u.get_position())
+    .collect();
+
+  for templar in templars {
+    if templar.get_order() == Order::CastHallucination {
+      busy.push(templar.get_id());
+      continue;
+    }
+    if busy.contains(&templar.get_id()) || templar.get_energy() < HALLUCINATION_ENERGY {
+      continue;
+    }
+    let position = templar.get_position();
+    if !enemies
+      .iter()
+      .any(|e| distance(*e, position) <= ENGAGE_RANGE)
+    {
+      continue;
+    }
+
+    let Some(target) = our_units
+      .iter()
+      .filter(|u| army_manager::is_combat_unit(u) && !u.is_hallucination())
+      .filter(|u| distance(u.get_position(), position) <= STORM_RANGE)
+      .max_by_key(|u| u.get_type().mineral_price() + u.get_type().gas_price())
+    else {
+      continue;
+    };
+
+    log_info!(
+      LogTarget::Spells,
+      "Templar {} hallucinating {:?}",
+      templar.get_id(),
+      target.get_type()
+    );
+    let result = templar.use_tech(TechType::Hallucination, target);
+    commands.record(
+      CommandRecord::new(
+        templar.get_id(),
+        "UseTech",
+        "spellcasting",
+        "decoys for a fight",
+      )
+      .with_target((TechType::Hallucination, target.get_id()))
+      .with_result(&result),
+    );
+    if result.is_ok() {
+      busy.push(templar.get_id());
+    }
+  }
+}
+
+#[cfg(feature = "bwapi")]
+fn merge_archons(
+  player: &Player,
+  templars: &[&Unit],
+  busy: &mut Vec<usize>,
+  commands: &mut CommandRecorder,
+) {
+  // Keep fresh templars around for storms until storm is off the table for the game:
+  // not researched, not under way, and no Templar Archives left to research it at
+  let storm_available = player.has_researched(TechType::Psionic_Storm)
+    || player.is_researching(TechType::Psionic_Storm)
+    || player
+      .get_units()
+      .iter()
+      .any(|u| u.get_type() == UnitType::Protoss_Templar_Archives);
+
+  let candidates: Vec<(usize, Position, i32)> = templars
+    .iter()
+    .filter(|t| !busy.contains(&t.get_id()) && t.get_order() != Order::ArchonWarp)
+    .map(|t| {
+      let energy = if storm_available { t.get_energy() } else { 0 };
+      (t.get_id(), t.get_position(), energy)
+    })
+    .collect();
+
+  for (first_id, second_id) in archon_pairs(&candidates) {
+    let first = templars.iter().find(|t| t.get_id() == first_id);
+    let second = templars.iter().find(|t| t.get_id() == second_id);
+    let (Some(first), Some(second)) = (first, second) else {
+      continue;
+    };
+
+    log_info!(
+      LogTarget::Spells,
+      "Merging templars {} and {} into an archon",
+      first_id,
+      second_id
+    );
+    let result = first.use_tech(TechType::Archon_Warp, *second);
+    commands.record(
+      CommandRecord::new(
+        first_id,
+        "UseTech",
+        "spellcasting",
+        "templars out of energy",
+      )
+      .with_target((TechType::Archon_Warp, second_id))
+      .with_result(&result),
+    );
+    if let Err(e) = result {
+      log_warn!(LogTarget::Spells, "Archon merge FAILED: {:?}", e);
+      continue;
+    }
+    busy.push(first_id);
+    busy.push(second_id);
+  }
+}
+
+/// Moves the unit towards a moving target without re-issuing the same order every frame.
+#[cfg(feature = "bwapi")]
+pub fn follow(
+  unit: &Unit,
+  target: Position,
+  source: &'static str,
+  reason: &str,
+  commands: &mut CommandRecorder,
+) {
+  if distance(unit.get_position(), target) <= FOLLOW_RANGE {
+    return;
+  }
+  if unit.get_order() == Order::Move && !unit.is_idle() {
+    if let Some(current) = unit.get_target_position() {
+      if distance(current, target) <= FOLLOW_RANGE {
+        return;
+      }
+    }
+  }
+  let result = unit.move_(target);
+  commands.record(
+    CommandRecord::new(unit.get_id(), "Move", source, reason)
+      .with_target(target)
+      .with_result(&result),
+  );
+  if let Err(e) = result {
+    log_warn!(
+      LogTarget::Spells,
+      "Follow move FAILED for unit {}: {:?}",
+      unit.get_id(),
+      e
+    );
+  }
+}
+
+fn step_towards(from: Position, to: Position, step: i32) -> Position {
+  let length = distance(from, to);
+  if length <= step {
+    return to;
+  }
+  Position {
+    x: from.x + (to.x - from.x) * step / length,
+    y: from.y + (to.y - from.y) * step / length,
+  }
+}
+
+fn distance(a: Position, b: Position) -> i32 {
+  (((a.x - b.x).pow(2) + (a.y - b.y).pow(2)) as f32).sqrt() as i32
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn pos(x: i32, y: i32) -> Position {
+    Position { x, y }
+  }
+
+  fn hydra(x: i32, y: i32) -> StormUnit {
+    StormUnit {
+      position: pos(x, y),
+      value: 125.0,
+    }
+  }
+
+  #[test]
+  fn storms_the_center_of_a_cluster() {
+    let enemies = vec![
+      hydra(200, 200),
+      hydra(220, 200),
+      hydra(200, 220),
+      hydra(220, 220),
+    ];
+
+    let target = find_storm_target(pos(0, 100), &enemies, &[], &[]).unwrap();
+
+    assert!(distance(target, pos(210, 210)) <= 16);
+  }
+
+  #[test]
+  fn never_storms_on_top_of_friendly_units() {
+    let enemies = vec![hydra(200, 200), hydra(220, 200), hydra(210, 220)];
+    let friends = vec![pos(215, 205)];
+
+    assert_eq!(
+      find_storm_target(pos(0, 100), &enemies, &friends, &[]),
+      None
+    );
+  }
+
+  #[test]
+  fn picks_the_cluster_away_from_friendly_units() {
+    let mut enemies = vec![hydra(100, 100), hydra(110, 100), hydra(105, 110)];
+    enemies.extend([hydra(200, 300), hydra(210, 300), hydra(205, 310)]);
+    let friends = vec![pos(105, 105)];
+
+    let target = find_storm_target(pos(150, 150), &enemies, &friends, &[]).unwrap();
+
+    assert!(distance(target, pos(205, 303)) <= STORM_RADIUS);
+  }
+
+  #[test]
+  fn lone_units_are_not_worth_a_storm() {
+    let enemies = vec![hydra(100, 100), hydra(300, 100)];
+
+    assert_eq!(find_storm_target(pos(200, 100), &enemies, &[], &[]), None);
+  }
+
+  #[test]
+  fn ignores_clusters_out_of_range() {
+    let enemies = vec![hydra(1000, 1000), hydra(1010, 1000), hydra(1005, 1010)];
+
+    assert_eq!(find_storm_target(pos(0, 0), &enemies, &[], &[]), None);
+  }
+
+  #[test]
+  fn does_not_stack_storms() {
+    let enemies = vec![hydra(200, 200), hydra(220, 200), hydra(210, 220)];
+    let recent = vec![pos(210, 206)];
+
+    assert_eq!(find_storm_target(pos(0, 100), &enemies, &[], &recent), None);
+  }
+
+  #[test]
+  fn pairs_closest_low_energy_templars() {
+    let templars = vec![
+      (1, pos(0, 0), 10),
+      (2, pos(500, 0), 20),
+      (3, pos(10, 0), 5),
+      (4, pos(510, 0), 30),
+      (5, pos(20, 0), 150),
+    ];
+
+    assert_eq!(archon_pairs(&templars), vec![(1, 3), (2, 4)]);
+  }
+
+  #[test]
+  fn does_not_pair_templars_across_the_map() {
+    let templars = vec![
+      (1, pos(0, 0), 10),
+      (2, pos(2000, 0), 10),
+      (3, pos(2000, 150), 10),
+      (4, pos(4000, 0), 10),
+    ];
+
+    assert_eq!(archon_pairs(&templars), vec![(2, 3)]);
+    assert_eq!(
+      archon_pairs(&[(1, pos(0, 0), 10), (2, pos(ARCHON_PAIR_RANGE + 1, 0), 10)]),
+      vec![]
+    );
+  }
+}
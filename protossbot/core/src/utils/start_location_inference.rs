@@ -0,0 +1,275 @@
+#[cfg(feature = "bwapi")]
+use rsbwapi::{Game, Player};
+
+use crate::{
+  log_info, logging::LogTarget, state::game_state::GameState, types::TilePosition,
+  utils::terrain_analysis::TerrainAnalysis,
+};
+#[cfg(feature = "bwapi")]
+use crate::{state::game_state::StartLocationCandidate, utils::pathfinding::FlowField};
+
+/// Weight multiplier for the closest start location when a building shows up elsewhere
+const DISTANT_BUILDING_WEIGHT: f32 = 2.0;
+/// Weight of a start location with an enemy building in its region
+const CONFIRMED_WEIGHT: f32 = 100.0;
+
+#[cfg(feature = "bwapi")]
+pub fn initialize(game: &Game, player: &Player, state: &mut GameState) {
+  let own_start = player.get_start_location();
+  let grid = &state.terrain.grid;
+
+  state.enemy_starts.candidates = game
+    .get_start_locations()
+    .into_iter()
+    .filter(|tile| *tile != own_start)
+    .map(|tile| StartLocationCandidate {
+      tile,
+      weight: 1.0,
+      probability: 0.0,
+      scouted_empty: false,
+      enemy_confirmed: false,
+      distances: FlowField::towards(grid, &[tile]),
+    })
+    .collect();
+  state.enemy_starts.observed_building_ids.clear();
+
+  normalize(state);
+  log_info!(
+    LogTarget::Scouting,
+    "Possible enemy start locations: {:?}",
+    state
+      .enemy_starts
+      .candidates
+      .iter()
+      .map(|c| c.tile)
+      .collect::<Vec<_>>()
+  );
+}
+
+#[cfg(feature = "bwapi")]
+pub fn on_frame(game: &Game, state: &mut GameState) {
+  if state.enemy_starts.candidates.is_empty() {
+    return;
+  }
+
+  let enemy_buildings: Vec<(usize, TilePosition)> = state
+    .enemy_memory
+    .buildings()
+    .map(|info| (info.unit_id, info.last_tile()))
+    .collect();
+
+  for &(building_id, tile) in &enemy_buildings {
+    observe_enemy_building(state, building_id, tile);
+  }
+
+  let terrain = &state.terrain;
+  for candidate in state.enemy_starts.candidates.iter_mut() {
+    if candidate.enemy_confirmed || candidate.scouted_empty {
+      continue;
+    }
+
+    let center = TilePosition {
+      x: candidate.tile.x + 2,
+      y: candidate.tile.y + 1,
+    };
+    let has_enemy_building = enemy_buildings
+      .iter()
+      .any(|(_, tile)| in_start_region(terrain, *tile, candidate.tile));
+
+    if game.is_visible(center) && !has_enemy_building {
+      log_info!(
+        LogTarget::Scouting,
+        "Start location {:?} scouted empty",
+        candidate.tile
+      );
+      candidate.scouted_empty = true;
+      candidate.weight = 0.0;
+    }
+  }
+
+  let previous = state.enemy_starts.most_likely_enemy_main;
+  normalize(state);
+  if state.enemy_starts.most_likely_enemy_main != previous {
+    log_info!(
+      LogTarget::Scouting,
+      "Most likely enemy main is now {:?}",
+      state.enemy_starts.most_likely_enemy_main
+    );
+  }
+}
+
+/// Records evidence from an enemy building; each building id only counts once. A
+/// building in a start location's own region confirms it, any other building makes the
+/// closest open start location more likely.
+pub fn observe_enemy_building(state: &mut GameState, building_id: usize, tile: TilePosition) {
+  if !state.enemy_starts.observed_building_ids.insert(building_id) {
+    return;
+  }
+
+  let terrain = &state.terrain;
+  let candidates = &mut state.enemy_starts.candidates;
+  if let Some(candidate) = candidates
+    .iter_mut()
+    .find(|c| in_start_region(terrain, tile, c.tile))
+  {
+    if !candidate.enemy_confirmed {
+      log_info!(
+        LogTarget::Scouting,
+        "Enemy main confirmed at {:?}",
+        candidate.tile
+      );
+    }
+    candidate.enemy_confirmed = true;
+    candidate.scouted_empty = false;
+    candidate.weight = CONFIRMED_WEIGHT;
+    return;
+  }
+
+  // A building away from every start location still hints at the closest one
+  if let Some(closest) = candidates
+    .iter_mut()
+    .filter(|c| !c.scouted_empty && !c.enemy_confirmed)
+    .min_by_key(|c| (c.tile.x - tile.x).pow(2) + (c.tile.y - tile.y).pow(2))
+  {
+    closest.weight *= DISTANT_BUILDING_WEIGHT;
+  }
+}
+
+fn normalize(state: &mut GameState) {
+  let candidates = &mut state.enemy_starts.candidates;
+  let total: f32 = candidates.iter().map(|c| c.weight).sum();
+
+  for candidate in candidates.iter_mut() {
+    candidate.probability = if total > 0.0 {
+      candidate.weight / total
+    } else {
+      0.0
+    };
+  }
+
+  state.enemy_starts.most_likely_enemy_main = candidates
+    .iter()
+    .filter(|c| c.probability > 0.0)
+    .max_by(|a, b| a.probability.total_cmp(&b.probability))
+    .map(|c| c.tile);
+}
+
+fn in_start_region(terrain: &TerrainAnalysis, tile: TilePosition, start: TilePosition) -> bool {
+  terrain
+    .region_at(tile)
+    .is_some_and(|region| terrain.region_at(start) == Some(region))
+}
+
+#[cfg(feature = "bwapi")]
+pub fn draw_debug(game: &Game, state: &GameState) {
+  for candidate in &state.enemy_starts.candidates {
+    game.draw_text_map(
+      candidate.tile.to_position(),
+      &format!("Enemy start? {:.0}%", candidate.probability * 100.0),
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    state::game_state::StartLocationCandidate,
+    utils::{pathfinding::FlowField, terrain_analysis::MapGrid},
+  };
+
+  fn tile(x: i32, y: i32) -> TilePosition {
+    TilePosition { x, y }
+  }
+
+  /// A 60x20 map split into three regions of 20 columns, with a start location in the
+  /// left and in the right one
+  fn state_with_candidates() -> GameState {
+    let mut state = GameState::default();
+    state.terrain.grid = MapGrid {
+      width: 60,
+      height: 20,
+      walkable: vec![true; 1200],
+      buildable: vec![true; 1200],
+    };
+    state.terrain.region_map = (0..1200).map(|i| Some((i % 60) as usize / 20)).collect();
+    state.enemy_starts.candidates = [tile(5, 5), tile(50, 5)]
+      .into_iter()
+      .map(|tile| StartLocationCandidate {
+        tile,
+        weight: 1.0,
+        probability: 0.0,
+        scouted_empty: false,
+        enemy_confirmed: false,
+        distances: FlowField::default(),
+      })
+      .collect();
+    normalize(&mut state);
+    state
+  }
+
+  #[test]
+  fn building_in_the_start_region_confirms_it() {
+    let mut state = state_with_candidates();
+    state.enemy_starts.candidates[1].scouted_empty = true;
+    state.enemy_starts.candidates[1].weight = 0.0;
+
+    observe_enemy_building(&mut state, 7, tile(45, 18));
+    normalize(&mut state);
+
+    let right = &state.enemy_starts.candidates[1];
+    assert!(right.enemy_confirmed);
+    assert!(!right.scouted_empty);
+    assert_eq!(right.weight, CONFIRMED_WEIGHT);
+    assert_eq!(state.enemy_starts.most_likely_enemy_main, Some(tile(50, 5)));
+  }
+
+  #[test]
+  fn nearby_building_in_another_region_only_hints() {
+    let mut state = state_with_candidates();
+
+    // Two tiles from the left start location, but across the region border
+    observe_enemy_building(&mut state, 7, tile(21, 5));
+    normalize(&mut state);
+
+    let left = &state.enemy_starts.candidates[0];
+    assert!(!left.enemy_confirmed);
+    assert_eq!(left.weight, DISTANT_BUILDING_WEIGHT);
+    assert!((left.probability - 2.0 / 3.0).abs() < 1e-6);
+  }
+
+  #[test]
+  fn distant_buildings_skip_scouted_start_locations() {
+    let mut state = state_with_candidates();
+    state.enemy_starts.candidates[0].scouted_empty = true;
+    state.enemy_starts.candidates[0].weight = 0.0;
+
+    observe_enemy_building(&mut state, 7, tile(30, 5));
+    // The same building seen again is no new evidence
+    observe_enemy_building(&mut state, 7, tile(30, 5));
+
+    assert_eq!(state.enemy_starts.candidates[0].weight, 0.0);
+    assert_eq!(
+      state.enemy_starts.candidates[1].weight,
+      DISTANT_BUILDING_WEIGHT
+    );
+  }
+
+  #[test]
+  fn no_likely_main_once_every_start_location_is_empty() {
+    let mut state = state_with_candidates();
+    for candidate in state.enemy_starts.candidates.iter_mut() {
+      candidate.scouted_empty = true;
+      candidate.weight = 0.0;
+    }
+
+    normalize(&mut state);
+
+    assert!(state
+      .enemy_starts
+      .candidates
+      .iter()
+      .all(|c| c.probability == 0.0));
+    assert_eq!(state.enemy_starts.most_likely_enemy_main, None);
+  }
+}
@@ -0,0 +1,407 @@
+use crate::{
+  log_info,
+  logging::LogTarget,
+  state::{enemy_memory::EnemyMemory, game_state::GameState},
+  types::{TilePosition, UnitType},
+};
+
+/// Game seconds are 24 frames at fastest speed
+const FRAMES_PER_SECOND: i32 = 24;
+const RECOGNITION_INTERVAL_FRAMES: i32 = 24;
+const PROXY_DISTANCE_TILES: i32 = 30;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum EnemyOpening {
+  #[default]
+  Unknown,
+  FourPool,
+  TwoGateZealot,
+  FastExpand,
+  ProxyBarracks,
+  ProxyGateway,
+  EarlyFactory,
+}
+
+impl EnemyOpening {
+  pub fn name(&self) -> &'static str {
+    match self {
+      EnemyOpening::Unknown => "Unknown",
+      EnemyOpening::FourPool => "4-Pool",
+      EnemyOpening::TwoGateZealot => "2-Gate Zealot",
+      EnemyOpening::FastExpand => "Fast Expand",
+      EnemyOpening::ProxyBarracks => "Proxy Barracks",
+      EnemyOpening::ProxyGateway => "Proxy Gateway",
+      EnemyOpening::EarlyFactory => "Early Factory",
+    }
+  }
+
+  pub fn is_rush(&self) -> bool {
+    matches!(
+      self,
+      EnemyOpening::FourPool
+        | EnemyOpening::TwoGateZealot
+        | EnemyOpening::ProxyBarracks
+        | EnemyOpening::ProxyGateway
+    )
+  }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct StrategyHypothesis {
+  pub opening: EnemyOpening,
+  pub confidence: f32,
+  pub evidence: String,
+}
+
+pub fn on_frame(frame: i32, state: &mut GameState) {
+  if frame % RECOGNITION_INTERVAL_FRAMES != 0 {
+    return;
+  }
+
+  let enemy_starts: Vec<TilePosition> = state
+    .enemy_starts
+    .candidates
+    .iter()
+    .filter(|c| !c.scouted_empty)
+    .map(|c| c.tile)
+    .collect();
+
+  let hypothesis = classify(&state.enemy_memory, &enemy_starts);
+
+  // Keep the strongest belief; an opening does not stop being true once it is seen
+  let current_opening = state.enemy_strategy.opening;
+  let current_confidence = state.enemy_strategy.confidence;
+  if hypothesis.opening != current_opening && hypothesis.confidence > current_confidence {
+    log_info!(
+      LogTarget::Strategy,
+      "Enemy opening looks like {} ({:.0}%): {}",
+      hypothesis.opening.name(),
+      hypothesis.confidence * 100.0,
+      hypothesis.evidence
+    );
+    state.enemy_strategy = hypothesis;
+  } else if hypothesis.opening == current_opening {
+    state.enemy_strategy.confidence = current_confidence.max(hypothesis.confidence);
+  }
+}
+
+/// Matches first-seen timings of enemy structures and units against known openings
+/// and returns the most confident match.
+pub fn classify(memory: &EnemyMemory, enemy_starts: &[TilePosition]) -> StrategyHypothesis {
+  let candidates = [
+    four_pool(memory),
+    two_gate_zealot(memory),
+    fast_expand(memory, enemy_starts),
+    proxy(memory, enemy_starts, UnitType::Terran_Barracks),
+    proxy(memory, enemy_starts, UnitType::Protoss_Gateway),
+    early_factory(memory),
+  ];
+
+  candidates
+    .into_iter()
+    .flatten()
+    .max_by(|a, b| a.confidence.total_cmp(&b.confidence))
+    .unwrap_or_default()
+}
+
+fn seconds(frame: i32) -> i32 {
+  frame / FRAMES_PER_SECOND
+}
+
+fn four_pool(memory: &EnemyMemory) -> Option<StrategyHypothesis> {
+  if let Some(frame) = memory.first_seen_frame(UnitType::Zerg_Zergling) {
+    if seconds(frame) < 165 {
+      return Some(StrategyHypothesis {
+        opening: EnemyOpening::FourPool,
+        confidence: 0.9,
+        evidence: format!("Zerglings seen at {}s", seconds(frame)),
+      });
+    }
+  }
+
+  let frame = memory.first_seen_frame(UnitType::Zerg_Spawning_Pool)?;
+  (seconds(frame) < 105).then(|| StrategyHypothesis {
+    opening: EnemyOpening::FourPool,
+    confidence: 0.7,
+    evidence: format!("Spawning Pool seen at {}s", seconds(frame)),
+  })
+}
+
+fn two_gate_zealot(memory: &EnemyMemory) -> Option<StrategyHypothesis> {
+  let early_gateways = memory
+    .units
+    .values()
+    .filter(|info| {
+      info.unit_type == UnitType::Protoss_Gateway && seconds(info.first_seen_frame) < 180
+    })
+    .count();
+  let has_gas = memory.count_of_type(UnitType::Protoss_Assimilator) > 0;
+
+  if early_gateways >= 2 && !has_gas {
+    return Some(StrategyHypothesis {
+      opening: EnemyOpening::TwoGateZealot,
+      confidence: 0.8,
+      evidence: format!("{} Gateways and no gas before 3:00", early_gateways),
+    });
+  }
+
+  let early_zealots = memory
+    .units
+    .values()
+    .filter(|info| {
+      info.unit_type == UnitType::Protoss_Zealot && seconds(info.first_seen_frame) < 210
+    })
+    .count();
+  (early_zealots >= 3).then(|| StrategyHypothesis {
+    opening: EnemyOpening::TwoGateZealot,
+    confidence: 0.7,
+    evidence: format!("{} Zealots before 3:30", early_zealots),
+  })
+}
+
+fn fast_expand(memory: &EnemyMemory, enemy_starts: &[TilePosition]) -> Option<StrategyHypothesis> {
+  let expansion = memory.buildings().find(|info| {
+    info.unit_type.is_resource_depot()
+      && seconds(info.first_seen_frame) < 240
+      && !enemy_starts
+        .iter()
+        .any(|start| is_within(info.last_tile(), *start, 10))
+  })?;
+
+  Some(StrategyHypothesis {
+    opening: EnemyOpening::FastExpand,
+    confidence: 0.8,
+    evidence: format!(
+      "{} outside the main at {}s",
+      expansion.unit_type.name(),
+      seconds(expansion.first_seen_frame)
+    ),
+  })
+}
+
+fn proxy(
+  memory: &EnemyMemory,
+  enemy_starts: &[TilePosition],
+  unit_type: UnitType,
+) -> Option<StrategyHypothesis> {
+  if enemy_starts.is_empty() {
+    return None;
+  }
+
+  let proxy_building = memory.buildings().find(|info| {
+    info.unit_type == unit_type
+      && seconds(info.first_seen_frame) < 180
+      && !enemy_starts
+        .iter()
+        .any(|start| is_within(info.last_tile(), *start, PROXY_DISTANCE_TILES))
+  })?;
+
+  let opening = if unit_type == UnitType::Terran_Barracks {
+    EnemyOpening::ProxyBarracks
+  } else {
+    EnemyOpening::ProxyGateway
+  };
+
+  Some(StrategyHypothesis {
+    opening,
+    confidence: 0.85,
+    evidence: format!(
+      "{} far from enemy start at {}s",
+      unit_type.name(),
+      seconds(proxy_building.first_seen_frame)
+    ),
+  })
+}
+
+fn early_factory(memory: &EnemyMemory) -> Option<StrategyHypothesis> {
+  let frame = memory.first_seen_frame(UnitType::Terran_Factory)?;
+  let confidence = match seconds(frame) {
+    s if s < 195 => 0.9,
+    s if s < 240 => 0.75,
+    _ => return None,
+  };
+
+  Some(StrategyHypothesis {
+    opening: EnemyOpening::EarlyFactory,
+    confidence,
+    evidence: format!("Factory seen at {}s", seconds(frame)),
+  })
+}
+
+fn is_within(tile: TilePosition, start: TilePosition, range: i32) -> bool {
+  (tile.x - start.x).abs() <= range && (tile.y - start.y).abs() <= range
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::state::enemy_memory::EnemyUnitInfo;
+  use crate::types::Position;
+
+  const ENEMY_START: TilePosition = TilePosition { x: 10, y: 10 };
+  const FAR_AWAY: (i32, i32) = (90, 90);
+
+  /// Enemy memory holding `unit_type` units first seen at the given game seconds
+  fn memory(seen: &[(UnitType, (i32, i32), i32)]) -> EnemyMemory {
+    let mut memory = EnemyMemory::default();
+    for (unit_id, &(unit_type, (x, y), second)) in seen.iter().enumerate() {
+      memory.units.insert(
+        unit_id,
+        EnemyUnitInfo {
+          unit_id,
+          unit_type,
+          owner_id: 1,
+          last_position: Position {
+            x: x * 32 + 16,
+            y: y * 32 + 16,
+          },
+          first_seen_frame: second * FRAMES_PER_SECOND,
+          last_seen_frame: second * FRAMES_PER_SECOND,
+          hit_points: unit_type.max_hit_points(),
+          shields: unit_type.max_shields(),
+          is_completed: true,
+          is_visible: true,
+          position_known: true,
+          believed_dead: false,
+        },
+      );
+    }
+    memory
+  }
+
+  fn opening(seen: &[(UnitType, (i32, i32), i32)]) -> EnemyOpening {
+    classify(&memory(seen), &[ENEMY_START]).opening
+  }
+
+  #[test]
+  fn unknown_without_evidence() {
+    let hypothesis = classify(&EnemyMemory::default(), &[ENEMY_START]);
+
+    assert_eq!(hypothesis.opening, EnemyOpening::Unknown);
+    assert_eq!(hypothesis.confidence, 0.0);
+  }
+
+  #[test]
+  fn four_pool_from_early_zerglings_or_pool() {
+    let main = (12, 12);
+    assert_eq!(
+      opening(&[(UnitType::Zerg_Zergling, main, 164)]),
+      EnemyOpening::FourPool
+    );
+    assert_eq!(
+      opening(&[(UnitType::Zerg_Zergling, main, 165)]),
+      EnemyOpening::Unknown
+    );
+    assert_eq!(
+      opening(&[(UnitType::Zerg_Spawning_Pool, main, 104)]),
+      EnemyOpening::FourPool
+    );
+    assert_eq!(
+      opening(&[(UnitType::Zerg_Spawning_Pool, main, 105)]),
+      EnemyOpening::Unknown
+    );
+  }
+
+  #[test]
+  fn two_gateways_before_three_minutes() {
+    let main = (12, 12);
+    assert_eq!(
+      opening(&[
+        (UnitType::Protoss_Gateway, main, 150),
+        (UnitType::Protoss_Gateway, main, 179),
+      ]),
+      EnemyOpening::TwoGateZealot
+    );
+    assert_eq!(
+      opening(&[
+        (UnitType::Protoss_Gateway, main, 150),
+        (UnitType::Protoss_Gateway, main, 180),
+      ]),
+      EnemyOpening::Unknown
+    );
+    assert_eq!(
+      opening(&[
+        (UnitType::Protoss_Gateway, main, 150),
+        (UnitType::Protoss_Gateway, main, 170),
+        (UnitType::Protoss_Assimilator, main, 160),
+      ]),
+      EnemyOpening::Unknown
+    );
+  }
+
+  #[test]
+  fn two_gate_from_early_zealots() {
+    let zealots = |second| {
+      opening(&[
+        (UnitType::Protoss_Zealot, FAR_AWAY, 200),
+        (UnitType::Protoss_Zealot, FAR_AWAY, 205),
+        (UnitType::Protoss_Zealot, FAR_AWAY, second),
+      ])
+    };
+
+    assert_eq!(zealots(209), EnemyOpening::TwoGateZealot);
+    assert_eq!(zealots(210), EnemyOpening::Unknown);
+  }
+
+  #[test]
+  fn fast_expand_from_a_depot_outside_the_main() {
+    assert_eq!(
+      opening(&[(UnitType::Protoss_Nexus, (40, 40), 239)]),
+      EnemyOpening::FastExpand
+    );
+    assert_eq!(
+      opening(&[(UnitType::Protoss_Nexus, (40, 40), 240)]),
+      EnemyOpening::Unknown
+    );
+    assert_eq!(
+      opening(&[(UnitType::Protoss_Nexus, (15, 15), 200)]),
+      EnemyOpening::Unknown
+    );
+  }
+
+  #[test]
+  fn proxy_barracks_before_three_minutes() {
+    assert_eq!(
+      opening(&[(UnitType::Terran_Barracks, FAR_AWAY, 179)]),
+      EnemyOpening::ProxyBarracks
+    );
+    assert_eq!(
+      opening(&[(UnitType::Terran_Barracks, FAR_AWAY, 180)]),
+      EnemyOpening::Unknown
+    );
+    assert_eq!(
+      opening(&[(UnitType::Terran_Barracks, (14, 14), 100)]),
+      EnemyOpening::Unknown
+    );
+  }
+
+  #[test]
+  fn proxy_gateway_before_three_minutes() {
+    assert_eq!(
+      opening(&[(UnitType::Protoss_Gateway, FAR_AWAY, 179)]),
+      EnemyOpening::ProxyGateway
+    );
+    assert_eq!(
+      opening(&[(UnitType::Protoss_Gateway, FAR_AWAY, 180)]),
+      EnemyOpening::Unknown
+    );
+  }
+
+  #[test]
+  fn proxies_need_a_known_enemy_start() {
+    let seen = memory(&[(UnitType::Terran_Barracks, FAR_AWAY, 100)]);
+
+    assert_eq!(classify(&seen, &[]).opening, EnemyOpening::Unknown);
+  }
+
+  #[test]
+  fn early_factory_confidence_drops_with_time() {
+    let main = (12, 12);
+    let factory = |second| classify(&memory(&[(UnitType::Terran_Factory, main, second)]), &[]);
+
+    assert_eq!(factory(194).opening, EnemyOpening::EarlyFactory);
+    assert_eq!(factory(194).confidence, 0.9);
+    assert_eq!(factory(195).confidence, 0.75);
+    assert_eq!(factory(240).opening, EnemyOpening::Unknown);
+  }
+}
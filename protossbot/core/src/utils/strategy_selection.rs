@@ -0,0 +1,248 @@
+//! Picks the opening for a game from how each one did in earlier games against the same
+//! opponent. Every opening is an arm of a multi-armed bandit scored by win rate; UCB1
+//! balances replaying the best one against trying the others.
+
+use rand::Rng;
+
+use crate::{
+  log_info,
+  logging::LogTarget,
+  state::{build_stages, game_state::GameState},
+  utils::results_store::GameResult,
+};
+
+/// How much UCB1 favours rarely played openings, √2 is the textbook value
+pub const UCB_EXPLORATION: f32 = std::f32::consts::SQRT_2;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SelectionPolicy {
+  /// Highest win rate plus a bonus that shrinks as an opening gets played
+  Ucb1 { exploration: f32 },
+  /// Highest win rate, except a random opening `epsilon` of the time
+  EpsilonGreedy { epsilon: f32 },
+}
+
+pub const DEFAULT_POLICY: SelectionPolicy = SelectionPolicy::Ucb1 {
+  exploration: UCB_EXPLORATION,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StrategyChoice {
+  pub strategy: &'static str,
+  pub reason: String,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct ArmStats {
+  games: u32,
+  wins: u32,
+}
+
+impl ArmStats {
+  fn win_rate(&self) -> f32 {
+    self.wins as f32 / self.games as f32
+  }
+}
+
+/// Earlier games against this opponent by name, or against its race when we have never
+/// played it, with a description of which was used
+pub fn opponent_history<'a>(
+  results: &'a [GameResult],
+  opponent: &str,
+  race: &str,
+) -> (Vec<&'a GameResult>, String) {
+  let by_name: Vec<&GameResult> = results.iter().filter(|r| r.opponent == opponent).collect();
+  if !by_name.is_empty() {
+    return (by_name, opponent.to_string());
+  }
+
+  let by_race = results.iter().filter(|r| r.opponent_race == race).collect();
+  (by_race, race.to_string())
+}
+
+pub fn choose(
+  strategies: &[&'static str],
+  history: &[&GameResult],
+  against: &str,
+  policy: SelectionPolicy,
+  rng: &mut impl Rng,
+) -> StrategyChoice {
+  let arms: Vec<(&'static str, ArmStats)> = strategies
+    .iter()
+    .map(|strategy| {
+      let played: Vec<&&GameResult> = history.iter().filter(|r| r.strategy == *strategy).collect();
+      let stats = ArmStats {
+        games: played.len() as u32,
+        wins: played.iter().filter(|r| r.won).count() as u32,
+      };
+      (*strategy, stats)
+    })
+    .collect();
+
+  if let Some((strategy, _)) = arms.iter().find(|(_, stats)| stats.games == 0) {
+    return StrategyChoice {
+      strategy,
+      reason: format!("never played against {}", against),
+    };
+  }
+
+  let describe =
+    |stats: &ArmStats| format!("{}/{} wins against {}", stats.wins, stats.games, against);
+  let best = |score: &dyn Fn(&ArmStats) -> f32| {
+    arms
+      .iter()
+      .max_by(|a, b| score(&a.1).total_cmp(&score(&b.1)))
+      .copied()
+      .expect("there is at least one strategy")
+  };
+
+  match policy {
+    SelectionPolicy::Ucb1 { exploration } => {
+      let total_games: u32 = arms.iter().map(|(_, stats)| stats.games).sum();
+      let ucb = |stats: &ArmStats| {
+        stats.win_rate() + exploration * ((total_games as f32).ln() / stats.games as f32).sqrt()
+      };
+      let (strategy, stats) = best(&ucb);
+      StrategyChoice {
+        strategy,
+        reason: format!("best UCB score {:.2}, {}", ucb(&stats), describe(&stats)),
+      }
+    }
+    SelectionPolicy::EpsilonGreedy { epsilon } => {
+      if rng.gen::<f32>() < epsilon {
+        let (strategy, stats) = arms[rng.gen_range(0..arms.len())];
+        return StrategyChoice {
+          strategy,
+          reason: format!(
+            "exploring ({:.0}% of games), {}",
+            epsilon * 100.0,
+            describe(&stats)
+          ),
+        };
+      }
+      let (strategy, stats) = best(&ArmStats::win_rate);
+      StrategyChoice {
+        strategy,
+        reason: format!("best win rate, {}", describe(&stats)),
+      }
+    }
+  }
+}
+
+/// Chooses this game's opening from `results` and loads its build stages
+pub fn on_start(opponent: &str, race: &str, results: &[GameResult], state: &mut GameState) {
+  let (history, against) = opponent_history(results, opponent, race);
+  let choice = choose(
+    &build_stages::STRATEGIES,
+    &history,
+    &against,
+    DEFAULT_POLICY,
+    &mut rand::thread_rng(),
+  );
+
+  log_info!(
+    LogTarget::Strategy,
+    "Playing {} against {} ({}): {}",
+    choice.strategy,
+    opponent,
+    race,
+    choice.reason
+  );
+  state.strategy = choice.strategy;
+  state.build_stages = build_stages::get_strategy_stages(choice.strategy);
+  state.current_stage_index = 0;
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use rand::{rngs::StdRng, SeedableRng};
+
+  const STRATEGIES: [&str; 2] = ["safe", "greedy"];
+
+  fn game(opponent: &str, race: &str, strategy: &str, won: bool) -> GameResult {
+    GameResult {
+      started_at: 0,
+      map: "Python".to_string(),
+      opponent: opponent.to_string(),
+      opponent_race: race.to_string(),
+      strategy: strategy.to_string(),
+      won,
+      duration_frames: 0,
+    }
+  }
+
+  fn pick(results: &[GameResult], policy: SelectionPolicy) -> StrategyChoice {
+    let (history, against) = opponent_history(results, "UAlbertaBot", "Zerg");
+    choose(
+      &STRATEGIES,
+      &history,
+      &against,
+      policy,
+      &mut StdRng::seed_from_u64(7),
+    )
+  }
+
+  #[test]
+  fn tries_every_strategy_first() {
+    let results = vec![game("UAlbertaBot", "Zerg", "safe", true)];
+
+    let choice = pick(&results, DEFAULT_POLICY);
+
+    assert_eq!(choice.strategy, "greedy");
+    assert_eq!(choice.reason, "never played against UAlbertaBot");
+  }
+
+  #[test]
+  fn ucb_replays_the_winning_strategy() {
+    let mut results = Vec::new();
+    for _ in 0..5 {
+      results.push(game("UAlbertaBot", "Zerg", "safe", false));
+      results.push(game("UAlbertaBot", "Zerg", "greedy", true));
+    }
+
+    assert_eq!(pick(&results, DEFAULT_POLICY).strategy, "greedy");
+  }
+
+  #[test]
+  fn ucb_revisits_a_rarely_played_strategy() {
+    let mut results = vec![game("UAlbertaBot", "Zerg", "greedy", false)];
+    for _ in 0..40 {
+      results.push(game("UAlbertaBot", "Zerg", "safe", true));
+      results.push(game("UAlbertaBot", "Zerg", "safe", false));
+    }
+
+    assert_eq!(pick(&results, DEFAULT_POLICY).strategy, "greedy");
+  }
+
+  #[test]
+  fn falls_back_to_games_against_the_same_race() {
+    let results = vec![
+      game("Other", "Zerg", "safe", false),
+      game("Other", "Zerg", "greedy", true),
+      game("Terran Bot", "Terran", "safe", true),
+    ];
+
+    let (history, against) = opponent_history(&results, "UAlbertaBot", "Zerg");
+
+    assert_eq!(history.len(), 2);
+    assert_eq!(against, "Zerg");
+  }
+
+  #[test]
+  fn greedy_without_exploration_takes_the_best_win_rate() {
+    let results = vec![
+      game("UAlbertaBot", "Zerg", "safe", true),
+      game("UAlbertaBot", "Zerg", "greedy", false),
+    ];
+
+    let choice = pick(&results, SelectionPolicy::EpsilonGreedy { epsilon: 0.0 });
+
+    assert_eq!(choice.strategy, "safe");
+    assert!(
+      choice.reason.starts_with("best win rate"),
+      "{}",
+      choice.reason
+    );
+  }
+}
@@ -0,0 +1,165 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::{log_info, log_warn, logging::LogTarget};
+
+const DEFAULT_TABLE: &str = include_str!("../../config/target_priorities.yaml");
+/// Optional override next to the bot, same format as the built-in table
+pub const TARGET_PRIORITY_PATH: &str = "target_priorities.yaml";
+
+/// Attacker type × target type → score table used to pick targets in combat micro.
+/// Unit types are keyed by name so the table can live in a config file.
+#[derive(Clone, Debug, Deserialize)]
+pub struct TargetPriorityTable {
+  #[serde(default = "default_score")]
+  pub default_score: f32,
+  #[serde(default)]
+  pub targets: HashMap<String, f32>,
+  #[serde(default)]
+  pub matchups: HashMap<String, HashMap<String, f32>>,
+}
+
+fn default_score() -> f32 {
+  1.0
+}
+
+impl Default for TargetPriorityTable {
+  fn default() -> Self {
+    Self::from_yaml(DEFAULT_TABLE).expect("built-in target priority table is valid")
+  }
+}
+
+impl TargetPriorityTable {
+  pub fn from_yaml(text: &str) -> Result<Self, serde_yaml::Error> {
+    let table: TargetPriorityTable = serde_yaml::from_str(text)?;
+    Ok(table.normalized())
+  }
+
+  /// Loads the table from `path`, falling back to the built-in one when the file is
+  /// missing or invalid.
+  pub fn load(path: &str) -> Self {
+    let Ok(text) = std::fs::read_to_string(path) else {
+      return Self::default();
+    };
+
+    match Self::from_yaml(&text) {
+      Ok(table) => {
+        log_info!(LogTarget::Game, "Loaded target priorities from {}", path);
+        table
+      }
+      Err(e) => {
+        log_warn!(
+          LogTarget::Game,
+          "Invalid target priorities in {}: {}, using defaults",
+          path,
+          e
+        );
+        Self::default()
+      }
+    }
+  }
+
+  pub fn score(&self, attacker: &str, target: &str) -> f32 {
+    let target = normalize(target);
+    self
+      .matchups
+      .get(&normalize(attacker))
+      .and_then(|scores| scores.get(&target))
+      .or_else(|| self.targets.get(&target))
+      .copied()
+      .unwrap_or(self.default_score)
+  }
+
+  fn normalized(self) -> Self {
+    let normalize_scores = |scores: HashMap<String, f32>| {
+      scores
+        .into_iter()
+        .map(|(name, score)| (normalize(&name), score))
+        .collect()
+    };
+
+    Self {
+      default_score: self.default_score,
+      targets: normalize_scores(self.targets),
+      matchups: self
+        .matchups
+        .into_iter()
+        .map(|(attacker, scores)| (normalize(&attacker), normalize_scores(scores)))
+        .collect(),
+    }
+  }
+}
+
+fn normalize(name: &str) -> String {
+  name.trim().replace(' ', "_").to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn built_in_table_loads() {
+    let table = TargetPriorityTable::default();
+
+    assert!(table.score("Protoss_Zealot", "Protoss_High_Templar") > 0.0);
+    assert!(!table.matchups.is_empty());
+  }
+
+  #[test]
+  fn dragoons_prefer_vultures_over_marines() {
+    let table = TargetPriorityTable::default();
+
+    assert!(
+      table.score("Protoss_Dragoon", "Terran_Vulture")
+        > table.score("Protoss_Dragoon", "Terran_Marine")
+    );
+  }
+
+  #[test]
+  fn matchup_overrides_general_target_score() {
+    let table = TargetPriorityTable::from_yaml(
+      "
+targets:
+  Terran_Marine: 4
+matchups:
+  Protoss_Zealot:
+    Terran_Marine: 7
+",
+    )
+    .unwrap();
+
+    assert_eq!(table.score("Protoss_Zealot", "Terran_Marine"), 7.0);
+    assert_eq!(table.score("Protoss_Dragoon", "Terran_Marine"), 4.0);
+  }
+
+  #[test]
+  fn unknown_targets_use_default_score() {
+    let table = TargetPriorityTable::from_yaml("default_score: 0.5").unwrap();
+
+    assert_eq!(table.score("Protoss_Zealot", "Zerg_Ultralisk"), 0.5);
+  }
+
+  #[test]
+  fn names_match_with_spaces_or_underscores() {
+    let table = TargetPriorityTable::from_yaml("targets:\n  Zerg Lurker: 8").unwrap();
+
+    assert_eq!(table.score("Protoss Dragoon", "Zerg_Lurker"), 8.0);
+    assert_eq!(table.score("protoss_dragoon", "zerg lurker"), 8.0);
+  }
+
+  #[test]
+  fn invalid_yaml_is_an_error() {
+    assert!(TargetPriorityTable::from_yaml("targets: [1, 2").is_err());
+  }
+
+  #[test]
+  fn missing_file_falls_back_to_defaults() {
+    let table = TargetPriorityTable::load("does/not/exist.yaml");
+
+    assert_eq!(
+      table.score("Protoss_Dragoon", "Terran_Vulture"),
+      TargetPriorityTable::default().score("Protoss_Dragoon", "Terran_Vulture")
+    );
+  }
+}
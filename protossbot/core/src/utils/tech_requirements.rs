@@ -0,0 +1,47 @@
+//! Which buildings have to be finished before a Protoss unit or building can be started.
+//! BWAPI only reports a missing requirement once the command has failed, so the build
+//! planner and the economy simulator both check this table up front.
+
+use crate::types::UnitType;
+
+/// Buildings that have to be finished before `unit_type` can be started
+pub fn requirements(unit_type: UnitType) -> &'static [UnitType] {
+  match unit_type {
+    UnitType::Protoss_Photon_Cannon => &[UnitType::Protoss_Forge],
+    UnitType::Protoss_Cybernetics_Core | UnitType::Protoss_Shield_Battery => {
+      &[UnitType::Protoss_Gateway]
+    }
+    UnitType::Protoss_Robotics_Facility
+    | UnitType::Protoss_Stargate
+    | UnitType::Protoss_Citadel_of_Adun
+    | UnitType::Protoss_Dragoon => &[UnitType::Protoss_Cybernetics_Core],
+    UnitType::Protoss_Templar_Archives => &[UnitType::Protoss_Citadel_of_Adun],
+    UnitType::Protoss_Observatory | UnitType::Protoss_Robotics_Support_Bay => {
+      &[UnitType::Protoss_Robotics_Facility]
+    }
+    UnitType::Protoss_Fleet_Beacon => &[UnitType::Protoss_Stargate],
+    UnitType::Protoss_Arbiter_Tribunal => &[
+      UnitType::Protoss_Templar_Archives,
+      UnitType::Protoss_Stargate,
+    ],
+    UnitType::Protoss_High_Templar | UnitType::Protoss_Dark_Templar => {
+      &[UnitType::Protoss_Templar_Archives]
+    }
+    UnitType::Protoss_Observer => &[UnitType::Protoss_Observatory],
+    UnitType::Protoss_Reaver => &[UnitType::Protoss_Robotics_Support_Bay],
+    UnitType::Protoss_Carrier => &[UnitType::Protoss_Fleet_Beacon],
+    UnitType::Protoss_Arbiter => &[UnitType::Protoss_Arbiter_Tribunal],
+    _ => &[],
+  }
+}
+
+/// First requirement of `unit_type` for which `is_complete` finds no finished building
+pub fn missing_requirement(
+  unit_type: UnitType,
+  is_complete: impl Fn(UnitType) -> bool,
+) -> Option<UnitType> {
+  requirements(unit_type)
+    .iter()
+    .copied()
+    .find(|required| !is_complete(*required))
+}
@@ -0,0 +1,790 @@
+#[cfg(feature = "bwapi")]
+use rsbwapi::Game;
+use std::collections::{HashMap, VecDeque};
+
+#[cfg(feature = "bwapi")]
+use crate::types::WalkPosition;
+use crate::{log_info, logging::LogTarget, types::TilePosition};
+
+const TILE_SIZE: i32 = 32;
+const MIN_REGION_TILES: usize = 40;
+const MIN_CHOKEPOINT_TILES: usize = 2;
+const RESOURCE_CLUSTER_RANGE: i32 = 6;
+const MIN_MINERAL_AMOUNT: i32 = 50;
+const BASE_SEARCH_MARGIN: i32 = 10;
+const START_LOCATION_MATCH_RANGE: i32 = 12;
+pub const DEPOT_WIDTH: i32 = 4;
+pub const DEPOT_HEIGHT: i32 = 3;
+const DEPOT_RESOURCE_GAP: i32 = 3;
+
+#[derive(Clone, Debug)]
+pub struct Region {
+  pub id: usize,
+  pub center: TilePosition,
+  pub tile_count: usize,
+  pub max_altitude: i32,
+  pub chokepoint_ids: Vec<usize>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Chokepoint {
+  pub id: usize,
+  pub regions: (usize, usize),
+  pub center: TilePosition,
+  pub ends: (TilePosition, TilePosition),
+  /// Passable width in pixels, from the frontier extent or the clearance at its center
+  pub width: i32,
+}
+
+#[derive(Clone, Debug)]
+pub struct BaseLocation {
+  pub id: usize,
+  /// Top-left tile where a resource depot fits closest to the resources
+  pub tile: TilePosition,
+  pub region_id: Option<usize>,
+  pub minerals: Vec<TilePosition>,
+  pub geysers: Vec<TilePosition>,
+  pub total_minerals: i32,
+  pub total_gas: i32,
+  pub is_start_location: bool,
+}
+
+impl BaseLocation {
+  pub fn center(&self) -> crate::types::Position {
+    crate::types::Position {
+      x: self.tile.x * TILE_SIZE + DEPOT_WIDTH * TILE_SIZE / 2,
+      y: self.tile.y * TILE_SIZE + DEPOT_HEIGHT * TILE_SIZE / 2,
+    }
+  }
+}
+
+#[derive(Clone, Debug)]
+pub struct ResourceInfo {
+  pub tile: TilePosition,
+  pub tile_width: i32,
+  pub tile_height: i32,
+  pub amount: i32,
+  pub is_geyser: bool,
+}
+
+/// Tile-level snapshot of the map used as input for the analysis.
+#[derive(Clone, Debug, Default)]
+pub struct MapGrid {
+  pub width: i32,
+  pub height: i32,
+  pub walkable: Vec<bool>,
+  pub buildable: Vec<bool>,
+}
+
+impl MapGrid {
+  #[cfg(feature = "bwapi")]
+  pub fn from_game(game: &Game) -> Self {
+    let width = game.map_width();
+    let height = game.map_height();
+    let mut walkable = Vec::with_capacity((width * height) as usize);
+    let mut buildable = Vec::with_capacity((width * height) as usize);
+
+    for y in 0..height {
+      for x in 0..width {
+        // A tile only counts as walkable when all 4x4 walk cells inside it are
+        let tile_walkable = (0..4).all(|dy| {
+          (0..4).all(|dx| {
+            game.is_walkable(WalkPosition {
+              x: x * 4 + dx,
+              y: y * 4 + dy,
+            })
+          })
+        });
+        walkable.push(tile_walkable);
+        buildable.push(game.is_buildable(TilePosition { x, y }));
+      }
+    }
+
+    Self {
+      width,
+      height,
+      walkable,
+      buildable,
+    }
+  }
+
+  fn index(&self, x: i32, y: i32) -> Option<usize> {
+    if x < 0 || y < 0 || x >= self.width || y >= self.height {
+      return None;
+    }
+    Some((y * self.width + x) as usize)
+  }
+
+  pub fn is_walkable(&self, x: i32, y: i32) -> bool {
+    self.index(x, y).map(|i| self.walkable[i]).unwrap_or(false)
+  }
+
+  pub fn is_buildable(&self, x: i32, y: i32) -> bool {
+    self.index(x, y).map(|i| self.buildable[i]).unwrap_or(false)
+  }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TerrainAnalysis {
+  pub grid: MapGrid,
+  /// Distance in tiles from each walkable tile to the nearest unwalkable one
+  pub altitude: Vec<i32>,
+  pub region_map: Vec<Option<usize>>,
+  pub regions: Vec<Region>,
+  pub chokepoints: Vec<Chokepoint>,
+  pub base_locations: Vec<BaseLocation>,
+}
+
+impl TerrainAnalysis {
+  pub fn region_at(&self, tile: TilePosition) -> Option<usize> {
+    self
+      .grid
+      .index(tile.x, tile.y)
+      .and_then(|i| self.region_map[i])
+  }
+
+  pub fn is_walkable(&self, tile: TilePosition) -> bool {
+    self.grid.is_walkable(tile.x, tile.y)
+  }
+
+  pub fn base_at(&self, tile: TilePosition) -> Option<&BaseLocation> {
+    self.base_locations.iter().find(|b| b.tile == tile)
+  }
+
+  pub fn chokepoints_of_region(&self, region_id: usize) -> Vec<&Chokepoint> {
+    self
+      .chokepoints
+      .iter()
+      .filter(|c| c.regions.0 == region_id || c.regions.1 == region_id)
+      .collect()
+  }
+}
+
+#[cfg(feature = "bwapi")]
+pub fn analyze_game(game: &Game) -> TerrainAnalysis {
+  let grid = MapGrid::from_game(game);
+
+  let resources: Vec<ResourceInfo> = game
+    .get_static_minerals()
+    .iter()
+    .map(|m| ResourceInfo {
+      tile: m.get_initial_tile_position(),
+      tile_width: m.get_type().tile_width(),
+      tile_height: m.get_type().tile_height(),
+      amount: m.get_initial_resources(),
+      is_geyser: false,
+    })
+    .chain(game.get_static_geysers().iter().map(|g| ResourceInfo {
+      tile: g.get_initial_tile_position(),
+      tile_width: g.get_type().tile_width(),
+      tile_height: g.get_type().tile_height(),
+      amount: g.get_initial_resources(),
+      is_geyser: true,
+    }))
+    .collect();
+
+  analyze(grid, &resources, &game.get_start_locations())
+}
+
+pub fn analyze(
+  grid: MapGrid,
+  resources: &[ResourceInfo],
+  start_locations: &[TilePosition],
+) -> TerrainAnalysis {
+  let altitude = compute_altitude(&grid);
+  let (region_map, regions, frontier) = compute_regions(&grid, &altitude);
+  let mut analysis = TerrainAnalysis {
+    grid,
+    altitude,
+    region_map,
+    regions,
+    chokepoints: Vec::new(),
+    base_locations: Vec::new(),
+  };
+
+  analysis.chokepoints = compute_chokepoints(&analysis, &frontier);
+  for choke in &analysis.chokepoints {
+    analysis.regions[choke.regions.0]
+      .chokepoint_ids
+      .push(choke.id);
+    analysis.regions[choke.regions.1]
+      .chokepoint_ids
+      .push(choke.id);
+  }
+
+  analysis.base_locations = compute_base_locations(&analysis, resources, start_locations);
+
+  log_info!(
+    LogTarget::Game,
+    "Terrain analysis: {} regions, {} chokepoints, {} base locations",
+    analysis.regions.len(),
+    analysis.chokepoints.len(),
+    analysis.base_locations.len()
+  );
+
+  analysis
+}
+
+fn compute_altitude(grid: &MapGrid) -> Vec<i32> {
+  let mut altitude = vec![-1; (grid.width * grid.height) as usize];
+  let mut queue = VecDeque::new();
+
+  for y in 0..grid.height {
+    for x in 0..grid.width {
+      let i = grid.index(x, y).unwrap();
+      if !grid.walkable[i] {
+        altitude[i] = 0;
+        queue.push_back((x, y));
+      } else if x == 0 || y == 0 || x == grid.width - 1 || y == grid.height - 1 {
+        // The map border behaves like a wall
+        altitude[i] = 1;
+        queue.push_back((x, y));
+      }
+    }
+  }
+
+  while let Some((x, y)) = queue.pop_front() {
+    let current = altitude[grid.index(x, y).unwrap()];
+    for (dx, dy) in NEIGHBORS_8 {
+      let Some(i) = grid.index(x + dx, y + dy) else {
+        continue;
+      };
+      if altitude[i] == -1 {
+        altitude[i] = current + 1;
+        queue.push_back((x + dx, y + dy));
+      }
+    }
+  }
+
+  altitude
+}
+
+const NEIGHBORS_4: [(i32, i32); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+const NEIGHBORS_8: [(i32, i32); 8] = [
+  (1, 0),
+  (-1, 0),
+  (0, 1),
+  (0, -1),
+  (1, 1),
+  (1, -1),
+  (-1, 1),
+  (-1, -1),
+];
+
+struct RegionBuilder {
+  parent: Vec<usize>,
+  tile_count: Vec<usize>,
+  max_altitude: Vec<i32>,
+}
+
+impl RegionBuilder {
+  fn find(&mut self, id: usize) -> usize {
+    let mut root = id;
+    while self.parent[root] != root {
+      root = self.parent[root];
+    }
+    let mut current = id;
+    while self.parent[current] != root {
+      let next = self.parent[current];
+      self.parent[current] = root;
+      current = next;
+    }
+    root
+  }
+
+  fn create(&mut self, altitude: i32) -> usize {
+    let id = self.parent.len();
+    self.parent.push(id);
+    self.tile_count.push(0);
+    self.max_altitude.push(altitude);
+    id
+  }
+
+  fn merge(&mut self, a: usize, b: usize) -> usize {
+    let (big, small) = if self.tile_count[a] >= self.tile_count[b] {
+      (a, b)
+    } else {
+      (b, a)
+    };
+    self.parent[small] = big;
+    self.tile_count[big] += self.tile_count[small];
+    self.max_altitude[big] = self.max_altitude[big].max(self.max_altitude[small]);
+    big
+  }
+
+  fn is_separate_area(&self, id: usize, altitude: i32) -> bool {
+    self.tile_count[id] >= MIN_REGION_TILES && altitude * 3 <= self.max_altitude[id] * 2
+  }
+}
+
+/// A tile where two regions meet: x, y and the two region ids
+type FrontierTile = (i32, i32, usize, usize);
+
+/// Watershed from the highest-clearance tiles outwards. Two basins that meet at a
+/// tile noticeably narrower than both of their peaks stay separate regions and the
+/// meeting tile becomes part of a frontier; otherwise the smaller basin is absorbed.
+fn compute_regions(
+  grid: &MapGrid,
+  altitude: &[i32],
+) -> (Vec<Option<usize>>, Vec<Region>, Vec<FrontierTile>) {
+  let mut tiles: Vec<(i32, i32)> = (0..grid.height)
+    .flat_map(|y| (0..grid.width).map(move |x| (x, y)))
+    .filter(|&(x, y)| grid.is_walkable(x, y))
+    .collect();
+  tiles.sort_by_key(|&(x, y)| -altitude[grid.index(x, y).unwrap()]);
+
+  let mut builder = RegionBuilder {
+    parent: Vec::new(),
+    tile_count: Vec::new(),
+    max_altitude: Vec::new(),
+  };
+  let mut raw_map: Vec<Option<usize>> = vec![None; altitude.len()];
+  let mut raw_frontier = Vec::new();
+
+  for (x, y) in tiles {
+    let i = grid.index(x, y).unwrap();
+    let tile_altitude = altitude[i];
+
+    let mut neighbor_regions: Vec<usize> = Vec::new();
+    for (dx, dy) in NEIGHBORS_4 {
+      if let Some(raw) = grid.index(x + dx, y + dy).and_then(|n| raw_map[n]) {
+        let root = builder.find(raw);
+        if !neighbor_regions.contains(&root) {
+          neighbor_regions.push(root);
+        }
+      }
+    }
+
+    let region = match neighbor_regions.as_slice() {
+      [] => builder.create(tile_altitude),
+      [only] => *only,
+      [first, rest @ ..] => {
+        let mut region = *first;
+        for &other in rest {
+          let region_root = builder.find(region);
+          let other_root = builder.find(other);
+          if region_root == other_root {
+            continue;
+          }
+          if builder.is_separate_area(region_root, tile_altitude)
+            && builder.is_separate_area(other_root, tile_altitude)
+          {
+            raw_frontier.push((x, y, region_root, other_root));
+          } else {
+            region = builder.merge(region_root, other_root);
+          }
+        }
+        builder.find(region)
+      }
+    };
+
+    raw_map[i] = Some(region);
+    builder.tile_count[region] += 1;
+  }
+
+  // Compact the surviving roots into dense region ids
+  let mut id_for_root: HashMap<usize, usize> = HashMap::new();
+  let mut region_map = vec![None; raw_map.len()];
+  let mut sums: Vec<(i64, i64, usize, i32)> = Vec::new();
+
+  for y in 0..grid.height {
+    for x in 0..grid.width {
+      let i = grid.index(x, y).unwrap();
+      let Some(raw) = raw_map[i] else {
+        continue;
+      };
+      let root = builder.find(raw);
+      let next_id = id_for_root.len();
+      let id = *id_for_root.entry(root).or_insert(next_id);
+      if id == sums.len() {
+        sums.push((0, 0, 0, 0));
+      }
+      sums[id].0 += x as i64;
+      sums[id].1 += y as i64;
+      sums[id].2 += 1;
+      sums[id].3 = sums[id].3.max(altitude[i]);
+      region_map[i] = Some(id);
+    }
+  }
+
+  let regions = sums
+    .iter()
+    .enumerate()
+    .map(|(id, &(sum_x, sum_y, count, max_altitude))| Region {
+      id,
+      center: TilePosition {
+        x: (sum_x / count as i64) as i32,
+        y: (sum_y / count as i64) as i32,
+      },
+      tile_count: count,
+      max_altitude,
+      chokepoint_ids: Vec::new(),
+    })
+    .collect();
+
+  let frontier = raw_frontier
+    .into_iter()
+    .filter_map(|(x, y, a, b)| {
+      let a = id_for_root[&builder.find(a)];
+      let b = id_for_root[&builder.find(b)];
+      (a != b).then_some((x, y, a.min(b), a.max(b)))
+    })
+    .collect();
+
+  (region_map, regions, frontier)
+}
+
+fn compute_chokepoints(analysis: &TerrainAnalysis, frontier: &[FrontierTile]) -> Vec<Chokepoint> {
+  let mut by_pair: HashMap<(usize, usize), Vec<(i32, i32)>> = HashMap::new();
+  for &(x, y, a, b) in frontier {
+    by_pair.entry((a, b)).or_default().push((x, y));
+  }
+
+  let mut pairs: Vec<_> = by_pair.into_iter().collect();
+  pairs.sort_by_key(|(pair, _)| *pair);
+
+  let mut chokepoints = Vec::new();
+  for (pair, tiles) in pairs {
+    for component in connected_components(&tiles) {
+      if component.len() < MIN_CHOKEPOINT_TILES {
+        continue;
+      }
+
+      let ends = farthest_pair(&component);
+      let mid_x = (ends.0 .0 + ends.1 .0) / 2;
+      let mid_y = (ends.0 .1 + ends.1 .1) / 2;
+      let &(cx, cy) = component
+        .iter()
+        .min_by_key(|(x, y)| (x - mid_x).pow(2) + (y - mid_y).pow(2))
+        .unwrap();
+      let center_altitude = analysis.altitude[analysis.grid.index(cx, cy).unwrap()];
+      let extent = (ends.0 .0 - ends.1 .0)
+        .abs()
+        .max((ends.0 .1 - ends.1 .1).abs())
+        + 1;
+
+      chokepoints.push(Chokepoint {
+        id: chokepoints.len(),
+        regions: pair,
+        center: TilePosition { x: cx, y: cy },
+        ends: (
+          TilePosition {
+            x: ends.0 .0,
+            y: ends.0 .1,
+          },
+          TilePosition {
+            x: ends.1 .0,
+            y: ends.1 .1,
+          },
+        ),
+        width: (2 * center_altitude).max(extent) * TILE_SIZE,
+      });
+    }
+  }
+
+  chokepoints
+}
+
+fn connected_components(tiles: &[(i32, i32)]) -> Vec<Vec<(i32, i32)>> {
+  let mut remaining: Vec<(i32, i32)> = tiles.to_vec();
+  let mut components = Vec::new();
+
+  while let Some(seed) = remaining.pop() {
+    let mut component = vec![seed];
+    let mut queue = VecDeque::from([seed]);
+    while let Some((x, y)) = queue.pop_front() {
+      let mut i = 0;
+      while i < remaining.len() {
+        let (ox, oy) = remaining[i];
+        if (ox - x).abs() <= 1 && (oy - y).abs() <= 1 {
+          let tile = remaining.swap_remove(i);
+          component.push(tile);
+          queue.push_back(tile);
+        } else {
+          i += 1;
+        }
+      }
+    }
+    components.push(component);
+  }
+
+  components
+}
+
+fn farthest_pair(tiles: &[(i32, i32)]) -> ((i32, i32), (i32, i32)) {
+  let mut best = (tiles[0], tiles[0]);
+  let mut best_distance = -1;
+  for &a in tiles {
+    for &b in tiles {
+      let distance = (a.0 - b.0).pow(2) + (a.1 - b.1).pow(2);
+      if distance > best_distance {
+        best_distance = distance;
+        best = (a, b);
+      }
+    }
+  }
+  best
+}
+
+fn compute_base_locations(
+  analysis: &TerrainAnalysis,
+  resources: &[ResourceInfo],
+  start_locations: &[TilePosition],
+) -> Vec<BaseLocation> {
+  let relevant: Vec<&ResourceInfo> = resources
+    .iter()
+    .filter(|r| r.is_geyser || r.amount > MIN_MINERAL_AMOUNT)
+    .collect();
+
+  let mut bases = Vec::new();
+  for cluster in cluster_resources(&relevant) {
+    let mineral_count = cluster.iter().filter(|r| !r.is_geyser).count();
+    let geyser_count = cluster.len() - mineral_count;
+    if mineral_count < 4 && !(geyser_count >= 1 && mineral_count >= 2) {
+      continue;
+    }
+
+    let (sum_x, sum_y) = cluster.iter().fold((0, 0), |(sx, sy), r| {
+      (
+        sx + r.tile.x + r.tile_width / 2,
+        sy + r.tile.y + r.tile_height / 2,
+      )
+    });
+    let cluster_center = (sum_x / cluster.len() as i32, sum_y / cluster.len() as i32);
+
+    let start_location = start_locations.iter().find(|s| {
+      (s.x - cluster_center.0).abs() <= START_LOCATION_MATCH_RANGE
+        && (s.y - cluster_center.1).abs() <= START_LOCATION_MATCH_RANGE
+    });
+
+    let tile = match start_location {
+      Some(start) => Some(*start),
+      None => find_depot_tile(&analysis.grid, &cluster),
+    };
+    let Some(tile) = tile else {
+      continue;
+    };
+
+    bases.push(BaseLocation {
+      id: bases.len(),
+      tile,
+      region_id: analysis.region_at(tile),
+      minerals: cluster
+        .iter()
+        .filter(|r| !r.is_geyser)
+        .map(|r| r.tile)
+        .collect(),
+      geysers: cluster
+        .iter()
+        .filter(|r| r.is_geyser)
+        .map(|r| r.tile)
+        .collect(),
+      total_minerals: cluster
+        .iter()
+        .filter(|r| !r.is_geyser)
+        .map(|r| r.amount)
+        .sum(),
+      total_gas: cluster
+        .iter()
+        .filter(|r| r.is_geyser)
+        .map(|r| r.amount)
+        .sum(),
+      is_start_location: start_location.is_some(),
+    });
+  }
+
+  bases
+}
+
+fn cluster_resources<'a>(resources: &[&'a ResourceInfo]) -> Vec<Vec<&'a ResourceInfo>> {
+  let mut remaining: Vec<&ResourceInfo> = resources.to_vec();
+  let mut clusters = Vec::new();
+
+  while let Some(seed) = remaining.pop() {
+    let mut cluster = vec![seed];
+    let mut index = 0;
+    while index < cluster.len() {
+      let current = cluster[index];
+      let mut i = 0;
+      while i < remaining.len() {
+        if rect_gap(current, remaining[i]) <= RESOURCE_CLUSTER_RANGE {
+          cluster.push(remaining.swap_remove(i));
+        } else {
+          i += 1;
+        }
+      }
+      index += 1;
+    }
+    clusters.push(cluster);
+  }
+
+  clusters
+}
+
+fn rect_gap(a: &ResourceInfo, b: &ResourceInfo) -> i32 {
+  let gap_x = (b.tile.x - (a.tile.x + a.tile_width))
+    .max(a.tile.x - (b.tile.x + b.tile_width))
+    .max(0);
+  let gap_y = (b.tile.y - (a.tile.y + a.tile_height))
+    .max(a.tile.y - (b.tile.y + b.tile_height))
+    .max(0);
+  gap_x.max(gap_y)
+}
+
+/// Finds the depot tile with the smallest total distance to the cluster's resources,
+/// honoring the rule that a depot may not be placed within 3 tiles of a resource.
+fn find_depot_tile(grid: &MapGrid, cluster: &[&ResourceInfo]) -> Option<TilePosition> {
+  let min_x = cluster.iter().map(|r| r.tile.x).min()? - BASE_SEARCH_MARGIN;
+  let min_y = cluster.iter().map(|r| r.tile.y).min()? - BASE_SEARCH_MARGIN;
+  let max_x = cluster.iter().map(|r| r.tile.x + r.tile_width).max()? + BASE_SEARCH_MARGIN;
+  let max_y = cluster.iter().map(|r| r.tile.y + r.tile_height).max()? + BASE_SEARCH_MARGIN;
+
+  let mut best: Option<(i32, TilePosition)> = None;
+  for y in min_y..=max_y {
+    for x in min_x..=max_x {
+      let fits =
+        (0..DEPOT_HEIGHT).all(|dy| (0..DEPOT_WIDTH).all(|dx| grid.is_buildable(x + dx, y + dy)));
+      if !fits {
+        continue;
+      }
+
+      let too_close = cluster.iter().any(|r| {
+        x - DEPOT_RESOURCE_GAP < r.tile.x + r.tile_width
+          && r.tile.x < x + DEPOT_WIDTH + DEPOT_RESOURCE_GAP
+          && y - DEPOT_RESOURCE_GAP < r.tile.y + r.tile_height
+          && r.tile.y < y + DEPOT_HEIGHT + DEPOT_RESOURCE_GAP
+      });
+      if too_close {
+        continue;
+      }
+
+      // Distances in half-tiles so the depot and resource centers stay integral
+      let score: i32 = cluster
+        .iter()
+        .map(|r| {
+          let dx = (2 * x + DEPOT_WIDTH) - (2 * r.tile.x + r.tile_width);
+          let dy = (2 * y + DEPOT_HEIGHT) - (2 * r.tile.y + r.tile_height);
+          let distance = ((dx * dx + dy * dy) as f64).sqrt() as i32;
+          if r.is_geyser {
+            distance * 2
+          } else {
+            distance
+          }
+        })
+        .sum();
+
+      if best.map(|(s, _)| score < s).unwrap_or(true) {
+        best = Some((score, TilePosition { x, y }));
+      }
+    }
+  }
+
+  best.map(|(_, tile)| tile)
+}
+
+#[cfg(feature = "bwapi")]
+pub fn draw_debug(game: &Game, analysis: &TerrainAnalysis) {
+  for choke in &analysis.chokepoints {
+    game.draw_text_map(
+      choke.center.to_position(),
+      &format!("Choke {} ({}px)", choke.id, choke.width),
+    );
+  }
+  for base in &analysis.base_locations {
+    game.draw_text_map(base.center(), &format!("Base {}", base.id));
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn grid(width: i32, height: i32, is_wall: impl Fn(i32, i32) -> bool) -> MapGrid {
+    let walkable: Vec<bool> = (0..height)
+      .flat_map(|y| (0..width).map(move |x| (x, y)))
+      .map(|(x, y)| !is_wall(x, y))
+      .collect();
+    MapGrid {
+      width,
+      height,
+      buildable: walkable.clone(),
+      walkable,
+    }
+  }
+
+  /// Two 19x20 rooms separated by a two tile thick wall with a three tile gap at y 9..=11
+  fn two_rooms() -> MapGrid {
+    grid(40, 20, |x, y| {
+      (19..=20).contains(&x) && !(9..=11).contains(&y)
+    })
+  }
+
+  fn mineral(x: i32, y: i32) -> ResourceInfo {
+    ResourceInfo {
+      tile: TilePosition { x, y },
+      tile_width: 2,
+      tile_height: 1,
+      amount: 1500,
+      is_geyser: false,
+    }
+  }
+
+  fn tile(x: i32, y: i32) -> TilePosition {
+    TilePosition { x, y }
+  }
+
+  #[test]
+  fn splits_rooms_joined_by_a_gap() {
+    let analysis = analyze(two_rooms(), &[], &[]);
+
+    assert_eq!(analysis.regions.len(), 2);
+    let left = analysis.region_at(tile(5, 10)).unwrap();
+    let right = analysis.region_at(tile(30, 10)).unwrap();
+    assert_ne!(left, right);
+    assert_eq!(analysis.region_at(tile(19, 5)), None);
+  }
+
+  #[test]
+  fn finds_the_choke_in_the_gap() {
+    let analysis = analyze(two_rooms(), &[], &[]);
+
+    assert_eq!(analysis.chokepoints.len(), 1);
+    let choke = &analysis.chokepoints[0];
+    assert!(
+      (19..=22).contains(&choke.center.x) && (9..=11).contains(&choke.center.y),
+      "choke center {:?} should be in the gap",
+      choke.center
+    );
+    assert_eq!(choke.width, 3 * TILE_SIZE);
+    for region in &analysis.regions {
+      assert_eq!(region.chokepoint_ids, vec![choke.id]);
+    }
+  }
+
+  #[test]
+  fn places_the_depot_next_to_the_mineral_line() {
+    let resources: Vec<ResourceInfo> = (14..22).map(|y| mineral(32, y)).collect();
+
+    let analysis = analyze(grid(48, 48, |_, _| false), &resources, &[]);
+
+    assert_eq!(analysis.base_locations.len(), 1);
+    let base = &analysis.base_locations[0];
+    assert!(!base.is_start_location);
+    assert_eq!(base.minerals.len(), 8);
+    assert_eq!(base.total_minerals, 8 * 1500);
+    // Right against the 3 tile gap, centered on the line
+    assert_eq!(base.tile, tile(32 - DEPOT_RESOURCE_GAP - DEPOT_WIDTH, 16));
+    assert_eq!(base.region_id, Some(0));
+  }
+
+  #[test]
+  fn start_location_overrides_the_depot_search() {
+    let resources: Vec<ResourceInfo> = (14..22).map(|y| mineral(32, y)).collect();
+
+    let analysis = analyze(grid(48, 48, |_, _| false), &resources, &[tile(24, 15)]);
+
+    let base = &analysis.base_locations[0];
+    assert!(base.is_start_location);
+    assert_eq!(base.tile, tile(24, 15));
+  }
+}
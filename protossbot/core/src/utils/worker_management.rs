@@ -0,0 +1,337 @@
+use std::collections::HashMap;
+
+use crate::{
+  log_debug,
+  logging::LogTarget,
+  state::game_state::{GameState, IntendedCommand},
+  types::{Order, Position, UnitType},
+  utils::{command_recorder::CommandRecord, pathfinding::FlowField},
+  world::{GameWorld, UnitInfo},
+};
+
+/// Workers that saturate one assimilator
+pub const WORKERS_PER_REFINERY: usize = 3;
+
+pub fn assign_idle_workers_to_minerals(world: &impl GameWorld, state: &mut GameState) {
+  let workers: Vec<UnitInfo> = world
+    .my_units()
+    .into_iter()
+    .filter(|u| u.unit_type.is_worker() && u.is_completed)
+    .collect();
+
+  // Assign idle workers to mining
+  for worker in workers {
+    assign_worker_to_mineral(world, &worker, state);
+  }
+}
+
+/// Keeps every finished assimilator at [`WORKERS_PER_REFINERY`] workers, taken from the
+/// mineral line. Gas tech in the build stages depends on this.
+pub fn assign_workers_to_gas(world: &impl GameWorld, state: &mut GameState) {
+  let units = world.my_units();
+  let refineries: Vec<&UnitInfo> = units
+    .iter()
+    .filter(|u| u.unit_type == UnitType::Protoss_Assimilator && u.is_completed)
+    .collect();
+
+  // Workers that were pulled off gas, by a build or a scout, get replaced
+  state
+    .workers
+    .gas_assignments
+    .retain(|worker_id, refinery_id| {
+      refineries.iter().any(|r| r.id == *refinery_id)
+        && units
+          .iter()
+          .any(|u| u.id == *worker_id && u.is_gathering_gas)
+    });
+
+  for refinery in refineries {
+    let assigned = state
+      .workers
+      .gas_assignments
+      .values()
+      .filter(|id| **id == refinery.id)
+      .count();
+
+    for _ in assigned..WORKERS_PER_REFINERY {
+      let Some(worker) = find_mineral_worker(&units, refinery.position, state) else {
+        return;
+      };
+      send_worker_to_gas(world, worker, refinery, state);
+    }
+  }
+}
+
+fn find_mineral_worker<'a>(
+  units: &'a [UnitInfo],
+  near: Position,
+  state: &GameState,
+) -> Option<&'a UnitInfo> {
+  units
+    .iter()
+    .filter(|u| {
+      u.unit_type.is_worker()
+        && u.is_completed
+        && u.is_gathering_minerals
+        && !state.intended_commands.contains_key(&u.id)
+        && !state.workers.gas_assignments.contains_key(&u.id)
+    })
+    .min_by_key(|u| {
+      let distance_sq = (u.position.x - near.x).pow(2) + (u.position.y - near.y).pow(2);
+      (u.is_carrying_minerals, distance_sq)
+    })
+}
+
+fn send_worker_to_gas(
+  world: &impl GameWorld,
+  worker: &UnitInfo,
+  refinery: &UnitInfo,
+  state: &mut GameState,
+) {
+  let result = world.gather(worker.id, refinery.id);
+  state.commands.record(
+    CommandRecord::new(
+      worker.id,
+      "Gather",
+      "worker_management",
+      "refinery needs workers",
+    )
+    .with_target(refinery.id)
+    .with_result(&result),
+  );
+  if result.is_ok() {
+    state.workers.gas_assignments.insert(worker.id, refinery.id);
+    log_debug!(
+      LogTarget::Workers,
+      "Sent worker {} to mine gas at {:?}",
+      worker.id,
+      refinery.position
+    );
+  }
+}
+
+fn assign_worker_to_mineral(world: &impl GameWorld, worker: &UnitInfo, state: &mut GameState) {
+  let worker_id = worker.id;
+
+  if let Some(cmd) = state.intended_commands.get(&worker_id) {
+    if cmd.order != Order::MiningMinerals {
+      return;
+    }
+  }
+
+  if !worker.is_idle {
+    return;
+  }
+
+  if worker.is_gathering_minerals || worker.is_gathering_gas {
+    return;
+  }
+
+  let Some(mineral) = find_available_mineral(world, worker, state) else {
+    return;
+  };
+
+  let intended_cmd = IntendedCommand {
+    order: Order::MiningMinerals,
+    target_position: None,
+    target_unit_id: Some(mineral.id),
+  };
+
+  state.intended_commands.insert(worker_id, intended_cmd);
+
+  log_debug!(
+    LogTarget::Workers,
+    "Worker {} current order: {:?}, assigning to mine from mineral at {:?}",
+    worker_id,
+    worker.order,
+    mineral.position
+  );
+
+  let result = world.gather(worker_id, mineral.id);
+  state.commands.record(
+    CommandRecord::new(worker_id, "Gather", "worker_management", "idle worker")
+      .with_target(mineral.id)
+      .with_result(&result),
+  );
+  if result.is_ok() {
+    log_debug!(
+      LogTarget::Workers,
+      "Assigned worker {} to mine from mineral at {:?}",
+      worker_id,
+      mineral.position
+    );
+  }
+}
+
+fn find_available_mineral(
+  world: &impl GameWorld,
+  worker: &UnitInfo,
+  state: &mut GameState,
+) -> Option<UnitInfo> {
+  let worker_pos = worker.position;
+  let mut mineral_list: Vec<UnitInfo> = world.mineral_fields();
+
+  // Ground distance first so workers don't pick patches across cliffs; unreachable last
+  let mut ground_distances: HashMap<usize, i32> = HashMap::new();
+  for mineral in &mineral_list {
+    let distance = mineral_line_distance(state, mineral, worker);
+    ground_distances.insert(mineral.id, distance);
+  }
+  mineral_list.sort_by_key(|m| {
+    let pos = m.position;
+    let distance_sq = (pos.x - worker_pos.x).pow(2) + (pos.y - worker_pos.y).pow(2);
+    (ground_distances[&m.id], distance_sq)
+  });
+
+  for mineral in mineral_list.iter() {
+    let mineral_id: usize = mineral.id;
+
+    let worker_count = state
+      .intended_commands
+      .values()
+      .filter(|cmd| cmd.target_unit_id == Some(mineral_id))
+      .count();
+
+    if worker_count < 2 {
+      return Some(mineral.clone());
+    }
+  }
+  mineral_list.first().cloned()
+}
+
+/// Ground distance from the worker to the base whose mineral line holds `mineral`.
+/// The flow field towards each base is cached so idle workers don't rebuild one per frame.
+fn mineral_line_distance(state: &mut GameState, mineral: &UnitInfo, worker: &UnitInfo) -> i32 {
+  let Some(base) = state
+    .terrain
+    .base_locations
+    .iter()
+    .find(|b| b.minerals.contains(&mineral.tile_position))
+  else {
+    return i32::MAX;
+  };
+
+  let grid = &state.terrain.grid;
+  state
+    .workers
+    .mineral_line_fields
+    .entry(base.id)
+    .or_insert_with(|| FlowField::towards(grid, &[base.tile]))
+    .distance_at(worker.tile_position)
+    .unwrap_or(i32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::types::TilePosition;
+  use crate::utils::terrain_analysis::{BaseLocation, MapGrid};
+  use crate::world::fake_world::{FakeCommand, FakeWorld};
+
+  fn world_with_assimilator(probes: usize) -> (FakeWorld, usize, Vec<usize>) {
+    let mut world = FakeWorld::default();
+    world.add_unit(UnitType::Protoss_Nexus, TilePosition { x: 10, y: 10 });
+    let assimilator = world.add_unit(UnitType::Protoss_Assimilator, TilePosition { x: 16, y: 8 });
+    let probes = (0..probes)
+      .map(|i| {
+        let id = world.add_unit(
+          UnitType::Protoss_Probe,
+          TilePosition {
+            x: 8 + i as i32,
+            y: 14,
+          },
+        );
+        let probe = world.unit_mut(id);
+        probe.is_idle = false;
+        probe.is_gathering_minerals = true;
+        id
+      })
+      .collect();
+    (world, assimilator, probes)
+  }
+
+  fn gas_orders(world: &FakeWorld, assimilator: usize) -> usize {
+    world
+      .issued()
+      .iter()
+      .filter(|c| matches!(c, FakeCommand::Gather { target_id, .. } if *target_id == assimilator))
+      .count()
+  }
+
+  #[test]
+  fn saturates_a_finished_assimilator() {
+    let (world, assimilator, _) = world_with_assimilator(6);
+    let mut state = GameState::default();
+
+    assign_workers_to_gas(&world, &mut state);
+
+    assert_eq!(gas_orders(&world, assimilator), WORKERS_PER_REFINERY);
+    assert_eq!(state.workers.gas_assignments.len(), WORKERS_PER_REFINERY);
+  }
+
+  #[test]
+  fn leaves_an_unfinished_assimilator_alone() {
+    let (mut world, assimilator, _) = world_with_assimilator(6);
+    world.unit_mut(assimilator).is_completed = false;
+    let mut state = GameState::default();
+
+    assign_workers_to_gas(&world, &mut state);
+
+    assert!(world.issued().is_empty());
+  }
+
+  #[test]
+  fn replaces_a_worker_pulled_off_gas() {
+    let (mut world, assimilator, probes) = world_with_assimilator(6);
+    let mut state = GameState::default();
+    for &probe in &probes[..3] {
+      let unit = world.unit_mut(probe);
+      unit.is_gathering_minerals = false;
+      unit.is_gathering_gas = true;
+      state.workers.gas_assignments.insert(probe, assimilator);
+    }
+    // Taken away to place a building
+    world.unit_mut(probes[0]).is_gathering_gas = false;
+
+    assign_workers_to_gas(&world, &mut state);
+
+    assert_eq!(gas_orders(&world, assimilator), 1);
+    assert!(!state.workers.gas_assignments.contains_key(&probes[0]));
+    assert_eq!(state.workers.gas_assignments.len(), WORKERS_PER_REFINERY);
+  }
+
+  #[test]
+  fn builds_one_flow_field_per_mineral_line() {
+    let mut world = FakeWorld::default();
+    world.add_unit(UnitType::Protoss_Nexus, TilePosition { x: 10, y: 10 });
+    let minerals: Vec<TilePosition> = (10..13).map(|y| TilePosition { x: 18, y }).collect();
+    for tile in &minerals {
+      world.add_mineral_field(*tile);
+    }
+    for x in 12..16 {
+      world.add_unit(UnitType::Protoss_Probe, TilePosition { x, y: 14 });
+    }
+    let mut state = GameState::default();
+    state.terrain.grid = MapGrid {
+      width: 32,
+      height: 32,
+      walkable: vec![true; 32 * 32],
+      buildable: vec![true; 32 * 32],
+    };
+    state.terrain.base_locations.push(BaseLocation {
+      id: 0,
+      tile: TilePosition { x: 10, y: 10 },
+      region_id: None,
+      minerals,
+      geysers: Vec::new(),
+      total_minerals: 4500,
+      total_gas: 0,
+      is_start_location: true,
+    });
+
+    assign_idle_workers_to_minerals(&world, &mut state);
+
+    assert_eq!(state.intended_commands.len(), 4);
+    assert_eq!(state.workers.mineral_line_fields.len(), 1);
+  }
+}
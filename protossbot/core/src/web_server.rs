@@ -0,0 +1,354 @@
+use axum::{
+  extract::State,
+  http::StatusCode,
+  response::{IntoResponse, Response},
+  routing::{get, post},
+  Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tower_http::{cors::CorsLayer, services::ServeDir};
+
+use crate::{
+  log_info,
+  logging::{self, LogLevel, LogTarget},
+  utils::results_store::{self, ResultsStore, RESULTS_PATH},
+  world::snapshot::WorldSnapshot,
+};
+
+#[derive(Clone)]
+pub struct SharedGameSpeed {
+  speed: Arc<Mutex<i32>>,
+}
+
+impl SharedGameSpeed {
+  pub fn new(initial_speed: i32) -> Self {
+    Self {
+      speed: Arc::new(Mutex::new(initial_speed)),
+    }
+  }
+
+  pub fn get(&self) -> i32 {
+    *self.speed.lock().unwrap()
+  }
+
+  pub fn set(&self, speed: i32) {
+    *self.speed.lock().unwrap() = speed;
+  }
+}
+
+#[derive(Clone, Default)]
+pub struct BuildStatusData {
+  pub stage_name: String,
+  pub stage_index: usize,
+  pub item_status: HashMap<String, String>,
+}
+
+#[derive(Clone)]
+pub struct SharedBuildStatus {
+  data: Arc<Mutex<BuildStatusData>>,
+}
+
+impl Default for SharedBuildStatus {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl SharedBuildStatus {
+  pub fn new() -> Self {
+    Self {
+      data: Arc::new(Mutex::new(BuildStatusData::default())),
+    }
+  }
+
+  pub fn update(
+    &self,
+    stage_name: String,
+    stage_index: usize,
+    item_status: HashMap<String, String>,
+  ) {
+    let mut data = self.data.lock().unwrap();
+    data.stage_name = stage_name;
+    data.stage_index = stage_index;
+    data.item_status = item_status;
+  }
+
+  pub fn get(&self) -> BuildStatusData {
+    self.data.lock().unwrap().clone()
+  }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct EnemyStrategyData {
+  pub opening: String,
+  pub confidence: f32,
+  pub evidence: String,
+  pub is_rush: bool,
+}
+
+#[derive(Clone)]
+pub struct SharedEnemyStrategy {
+  data: Arc<Mutex<EnemyStrategyData>>,
+}
+
+impl Default for SharedEnemyStrategy {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl SharedEnemyStrategy {
+  pub fn new() -> Self {
+    Self {
+      data: Arc::new(Mutex::new(EnemyStrategyData::default())),
+    }
+  }
+
+  pub fn update(&self, data: EnemyStrategyData) {
+    *self.data.lock().unwrap() = data;
+  }
+
+  pub fn get(&self) -> EnemyStrategyData {
+    self.data.lock().unwrap().clone()
+  }
+}
+
+/// How long the snapshot endpoint waits for the game thread to run a frame
+const SNAPSHOT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+const SNAPSHOT_POLL_ATTEMPTS: u32 = 40;
+
+#[derive(Default)]
+struct SnapshotData {
+  requested: bool,
+  latest: Option<WorldSnapshot>,
+}
+
+/// Hands a world snapshot from the game thread to the debug endpoint. The endpoint
+/// raises a request and the bot captures the next frame it runs.
+#[derive(Clone)]
+pub struct SharedSnapshot {
+  data: Arc<Mutex<SnapshotData>>,
+}
+
+impl Default for SharedSnapshot {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl SharedSnapshot {
+  pub fn new() -> Self {
+    Self {
+      data: Arc::new(Mutex::new(SnapshotData::default())),
+    }
+  }
+
+  pub fn request(&self) {
+    let mut data = self.data.lock().unwrap();
+    data.requested = true;
+    data.latest = None;
+  }
+
+  pub fn is_requested(&self) -> bool {
+    self.data.lock().unwrap().requested
+  }
+
+  pub fn store(&self, snapshot: WorldSnapshot) {
+    let mut data = self.data.lock().unwrap();
+    data.requested = false;
+    data.latest = Some(snapshot);
+  }
+
+  pub fn take(&self) -> Option<WorldSnapshot> {
+    self.data.lock().unwrap().latest.take()
+  }
+}
+
+#[derive(Clone)]
+struct AppState {
+  game_speed: SharedGameSpeed,
+  build_status: SharedBuildStatus,
+  enemy_strategy: SharedEnemyStrategy,
+  snapshot: SharedSnapshot,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GameSpeedResponse {
+  pub speed: i32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GameSpeedRequest {
+  pub speed: i32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BuildStatusResponse {
+  pub stage_name: String,
+  pub stage_index: usize,
+  pub items: Vec<BuildItemStatusInfo>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BuildItemStatusInfo {
+  pub unit_name: String,
+  pub status: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct LogFilterInfo {
+  pub target: LogTarget,
+  pub level: LogLevel,
+}
+
+/// Changes one target's filter, or every target's when `target` is left out
+#[derive(Serialize, Deserialize)]
+pub struct LogFilterRequest {
+  pub target: Option<LogTarget>,
+  pub level: LogLevel,
+}
+
+async fn get_game_speed(State(app_state): State<AppState>) -> Response {
+  let speed = app_state.game_speed.get();
+  (StatusCode::OK, Json(GameSpeedResponse { speed })).into_response()
+}
+
+async fn set_game_speed(
+  State(app_state): State<AppState>,
+  Json(payload): Json<GameSpeedRequest>,
+) -> Response {
+  if payload.speed < -1 || payload.speed > 1000 {
+    return (
+      StatusCode::BAD_REQUEST,
+      Json(GameSpeedResponse { speed: 0 }),
+    )
+      .into_response();
+  }
+
+  app_state.game_speed.set(payload.speed);
+
+  (
+    StatusCode::OK,
+    Json(GameSpeedResponse {
+      speed: payload.speed,
+    }),
+  )
+    .into_response()
+}
+
+async fn get_build_status(State(app_state): State<AppState>) -> Response {
+  let data = app_state.build_status.get();
+
+  let items: Vec<BuildItemStatusInfo> = data
+    .item_status
+    .iter()
+    .map(|(unit_name, status)| BuildItemStatusInfo {
+      unit_name: unit_name.clone(),
+      status: status.clone(),
+    })
+    .collect();
+
+  let response = BuildStatusResponse {
+    stage_name: data.stage_name,
+    stage_index: data.stage_index,
+    items,
+  };
+
+  (StatusCode::OK, Json(response)).into_response()
+}
+
+async fn get_enemy_strategy(State(app_state): State<AppState>) -> Response {
+  (StatusCode::OK, Json(app_state.enemy_strategy.get())).into_response()
+}
+
+async fn get_log_filters() -> Response {
+  let filters: Vec<LogFilterInfo> = logging::levels()
+    .into_iter()
+    .map(|(target, level)| LogFilterInfo { target, level })
+    .collect();
+  (StatusCode::OK, Json(filters)).into_response()
+}
+
+async fn set_log_filter(Json(payload): Json<LogFilterRequest>) -> Response {
+  let targets = match payload.target {
+    Some(target) => vec![target],
+    None => LogTarget::ALL.to_vec(),
+  };
+  for target in targets {
+    logging::set_level(target, payload.level);
+    log_info!(
+      LogTarget::Web,
+      "Log level for {} set to {}",
+      target.name(),
+      payload.level.name()
+    );
+  }
+  get_log_filters().await
+}
+
+/// Win rates of every game in the results store, read fresh on each request
+async fn get_results() -> Response {
+  let results = ResultsStore::new(RESULTS_PATH).load();
+  (StatusCode::OK, Json(results_store::summarize(&results))).into_response()
+}
+
+/// Captures the world as the bot sees it on the next frame, ready to be saved as a
+/// scenario test
+async fn get_snapshot(State(app_state): State<AppState>) -> Response {
+  app_state.snapshot.request();
+
+  for _ in 0..SNAPSHOT_POLL_ATTEMPTS {
+    tokio::time::sleep(SNAPSHOT_POLL_INTERVAL).await;
+    if let Some(snapshot) = app_state.snapshot.take() {
+      return (StatusCode::OK, Json(snapshot)).into_response();
+    }
+  }
+
+  (
+    StatusCode::SERVICE_UNAVAILABLE,
+    "No game frame ran while waiting for the snapshot",
+  )
+    .into_response()
+}
+
+pub async fn start_web_server(
+  shared_speed: SharedGameSpeed,
+  build_status: SharedBuildStatus,
+  enemy_strategy: SharedEnemyStrategy,
+  snapshot: SharedSnapshot,
+) {
+  let static_dir = std::env::current_dir().unwrap().join("static");
+
+  let cors = CorsLayer::very_permissive();
+
+  let app_state = AppState {
+    game_speed: shared_speed,
+    build_status,
+    enemy_strategy,
+    snapshot,
+  };
+
+  let app = Router::new()
+    .route("/api/speed", get(get_game_speed))
+    .route("/api/speed", post(set_game_speed))
+    .route("/api/build-status", get(get_build_status))
+    .route("/api/enemy-strategy", get(get_enemy_strategy))
+    .route("/api/snapshot", get(get_snapshot))
+    .route("/api/logging", get(get_log_filters))
+    .route("/api/logging", post(set_log_filter))
+    .route("/api/results", get(get_results))
+    .layer(cors)
+    .fallback_service(ServeDir::new(static_dir))
+    .with_state(app_state);
+
+  let addr = "127.0.0.1:3333";
+
+  let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+
+  log_info!(LogTarget::Web, "Web server running at http://{}", addr);
+
+  axum::serve(listener, app).await.unwrap();
+}
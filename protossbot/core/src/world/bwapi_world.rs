@@ -0,0 +1,123 @@
+use crate::types::{TilePosition, UnitType};
+use rsbwapi::{Game, Player, Unit};
+
+use super::{GameWorld, UnitInfo};
+
+/// `GameWorld` backed by the running game through BWAPI
+pub struct BwapiWorld<'a> {
+  game: &'a Game,
+  player: &'a Player,
+}
+
+impl<'a> BwapiWorld<'a> {
+  pub fn new(game: &'a Game, player: &'a Player) -> Self {
+    Self { game, player }
+  }
+
+  fn unit(&self, unit_id: usize) -> Result<Unit, String> {
+    self
+      .game
+      .get_unit(unit_id)
+      .ok_or_else(|| format!("unit {} not found", unit_id))
+  }
+}
+
+pub fn unit_info(unit: &Unit) -> UnitInfo {
+  UnitInfo {
+    id: unit.get_id(),
+    unit_type: unit.get_type(),
+    position: unit.get_position(),
+    tile_position: unit.get_tile_position(),
+    order: unit.get_order(),
+    is_completed: unit.is_completed(),
+    is_idle: unit.is_idle(),
+    is_constructing: unit.is_constructing(),
+    is_training: unit.is_training(),
+    is_gathering_minerals: unit.is_gathering_minerals(),
+    is_gathering_gas: unit.is_gathering_gas(),
+    is_carrying_minerals: unit.is_carrying_minerals(),
+  }
+}
+
+impl GameWorld for BwapiWorld<'_> {
+  fn frame_count(&self) -> i32 {
+    self.game.get_frame_count()
+  }
+
+  fn map_size(&self) -> (i32, i32) {
+    (self.game.map_width(), self.game.map_height())
+  }
+
+  fn my_units(&self) -> Vec<UnitInfo> {
+    self.player.get_units().iter().map(unit_info).collect()
+  }
+
+  fn minerals(&self) -> i32 {
+    self.player.minerals()
+  }
+
+  fn gas(&self) -> i32 {
+    self.player.gas()
+  }
+
+  fn supply_used(&self) -> i32 {
+    self.player.supply_used()
+  }
+
+  fn supply_total(&self) -> i32 {
+    self.player.supply_total()
+  }
+
+  fn start_location(&self) -> TilePosition {
+    self.player.get_start_location()
+  }
+
+  fn mineral_fields(&self) -> Vec<UnitInfo> {
+    self
+      .game
+      .get_static_minerals()
+      .iter()
+      .filter(|m| m.exists())
+      .map(unit_info)
+      .collect()
+  }
+
+  fn can_build_here(&self, builder_id: usize, tile: TilePosition, unit_type: UnitType) -> bool {
+    let Ok(builder) = self.unit(builder_id) else {
+      return false;
+    };
+    self
+      .game
+      .can_build_here(&builder, tile, unit_type, false)
+      .unwrap_or(false)
+  }
+
+  fn build(&self, unit_id: usize, unit_type: UnitType, tile: TilePosition) -> Result<(), String> {
+    self
+      .unit(unit_id)?
+      .build(unit_type, tile)
+      .map(|_| ())
+      .map_err(|e| format!("{:?}", e))
+  }
+
+  fn train(&self, unit_id: usize, unit_type: UnitType) -> Result<(), String> {
+    self
+      .unit(unit_id)?
+      .train(unit_type)
+      .map(|_| ())
+      .map_err(|e| format!("{:?}", e))
+  }
+
+  fn gather(&self, unit_id: usize, target_id: usize) -> Result<(), String> {
+    let target = self.unit(target_id)?;
+    self
+      .unit(unit_id)?
+      .gather(&target)
+      .map(|_| ())
+      .map_err(|e| format!("{:?}", e))
+  }
+
+  fn draw_text_screen(&self, x: i32, y: i32, text: &str) {
+    self.game.draw_text_screen((x, y), text);
+  }
+}
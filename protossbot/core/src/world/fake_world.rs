@@ -0,0 +1,234 @@
+use crate::types::{Order, Position, TilePosition, UnitType};
+use std::{cell::RefCell, collections::HashSet};
+
+use super::{
+  snapshot::{UnitSnapshot, WorldSnapshot},
+  GameWorld, UnitInfo,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FakeCommand {
+  Build {
+    unit_id: usize,
+    unit_type: UnitType,
+    tile: TilePosition,
+  },
+  Train {
+    unit_id: usize,
+    unit_type: UnitType,
+  },
+  Gather {
+    unit_id: usize,
+    target_id: usize,
+  },
+}
+
+/// In-memory `GameWorld` for tests and replayed snapshots. Commands are recorded
+/// instead of executed.
+pub struct FakeWorld {
+  pub frame: i32,
+  pub map_width: i32,
+  pub map_height: i32,
+  pub units: Vec<UnitInfo>,
+  pub minerals: i32,
+  pub gas: i32,
+  pub supply_used: i32,
+  pub supply_total: i32,
+  pub start_location: TilePosition,
+  pub mineral_fields: Vec<UnitInfo>,
+  pub unbuildable: HashSet<TilePosition>,
+  pub commands: RefCell<Vec<FakeCommand>>,
+}
+
+impl Default for FakeWorld {
+  fn default() -> Self {
+    Self {
+      frame: 0,
+      map_width: 64,
+      map_height: 64,
+      units: Vec::new(),
+      minerals: 50,
+      gas: 0,
+      supply_used: 8,
+      supply_total: 18,
+      start_location: TilePosition { x: 10, y: 10 },
+      mineral_fields: Vec::new(),
+      unbuildable: HashSet::new(),
+      commands: RefCell::new(Vec::new()),
+    }
+  }
+}
+
+impl FakeWorld {
+  /// Rebuilds the world a snapshot was captured from
+  pub fn from_snapshot(snapshot: &WorldSnapshot) -> Result<Self, String> {
+    let to_infos = |units: &[UnitSnapshot]| {
+      units
+        .iter()
+        .map(|u| u.to_info())
+        .collect::<Result<Vec<_>, _>>()
+    };
+
+    Ok(Self {
+      frame: snapshot.frame,
+      map_width: snapshot.map_width,
+      map_height: snapshot.map_height,
+      units: to_infos(&snapshot.units)?,
+      minerals: snapshot.minerals,
+      gas: snapshot.gas,
+      supply_used: snapshot.supply_used,
+      supply_total: snapshot.supply_total,
+      start_location: TilePosition {
+        x: snapshot.start_location.0,
+        y: snapshot.start_location.1,
+      },
+      mineral_fields: to_infos(&snapshot.mineral_fields)?,
+      unbuildable: snapshot.unbuildable_tiles().into_iter().collect(),
+      commands: RefCell::new(Vec::new()),
+    })
+  }
+
+  /// Adds a completed, idle unit of the given type on `tile` and returns its id.
+  pub fn add_unit(&mut self, unit_type: UnitType, tile: TilePosition) -> usize {
+    let id = self.units.len() + self.mineral_fields.len() + 1;
+    self.units.push(fake_unit(id, unit_type, tile));
+    id
+  }
+
+  pub fn add_mineral_field(&mut self, tile: TilePosition) -> usize {
+    let id = self.units.len() + self.mineral_fields.len() + 1;
+    self
+      .mineral_fields
+      .push(fake_unit(id, UnitType::Resource_Mineral_Field, tile));
+    id
+  }
+
+  pub fn unit_mut(&mut self, unit_id: usize) -> &mut UnitInfo {
+    self
+      .units
+      .iter_mut()
+      .find(|u| u.id == unit_id)
+      .expect("unit exists")
+  }
+
+  pub fn issued(&self) -> Vec<FakeCommand> {
+    self.commands.borrow().clone()
+  }
+
+  fn occupied(&self, tile: TilePosition) -> bool {
+    self
+      .units
+      .iter()
+      .chain(self.mineral_fields.iter())
+      .filter(|u| u.unit_type.is_building() || u.unit_type.is_mineral_field())
+      .any(|u| {
+        let t = u.tile_position;
+        tile.x >= t.x
+          && tile.x < t.x + u.unit_type.tile_width()
+          && tile.y >= t.y
+          && tile.y < t.y + u.unit_type.tile_height()
+      })
+  }
+}
+
+pub fn fake_unit(id: usize, unit_type: UnitType, tile: TilePosition) -> UnitInfo {
+  UnitInfo {
+    id,
+    unit_type,
+    position: Position {
+      x: tile.x * 32 + unit_type.tile_width() * 16,
+      y: tile.y * 32 + unit_type.tile_height() * 16,
+    },
+    tile_position: tile,
+    order: Order::None,
+    is_completed: true,
+    is_idle: true,
+    is_constructing: false,
+    is_training: false,
+    is_gathering_minerals: false,
+    is_gathering_gas: false,
+    is_carrying_minerals: false,
+  }
+}
+
+impl GameWorld for FakeWorld {
+  fn frame_count(&self) -> i32 {
+    self.frame
+  }
+
+  fn map_size(&self) -> (i32, i32) {
+    (self.map_width, self.map_height)
+  }
+
+  fn my_units(&self) -> Vec<UnitInfo> {
+    self.units.clone()
+  }
+
+  fn minerals(&self) -> i32 {
+    self.minerals
+  }
+
+  fn gas(&self) -> i32 {
+    self.gas
+  }
+
+  fn supply_used(&self) -> i32 {
+    self.supply_used
+  }
+
+  fn supply_total(&self) -> i32 {
+    self.supply_total
+  }
+
+  fn start_location(&self) -> TilePosition {
+    self.start_location
+  }
+
+  fn mineral_fields(&self) -> Vec<UnitInfo> {
+    self.mineral_fields.clone()
+  }
+
+  fn can_build_here(&self, _builder_id: usize, tile: TilePosition, unit_type: UnitType) -> bool {
+    (0..unit_type.tile_width()).all(|dx| {
+      (0..unit_type.tile_height()).all(|dy| {
+        let t = TilePosition {
+          x: tile.x + dx,
+          y: tile.y + dy,
+        };
+        t.x >= 0
+          && t.y >= 0
+          && t.x < self.map_width
+          && t.y < self.map_height
+          && !self.unbuildable.contains(&t)
+          && !self.occupied(t)
+      })
+    })
+  }
+
+  fn build(&self, unit_id: usize, unit_type: UnitType, tile: TilePosition) -> Result<(), String> {
+    self.commands.borrow_mut().push(FakeCommand::Build {
+      unit_id,
+      unit_type,
+      tile,
+    });
+    Ok(())
+  }
+
+  fn train(&self, unit_id: usize, unit_type: UnitType) -> Result<(), String> {
+    self
+      .commands
+      .borrow_mut()
+      .push(FakeCommand::Train { unit_id, unit_type });
+    Ok(())
+  }
+
+  fn gather(&self, unit_id: usize, target_id: usize) -> Result<(), String> {
+    self
+      .commands
+      .borrow_mut()
+      .push(FakeCommand::Gather { unit_id, target_id });
+    Ok(())
+  }
+
+  fn draw_text_screen(&self, _x: i32, _y: i32, _text: &str) {}
+}
@@ -0,0 +1,50 @@
+//! The slice of the game the build planner needs, behind a trait so decision logic
+//! can run against the live game or against an in-memory world in tests.
+
+use crate::types::{Order, Position, TilePosition, UnitType};
+
+#[cfg(feature = "bwapi")]
+pub mod bwapi_world;
+pub mod fake_world;
+pub mod snapshot;
+
+/// Snapshot of one unit as seen by decision logic
+#[derive(Clone, Debug)]
+pub struct UnitInfo {
+  pub id: usize,
+  pub unit_type: UnitType,
+  pub position: Position,
+  pub tile_position: TilePosition,
+  pub order: Order,
+  pub is_completed: bool,
+  pub is_idle: bool,
+  pub is_constructing: bool,
+  pub is_training: bool,
+  pub is_gathering_minerals: bool,
+  pub is_gathering_gas: bool,
+  pub is_carrying_minerals: bool,
+}
+
+pub trait GameWorld {
+  fn frame_count(&self) -> i32;
+  /// Map size in tiles
+  fn map_size(&self) -> (i32, i32);
+
+  fn my_units(&self) -> Vec<UnitInfo>;
+  fn minerals(&self) -> i32;
+  fn gas(&self) -> i32;
+  /// Supply values are doubled, as in BWAPI
+  fn supply_used(&self) -> i32;
+  fn supply_total(&self) -> i32;
+  fn start_location(&self) -> TilePosition;
+
+  /// Mineral fields that still exist
+  fn mineral_fields(&self) -> Vec<UnitInfo>;
+  fn can_build_here(&self, builder_id: usize, tile: TilePosition, unit_type: UnitType) -> bool;
+
+  fn build(&self, unit_id: usize, unit_type: UnitType, tile: TilePosition) -> Result<(), String>;
+  fn train(&self, unit_id: usize, unit_type: UnitType) -> Result<(), String>;
+  fn gather(&self, unit_id: usize, target_id: usize) -> Result<(), String>;
+
+  fn draw_text_screen(&self, x: i32, y: i32, text: &str);
+}
@@ -0,0 +1,563 @@
+//! Serializable picture of everything `GameWorld` exposes at one frame, plus the bits of
+//! planner state that decide what happens next. Snapshots are captured from a live game
+//! through the web server's debug endpoint and replayed against `FakeWorld`, so a frame
+//! where the bot misbehaved can be checked in as a scenario test.
+
+use crate::types::{Order, Position, TilePosition, UnitType};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::{GameWorld, UnitInfo};
+use crate::{
+  state::{
+    build_stages::{self, BuildStage, DEFAULT_STRATEGY, STRATEGIES},
+    game_state::{GameState, IntendedCommand},
+  },
+  utils::terrain_analysis::MapGrid,
+};
+
+const BUILDABLE_TILE: char = '.';
+const UNBUILDABLE_TILE: char = '#';
+const UNWALKABLE_TILE: char = 'x';
+
+/// Unit types a snapshot can contain: our own units and the resources we mine
+const KNOWN_UNIT_TYPES: [UnitType; 36] = [
+  UnitType::Protoss_Probe,
+  UnitType::Protoss_Zealot,
+  UnitType::Protoss_Dragoon,
+  UnitType::Protoss_High_Templar,
+  UnitType::Protoss_Dark_Templar,
+  UnitType::Protoss_Archon,
+  UnitType::Protoss_Dark_Archon,
+  UnitType::Protoss_Shuttle,
+  UnitType::Protoss_Reaver,
+  UnitType::Protoss_Scarab,
+  UnitType::Protoss_Observer,
+  UnitType::Protoss_Scout,
+  UnitType::Protoss_Carrier,
+  UnitType::Protoss_Interceptor,
+  UnitType::Protoss_Arbiter,
+  UnitType::Protoss_Corsair,
+  UnitType::Protoss_Nexus,
+  UnitType::Protoss_Pylon,
+  UnitType::Protoss_Assimilator,
+  UnitType::Protoss_Gateway,
+  UnitType::Protoss_Forge,
+  UnitType::Protoss_Photon_Cannon,
+  UnitType::Protoss_Cybernetics_Core,
+  UnitType::Protoss_Shield_Battery,
+  UnitType::Protoss_Robotics_Facility,
+  UnitType::Protoss_Stargate,
+  UnitType::Protoss_Citadel_of_Adun,
+  UnitType::Protoss_Robotics_Support_Bay,
+  UnitType::Protoss_Fleet_Beacon,
+  UnitType::Protoss_Templar_Archives,
+  UnitType::Protoss_Observatory,
+  UnitType::Protoss_Arbiter_Tribunal,
+  UnitType::Resource_Mineral_Field,
+  UnitType::Resource_Mineral_Field_Type_2,
+  UnitType::Resource_Mineral_Field_Type_3,
+  UnitType::Resource_Vespene_Geyser,
+];
+
+/// Orders the planners look at. Anything else is stored by name and loaded as `None`.
+const KNOWN_ORDERS: [Order; 17] = [
+  Order::None,
+  Order::Nothing,
+  Order::PlayerGuard,
+  Order::Stop,
+  Order::Move,
+  Order::AttackMove,
+  Order::AttackUnit,
+  Order::PlaceBuilding,
+  Order::Train,
+  Order::MoveToMinerals,
+  Order::WaitForMinerals,
+  Order::MiningMinerals,
+  Order::ReturnMinerals,
+  Order::MoveToGas,
+  Order::WaitForGas,
+  Order::HarvestGas,
+  Order::ReturnGas,
+];
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UnitSnapshot {
+  pub id: usize,
+  pub unit_type: String,
+  /// Center in pixels
+  pub position: (i32, i32),
+  /// Top-left tile
+  pub tile: (i32, i32),
+  #[serde(default = "order_none")]
+  pub order: String,
+  #[serde(default = "yes")]
+  pub is_completed: bool,
+  #[serde(default)]
+  pub is_idle: bool,
+  #[serde(default)]
+  pub is_constructing: bool,
+  #[serde(default)]
+  pub is_training: bool,
+  #[serde(default)]
+  pub is_gathering_minerals: bool,
+  #[serde(default)]
+  pub is_gathering_gas: bool,
+  #[serde(default)]
+  pub is_carrying_minerals: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CommandSnapshot {
+  pub unit_id: usize,
+  pub order: String,
+  #[serde(default)]
+  pub target_position: Option<(i32, i32)>,
+  #[serde(default)]
+  pub target_unit_id: Option<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StageSnapshot {
+  pub name: String,
+  /// Desired count per unit type name
+  pub desired_counts: BTreeMap<String, i32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WorldSnapshot {
+  pub frame: i32,
+  pub map_width: i32,
+  pub map_height: i32,
+  pub minerals: i32,
+  pub gas: i32,
+  pub supply_used: i32,
+  pub supply_total: i32,
+  pub start_location: (i32, i32),
+  pub units: Vec<UnitSnapshot>,
+  #[serde(default)]
+  pub mineral_fields: Vec<UnitSnapshot>,
+  /// One string per tile row, `.` for buildable, `#` for walkable but unbuildable and
+  /// `x` for unwalkable. Missing rows are buildable.
+  #[serde(default)]
+  pub tiles: Vec<String>,
+  #[serde(default = "default_strategy")]
+  pub strategy: String,
+  /// Build stages in order, including the ones inserted during the game. Missing
+  /// stages mean the strategy's own.
+  #[serde(default)]
+  pub build_stages: Vec<StageSnapshot>,
+  #[serde(default)]
+  pub current_stage_index: usize,
+  #[serde(default)]
+  pub intended_commands: Vec<CommandSnapshot>,
+  /// Workers sent to mine gas and the assimilator each one was sent to
+  #[serde(default)]
+  pub gas_assignments: Vec<(usize, usize)>,
+}
+
+fn order_none() -> String {
+  format!("{:?}", Order::None)
+}
+
+fn yes() -> bool {
+  true
+}
+
+fn default_strategy() -> String {
+  DEFAULT_STRATEGY.to_string()
+}
+
+impl UnitSnapshot {
+  pub fn from_info(info: &UnitInfo) -> Self {
+    Self {
+      id: info.id,
+      unit_type: info.unit_type.name().to_string(),
+      position: (info.position.x, info.position.y),
+      tile: (info.tile_position.x, info.tile_position.y),
+      order: format!("{:?}", info.order),
+      is_completed: info.is_completed,
+      is_idle: info.is_idle,
+      is_constructing: info.is_constructing,
+      is_training: info.is_training,
+      is_gathering_minerals: info.is_gathering_minerals,
+      is_gathering_gas: info.is_gathering_gas,
+      is_carrying_minerals: info.is_carrying_minerals,
+    }
+  }
+
+  pub fn to_info(&self) -> Result<UnitInfo, String> {
+    Ok(UnitInfo {
+      id: self.id,
+      unit_type: parse_unit_type(&self.unit_type)?,
+      position: Position {
+        x: self.position.0,
+        y: self.position.1,
+      },
+      tile_position: TilePosition {
+        x: self.tile.0,
+        y: self.tile.1,
+      },
+      order: find_order(&self.order).unwrap_or(Order::None),
+      is_completed: self.is_completed,
+      is_idle: self.is_idle,
+      is_constructing: self.is_constructing,
+      is_training: self.is_training,
+      is_gathering_minerals: self.is_gathering_minerals,
+      is_gathering_gas: self.is_gathering_gas,
+      is_carrying_minerals: self.is_carrying_minerals,
+    })
+  }
+}
+
+impl StageSnapshot {
+  pub fn from_stage(stage: &BuildStage) -> Self {
+    Self {
+      name: stage.name.clone(),
+      desired_counts: stage
+        .desired_counts
+        .iter()
+        .map(|(unit_type, count)| (unit_type.name().to_string(), *count))
+        .collect(),
+    }
+  }
+
+  pub fn to_stage(&self) -> Result<BuildStage, String> {
+    let mut stage = BuildStage::new(&self.name);
+    for (name, count) in &self.desired_counts {
+      stage = stage.with_unit(parse_unit_type(name)?, *count);
+    }
+    Ok(stage)
+  }
+}
+
+impl WorldSnapshot {
+  pub fn capture(world: &impl GameWorld, state: &GameState) -> Self {
+    let grid = &state.terrain.grid;
+    let (map_width, map_height) = world.map_size();
+    let start = world.start_location();
+    let tiles = (0..grid.height)
+      .map(|y| {
+        (0..grid.width)
+          .map(|x| {
+            if !grid.is_walkable(x, y) {
+              UNWALKABLE_TILE
+            } else if grid.is_buildable(x, y) {
+              BUILDABLE_TILE
+            } else {
+              UNBUILDABLE_TILE
+            }
+          })
+          .collect()
+      })
+      .collect();
+
+    let mut intended_commands: Vec<CommandSnapshot> = state
+      .intended_commands
+      .iter()
+      .map(|(unit_id, cmd)| CommandSnapshot {
+        unit_id: *unit_id,
+        order: format!("{:?}", cmd.order),
+        target_position: cmd.target_position.map(|p| (p.x, p.y)),
+        target_unit_id: cmd.target_unit_id,
+      })
+      .collect();
+    intended_commands.sort_by_key(|cmd| cmd.unit_id);
+    let mut gas_assignments: Vec<(usize, usize)> = state
+      .workers
+      .gas_assignments
+      .iter()
+      .map(|(worker_id, assimilator_id)| (*worker_id, *assimilator_id))
+      .collect();
+    gas_assignments.sort();
+
+    Self {
+      frame: world.frame_count(),
+      map_width,
+      map_height,
+      minerals: world.minerals(),
+      gas: world.gas(),
+      supply_used: world.supply_used(),
+      supply_total: world.supply_total(),
+      start_location: (start.x, start.y),
+      units: world
+        .my_units()
+        .iter()
+        .map(UnitSnapshot::from_info)
+        .collect(),
+      mineral_fields: world
+        .mineral_fields()
+        .iter()
+        .map(UnitSnapshot::from_info)
+        .collect(),
+      tiles,
+      strategy: state.strategy.to_string(),
+      build_stages: state
+        .build_stages
+        .iter()
+        .map(StageSnapshot::from_stage)
+        .collect(),
+      current_stage_index: state.current_stage_index,
+      intended_commands,
+      gas_assignments,
+    }
+  }
+
+  pub fn from_json(text: &str) -> Result<Self, String> {
+    serde_json::from_str(text).map_err(|e| format!("invalid snapshot: {}", e))
+  }
+
+  pub fn load(path: &str) -> Result<Self, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    Self::from_json(&text)
+  }
+
+  pub fn to_json(&self) -> String {
+    serde_json::to_string_pretty(self).expect("snapshots always serialize")
+  }
+
+  /// Tiles marked unbuildable or unwalkable in `tiles`
+  pub fn unbuildable_tiles(&self) -> Vec<TilePosition> {
+    self
+      .tiles
+      .iter()
+      .enumerate()
+      .flat_map(|(y, row)| {
+        row
+          .chars()
+          .enumerate()
+          .filter(|(_, c)| *c != BUILDABLE_TILE)
+          .map(move |(x, _)| TilePosition {
+            x: x as i32,
+            y: y as i32,
+          })
+      })
+      .collect()
+  }
+
+  /// Terrain grid described by `tiles`, map sized
+  pub fn grid(&self) -> MapGrid {
+    let rows: Vec<Vec<char>> = self.tiles.iter().map(|row| row.chars().collect()).collect();
+    let tiles: Vec<char> = (0..self.map_height as usize)
+      .flat_map(|y| {
+        let row = rows.get(y);
+        (0..self.map_width as usize).map(move |x| {
+          row
+            .and_then(|r| r.get(x))
+            .copied()
+            .unwrap_or(BUILDABLE_TILE)
+        })
+      })
+      .collect();
+    MapGrid {
+      width: self.map_width,
+      height: self.map_height,
+      walkable: tiles.iter().map(|c| *c != UNWALKABLE_TILE).collect(),
+      buildable: tiles.iter().map(|c| *c == BUILDABLE_TILE).collect(),
+    }
+  }
+
+  /// Restores the planner state the snapshot was taken with. The terrain grid is only
+  /// replaced when the snapshot has tiles.
+  pub fn apply_to(&self, state: &mut GameState) -> Result<(), String> {
+    state.strategy = STRATEGIES
+      .into_iter()
+      .find(|strategy| *strategy == self.strategy)
+      .ok_or_else(|| format!("unknown strategy {}", self.strategy))?;
+    state.build_stages = if self.build_stages.is_empty() {
+      build_stages::get_strategy_stages(state.strategy)
+    } else {
+      self
+        .build_stages
+        .iter()
+        .map(StageSnapshot::to_stage)
+        .collect::<Result<_, _>>()?
+    };
+    state.current_stage_index = self.current_stage_index;
+    state.intended_commands.clear();
+    for cmd in &self.intended_commands {
+      let order = find_order(&cmd.order).ok_or_else(|| format!("unknown order {}", cmd.order))?;
+      state.intended_commands.insert(
+        cmd.unit_id,
+        IntendedCommand {
+          order,
+          target_position: cmd.target_position.map(|(x, y)| Position { x, y }),
+          target_unit_id: cmd.target_unit_id,
+        },
+      );
+    }
+    state.workers.gas_assignments = self.gas_assignments.iter().copied().collect();
+    if !self.tiles.is_empty() {
+      state.terrain.grid = self.grid();
+    }
+    Ok(())
+  }
+}
+
+fn parse_unit_type(name: &str) -> Result<UnitType, String> {
+  KNOWN_UNIT_TYPES
+    .into_iter()
+    .find(|unit_type| unit_type.name() == name)
+    .ok_or_else(|| format!("unknown unit type {}", name))
+}
+
+fn find_order(name: &str) -> Option<Order> {
+  KNOWN_ORDERS
+    .into_iter()
+    .find(|order| format!("{:?}", order) == name)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{utils::terrain_analysis::MapGrid, world::fake_world::FakeWorld};
+
+  fn sample_world() -> FakeWorld {
+    let mut world = FakeWorld {
+      map_width: 4,
+      map_height: 2,
+      minerals: 125,
+      ..FakeWorld::default()
+    };
+    world.add_unit(UnitType::Protoss_Nexus, TilePosition { x: 0, y: 0 });
+    let probe = world.add_unit(UnitType::Protoss_Probe, TilePosition { x: 3, y: 1 });
+    world.unit_mut(probe).order = Order::MiningMinerals;
+    world.add_mineral_field(TilePosition { x: 2, y: 0 });
+    world
+  }
+
+  fn sample_grid() -> MapGrid {
+    MapGrid {
+      width: 4,
+      height: 2,
+      walkable: vec![true, true, true, true, true, true, true, false],
+      buildable: vec![true, true, false, true, true, true, true, false],
+    }
+  }
+
+  fn sample_state(current_stage_index: usize) -> GameState {
+    let mut state = GameState {
+      current_stage_index,
+      ..GameState::default()
+    };
+    state.terrain.grid = sample_grid();
+    state
+  }
+
+  #[test]
+  fn round_trips_through_json() {
+    let world = sample_world();
+    let mut state = sample_state(2);
+    state.intended_commands.insert(
+      2,
+      IntendedCommand {
+        order: Order::MiningMinerals,
+        target_position: None,
+        target_unit_id: Some(3),
+      },
+    );
+
+    let snapshot = WorldSnapshot::capture(&world, &state);
+    let loaded = WorldSnapshot::from_json(&snapshot.to_json()).unwrap();
+
+    assert_eq!(loaded, snapshot);
+    assert_eq!(loaded.tiles, vec!["..#.", "...x"]);
+    assert_eq!(loaded.units[1].order, "MiningMinerals");
+  }
+
+  #[test]
+  fn restores_units_and_planner_state() {
+    let world = sample_world();
+    let mut state = sample_state(1);
+    state.intended_commands.insert(
+      2,
+      IntendedCommand {
+        order: Order::PlaceBuilding,
+        target_position: Some(Position { x: 64, y: 32 }),
+        target_unit_id: None,
+      },
+    );
+    let snapshot = WorldSnapshot::capture(&world, &state);
+
+    let units: Vec<UnitInfo> = snapshot
+      .units
+      .iter()
+      .map(|u| u.to_info().unwrap())
+      .collect();
+    let mut restored = GameState::default();
+    snapshot.apply_to(&mut restored).unwrap();
+
+    assert_eq!(units[0].unit_type, UnitType::Protoss_Nexus);
+    assert_eq!(units[1].order, Order::MiningMinerals);
+    assert_eq!(restored.current_stage_index, 1);
+    assert_eq!(
+      restored.intended_commands[&2].target_position,
+      Some(Position { x: 64, y: 32 })
+    );
+    assert_eq!(
+      snapshot.unbuildable_tiles(),
+      vec![TilePosition { x: 2, y: 0 }, TilePosition { x: 3, y: 1 }]
+    );
+  }
+
+  #[test]
+  fn restores_strategy_stages_gas_and_terrain() {
+    let world = sample_world();
+    let mut state = sample_state(0);
+    state.strategy = "two_gate_zealot";
+    state.build_stages = build_stages::get_strategy_stages("two_gate_zealot");
+    state
+      .build_stages
+      .insert(0, build_stages::get_detection_stage(3));
+    state.workers.gas_assignments.insert(2, 9);
+    let snapshot =
+      WorldSnapshot::from_json(&WorldSnapshot::capture(&world, &state).to_json()).unwrap();
+
+    let mut restored = GameState::default();
+    snapshot.apply_to(&mut restored).unwrap();
+
+    assert_eq!(restored.strategy, "two_gate_zealot");
+    let names = |stages: &[BuildStage]| stages.iter().map(|s| s.name.clone()).collect::<Vec<_>>();
+    assert_eq!(names(&restored.build_stages), names(&state.build_stages));
+    assert_eq!(
+      restored.build_stages[0].desired_counts[&UnitType::Protoss_Photon_Cannon],
+      3
+    );
+    assert_eq!(restored.workers.gas_assignments[&2], 9);
+    assert_eq!(restored.terrain.grid.walkable, sample_grid().walkable);
+    assert_eq!(restored.terrain.grid.buildable, sample_grid().buildable);
+  }
+
+  #[test]
+  fn hand_written_snapshots_use_defaults() {
+    let snapshot = WorldSnapshot::from_json(
+      r#"{
+        "frame": 100, "map_width": 64, "map_height": 64,
+        "minerals": 50, "gas": 0, "supply_used": 8, "supply_total": 18,
+        "start_location": [10, 10],
+        "units": [{ "id": 1, "unit_type": "Protoss_Nexus", "position": [384, 368], "tile": [10, 10] }]
+      }"#,
+    )
+    .unwrap();
+
+    let nexus = snapshot.units[0].to_info().unwrap();
+    let mut state = GameState::default();
+    snapshot.apply_to(&mut state).unwrap();
+    assert!(nexus.is_completed);
+    assert_eq!(state.strategy, DEFAULT_STRATEGY);
+    assert_eq!(
+      state.build_stages.len(),
+      build_stages::get_build_stages().len()
+    );
+    assert_eq!(nexus.order, Order::None);
+    assert!(snapshot.unbuildable_tiles().is_empty());
+  }
+
+  #[test]
+  fn unknown_unit_types_are_rejected() {
+    let mut unit = UnitSnapshot::from_info(&sample_world().units[0]);
+    unit.unit_type = "Protoss_Mothership".to_string();
+
+    assert!(unit.to_info().is_err());
+  }
+}
@@ -0,0 +1,84 @@
+//! Replays recorded game snapshots through the planners and checks the commands they
+//! issue. To turn a live game into a new scenario, save the output of
+//! `GET http://127.0.0.1:3333/api/snapshot` into `tests/snapshots/` and add a test here.
+
+use protossbot_core::{
+  state::game_state::GameState,
+  types::{Order, UnitType},
+  utils::{build_manager, worker_management},
+  world::{
+    fake_world::{FakeCommand, FakeWorld},
+    snapshot::WorldSnapshot,
+    GameWorld,
+  },
+};
+
+/// Runs one frame of planning on the named snapshot
+fn run_scenario(name: &str) -> (FakeWorld, GameState) {
+  let path = format!(
+    "{}/tests/snapshots/{}.json",
+    env!("CARGO_MANIFEST_DIR"),
+    name
+  );
+  let snapshot = WorldSnapshot::load(&path).unwrap();
+  let world = FakeWorld::from_snapshot(&snapshot).unwrap();
+  let mut state = GameState::default();
+  snapshot.apply_to(&mut state).unwrap();
+
+  build_manager::on_frame(&world, &mut state);
+  worker_management::assign_idle_workers_to_minerals(&world, &mut state);
+
+  (world, state)
+}
+
+#[test]
+fn idle_probe_returns_to_mining() {
+  let (world, state) = run_scenario("idle_probe_returns_to_mining");
+
+  assert_eq!(
+    world.issued(),
+    vec![FakeCommand::Gather {
+      unit_id: 5,
+      target_id: 25
+    }]
+  );
+  assert_eq!(state.intended_commands[&5].order, Order::MiningMinerals);
+}
+
+#[test]
+fn supply_block_builds_pylon() {
+  let (world, state) = run_scenario("supply_block_builds_pylon");
+
+  let issued = world.issued();
+  assert_eq!(issued.len(), 1, "{:?}", issued);
+  let FakeCommand::Build {
+    unit_id,
+    unit_type,
+    tile,
+  } = issued[0]
+  else {
+    panic!("expected a build command, got {:?}", issued[0]);
+  };
+  assert_eq!(unit_type, UnitType::Protoss_Pylon);
+  assert!(
+    tile.x >= 9,
+    "pylon placed on unbuildable ground at {:?}",
+    tile
+  );
+  assert!(world.can_build_here(unit_id, tile, unit_type));
+  assert_eq!(
+    state.intended_commands[&unit_id].order,
+    Order::PlaceBuilding
+  );
+}
+
+#[test]
+fn walking_builder_is_not_reassigned() {
+  let (world, state) = run_scenario("walking_builder_is_not_reassigned");
+
+  assert!(world.issued().iter().all(|cmd| match cmd {
+    FakeCommand::Build { unit_id, .. } | FakeCommand::Gather { unit_id, .. } => *unit_id != 2,
+    FakeCommand::Train { .. } => true,
+  }));
+  assert_eq!(state.intended_commands[&2].order, Order::PlaceBuilding);
+}
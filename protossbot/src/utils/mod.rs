@@ -1,3 +0,0 @@
-pub mod build_location_utils;
-pub mod build_manager;
-pub mod worker_management;